@@ -1,15 +1,28 @@
 use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
 use std::hash::{Hash, Hasher};
 use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use arboard::{Clipboard, ImageData};
 use base64::{engine::general_purpose, Engine as _};
-use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
 
-#[derive(Serialize, Clone)]
+const DEFAULT_CLIPBOARD_HISTORY_SIZE: usize = 50;
+const DEFAULT_PAUSE_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_MAX_CONTENT_BYTES: usize = 1_000_000;
+const DEFAULT_MAX_IMAGE_BYTES: usize = 50 * 1024 * 1024;
+const DEFAULT_DEDUPLICATION_WINDOW_MS: u64 = 200;
+
+static CLIPBOARD_MONITORING_PAUSED: AtomicBool = AtomicBool::new(false);
+/// Bumped on every pause so an older auto-resume timer can't end a newer pause early.
+static PAUSE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ClipboardUpdate {
     pub id: String,
     #[serde(rename = "type")]
@@ -18,6 +31,171 @@ pub struct ClipboardUpdate {
     pub ts_ms: u128,
 }
 
+/// Most recent clipboard items, newest first. Managed as `Mutex<ClipboardHistory>` state.
+pub struct ClipboardHistory {
+    items: VecDeque<ClipboardUpdate>,
+    capacity: usize,
+}
+
+impl ClipboardHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, item: ClipboardUpdate) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.items.len() >= self.capacity {
+            self.items.pop_back();
+        }
+        self.items.push_front(item);
+    }
+
+    pub fn items(&self) -> Vec<ClipboardUpdate> {
+        self.items.iter().cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+fn history_capacity(app: &AppHandle) -> usize {
+    crate::commands::settings::get_setting(app.clone(), "clipboardHistorySize".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .map(|size| size as usize)
+        .unwrap_or(DEFAULT_CLIPBOARD_HISTORY_SIZE)
+}
+
+/// Record an item in the history and broadcast it to the renderer.
+fn publish(app: &AppHandle, update: ClipboardUpdate) {
+    let history_len = app
+        .try_state::<Mutex<ClipboardHistory>>()
+        .and_then(|history| {
+            let mut history = history.lock().ok()?;
+            history.push(update.clone());
+            Some(history.len())
+        });
+
+    if let Err(err) = crate::commands::database::db_save_clipboard_item(app.clone(), update.clone())
+    {
+        crate::commands::logging::component_log(
+            app,
+            "clipboard",
+            "error",
+            &format!("failed to persist clipboard item: {}", err),
+        );
+    }
+
+    let _ = app.emit("clipboard-update", update);
+    if let Some(count) = history_len {
+        let _ = app.emit(
+            "clipboard-history-updated",
+            serde_json::json!({ "count": count }),
+        );
+    }
+}
+
+fn pause_timeout_ms(app: &AppHandle) -> u64 {
+    crate::commands::settings::get_setting(
+        app.clone(),
+        "clipboardMonitoringPauseTimeoutMs".to_string(),
+    )
+    .ok()
+    .flatten()
+    .and_then(|v| v.as_u64())
+    .unwrap_or(DEFAULT_PAUSE_TIMEOUT_MS)
+}
+
+/// Stop reading the clipboard (e.g. while the user types a password). Monitoring resumes
+/// automatically after `clipboardMonitoringPauseTimeoutMs`; 0 disables the auto-resume.
+pub fn pause_monitoring(app: &AppHandle) {
+    let generation = PAUSE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    CLIPBOARD_MONITORING_PAUSED.store(true, Ordering::SeqCst);
+    let _ = app.emit("clipboard-monitoring-paused", ());
+
+    let timeout_ms = pause_timeout_ms(app);
+    if timeout_ms == 0 {
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(timeout_ms));
+        if PAUSE_GENERATION.load(Ordering::SeqCst) == generation
+            && CLIPBOARD_MONITORING_PAUSED.load(Ordering::SeqCst)
+        {
+            crate::commands::logging::component_log(
+                &app,
+                "clipboard",
+                "info",
+                &format!("auto-resuming monitoring after {}ms", timeout_ms),
+            );
+            resume_monitoring(&app);
+        }
+    });
+}
+
+pub fn resume_monitoring(app: &AppHandle) {
+    if CLIPBOARD_MONITORING_PAUSED.swap(false, Ordering::SeqCst) {
+        let _ = app.emit("clipboard-monitoring-resumed", ());
+    }
+}
+
+fn file_detection_enabled(app: &AppHandle) -> bool {
+    crate::commands::settings::get_setting(app.clone(), "clipboardFileDetection".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Classify copied text as `"file"` when it looks like a single file path or `file://` URI.
+fn detect_clipboard_item_type(text: &str) -> &'static str {
+    let candidate = text.trim();
+    if candidate.is_empty() || candidate.contains('\n') {
+        return "text";
+    }
+
+    if candidate.starts_with("file://") {
+        return "file";
+    }
+
+    // Unix-style absolute paths only count if they exist, so "/shrug" stays text.
+    if candidate.starts_with('/') && std::path::Path::new(candidate).exists() {
+        return "file";
+    }
+
+    // Windows drive paths: `C:\...` or `C:/...`.
+    let bytes = candidate.as_bytes();
+    if bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/')
+    {
+        return "file";
+    }
+
+    "text"
+}
+
+fn text_item_type(app: &AppHandle, text: &str) -> String {
+    if file_detection_enabled(app) {
+        detect_clipboard_item_type(text).to_string()
+    } else {
+        "text".to_string()
+    }
+}
+
 fn now_ms() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -31,7 +209,86 @@ fn hash_text(text: &str) -> u64 {
     hasher.finish()
 }
 
-fn image_to_data_url(img: ImageData<'static>) -> Option<(u64, String)> {
+fn usize_setting(app: &AppHandle, key: &str, default: usize) -> usize {
+    crate::commands::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .map(|value| value as usize)
+        .unwrap_or(default)
+}
+
+/// Cap text items at `clipboardMaxContentBytes` so huge pastes don't stall the renderer.
+fn limit_text(app: &AppHandle, mut content: String) -> String {
+    let max_bytes = usize_setting(app, "clipboardMaxContentBytes", DEFAULT_MAX_CONTENT_BYTES);
+    if content.len() <= max_bytes {
+        return content;
+    }
+
+    crate::commands::logging::component_log(
+        app,
+        "clipboard",
+        "warn",
+        &format!(
+            "text item is {} bytes; truncating to {} bytes",
+            content.len(),
+            max_bytes
+        ),
+    );
+    let mut end = max_bytes;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    content.truncate(end);
+    content.push_str("...[truncated]");
+    content
+}
+
+/// Suppresses re-emitting the same item when an app rewrites the clipboard several times
+/// in quick succession (common with password managers).
+struct EmitDeduplicator {
+    last_emit_hash: u64,
+    last_emit_ts_ms: u128,
+}
+
+impl EmitDeduplicator {
+    fn new() -> Self {
+        Self {
+            last_emit_hash: 0,
+            last_emit_ts_ms: 0,
+        }
+    }
+
+    /// Returns false for a repeat of the last item inside the window; otherwise records it.
+    fn should_emit(&mut self, app: &AppHandle, hash: u64, ts_ms: u128) -> bool {
+        let window_ms = crate::commands::settings::get_setting(
+            app.clone(),
+            "clipboardDeduplicationWindowMs".to_string(),
+        )
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_DEDUPLICATION_WINDOW_MS);
+
+        if hash == self.last_emit_hash
+            && ts_ms.saturating_sub(self.last_emit_ts_ms) < window_ms as u128
+        {
+            crate::commands::logging::component_log(
+                app,
+                "clipboard",
+                "warn",
+                &format!("skipping duplicate update within {}ms", window_ms),
+            );
+            return false;
+        }
+
+        self.last_emit_hash = hash;
+        self.last_emit_ts_ms = ts_ms;
+        true
+    }
+}
+
+fn image_hash(img: &ImageData<'_>) -> u64 {
     let mut hasher = DefaultHasher::new();
     img.width.hash(&mut hasher);
     img.height.hash(&mut hasher);
@@ -41,8 +298,31 @@ fn image_to_data_url(img: ImageData<'static>) -> Option<(u64, String)> {
         img.bytes[img.bytes.len() / 2].hash(&mut hasher);
         img.bytes[img.bytes.len() - 1].hash(&mut hasher);
     }
-    let hash = hasher.finish();
+    hasher.finish()
+}
+
+/// Item type and content for a clipboard image. Images above `clipboardMaxImageBytes`
+/// (raw RGBA) are reported as `image-too-large` without being encoded.
+fn image_payload(app: &AppHandle, img: ImageData<'static>) -> Option<(String, String)> {
+    let max_bytes = usize_setting(app, "clipboardMaxImageBytes", DEFAULT_MAX_IMAGE_BYTES);
+    if img.bytes.len() > max_bytes {
+        crate::commands::logging::component_log(
+            app,
+            "clipboard",
+            "warn",
+            &format!(
+                "image is {} bytes (limit {}); skipping encode",
+                img.bytes.len(),
+                max_bytes
+            ),
+        );
+        return Some(("image-too-large".to_string(), String::new()));
+    }
 
+    image_to_data_url(img).map(|data_url| ("image".to_string(), data_url))
+}
+
+fn image_to_data_url(img: ImageData<'static>) -> Option<String> {
     let rgba = image::RgbaImage::from_raw(
         img.width as u32,
         img.height as u32,
@@ -55,21 +335,30 @@ fn image_to_data_url(img: ImageData<'static>) -> Option<(u64, String)> {
         .write_to(&mut Cursor::new(&mut png_bytes), image::ImageFormat::Png)
         .ok()?;
     let b64 = general_purpose::STANDARD.encode(png_bytes);
-    let data_url = format!("data:image/png;base64,{b64}");
-    Some((hash, data_url))
+    Some(format!("data:image/png;base64,{b64}"))
 }
 
 pub fn start(app: AppHandle) {
+    if app.try_state::<Mutex<ClipboardHistory>>().is_none() {
+        app.manage(Mutex::new(ClipboardHistory::new(history_capacity(&app))));
+    }
+
     thread::spawn(move || {
         let clipboard = Clipboard::new();
         if clipboard.is_err() {
-            eprintln!("Failed to init clipboard: {:?}", clipboard.err());
+            crate::commands::logging::component_log(
+                &app,
+                "clipboard",
+                "error",
+                &format!("Failed to init clipboard: {:?}", clipboard.err()),
+            );
             return;
         }
         let mut clipboard = clipboard.unwrap();
 
         let mut last_text = String::new();
         let mut last_image_hash: u64 = 0;
+        let mut last_html_hash: u64 = 0;
 
         // Emit current clipboard content on startup so UI can populate quickly.
         if let Ok(content) = clipboard.get_text() {
@@ -77,60 +366,108 @@ pub fn start(app: AppHandle) {
                 last_text = content.clone();
                 let hash = hash_text(&content);
                 let ts_ms = now_ms();
-                let _ = app.emit(
-                    "clipboard-update",
+                publish(
+                    &app,
                     ClipboardUpdate {
                         id: format!("{ts_ms}-{hash}"),
-                        item_type: "text".to_string(),
-                        content,
+                        item_type: text_item_type(&app, &content),
+                        content: limit_text(&app, content),
                         ts_ms,
                     },
                 );
             }
         } else if let Ok(img) = clipboard.get_image() {
-            if let Some((hash, data_url)) = image_to_data_url(img) {
+            let hash = image_hash(&img);
+            if let Some((item_type, content)) = image_payload(&app, img) {
                 last_image_hash = hash;
                 let ts_ms = now_ms();
-                let _ = app.emit(
-                    "clipboard-update",
+                publish(
+                    &app,
                     ClipboardUpdate {
                         id: format!("{ts_ms}-{hash}"),
-                        item_type: "image".to_string(),
-                        content: data_url,
+                        item_type,
+                        content,
                         ts_ms,
                     },
                 );
             }
         }
 
+        let mut dedup = EmitDeduplicator::new();
+        let mut was_paused = false;
         loop {
+            if CLIPBOARD_MONITORING_PAUSED.load(Ordering::SeqCst) {
+                was_paused = true;
+                thread::sleep(Duration::from_millis(500));
+                continue;
+            }
+
+            // Whatever was copied during the pause (likely the password that prompted it)
+            // becomes the new baseline instead of being emitted.
+            if was_paused {
+                was_paused = false;
+                if let Ok(content) = clipboard.get_text() {
+                    last_text = content;
+                } else if let Ok(img) = clipboard.get_image() {
+                    last_image_hash = image_hash(&img);
+                    last_text.clear();
+                }
+                thread::sleep(Duration::from_millis(500));
+                continue;
+            }
+
             if let Ok(content) = clipboard.get_text() {
                 if content != last_text && !content.is_empty() {
                     last_text = content.clone();
                     let hash = hash_text(&content);
                     let ts_ms = now_ms();
-                    let _ = app.emit(
-                        "clipboard-update",
-                        ClipboardUpdate {
-                            id: format!("{ts_ms}-{hash}"),
-                            item_type: "text".to_string(),
-                            content,
-                            ts_ms,
-                        },
-                    );
+                    if dedup.should_emit(&app, hash, ts_ms) {
+                        publish(
+                            &app,
+                            ClipboardUpdate {
+                                id: format!("{ts_ms}-{hash}"),
+                                item_type: text_item_type(&app, &content),
+                                content: limit_text(&app, content),
+                                ts_ms,
+                            },
+                        );
+                    }
                 }
             } else if let Ok(img) = clipboard.get_image() {
-                if let Some((hash, data_url)) = image_to_data_url(img) {
-                    if hash != last_image_hash {
-                        last_image_hash = hash;
-                        last_text.clear();
-                        let ts_ms = now_ms();
-                        let _ = app.emit(
-                            "clipboard-update",
+                // Hash before encoding so an unchanged image isn't re-encoded every poll.
+                let hash = image_hash(&img);
+                if hash != last_image_hash {
+                    last_image_hash = hash;
+                    last_text.clear();
+                    let ts_ms = now_ms();
+                    if dedup.should_emit(&app, hash, ts_ms) {
+                        if let Some((item_type, content)) = image_payload(&app, img) {
+                            publish(
+                                &app,
+                                ClipboardUpdate {
+                                    id: format!("{ts_ms}-{hash}"),
+                                    item_type,
+                                    content,
+                                    ts_ms,
+                                },
+                            );
+                        }
+                    }
+                }
+            } else if let Ok(html) = clipboard.get().html() {
+                // HTML without a plain-text flavor (rare, but some editors do it).
+                let hash = hash_text(&html);
+                if hash != last_html_hash && !html.is_empty() {
+                    last_html_hash = hash;
+                    last_text.clear();
+                    let ts_ms = now_ms();
+                    if dedup.should_emit(&app, hash, ts_ms) {
+                        publish(
+                            &app,
                             ClipboardUpdate {
                                 id: format!("{ts_ms}-{hash}"),
-                                item_type: "image".to_string(),
-                                content: data_url,
+                                item_type: "html".to_string(),
+                                content: html,
                                 ts_ms,
                             },
                         );
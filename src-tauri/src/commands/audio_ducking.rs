@@ -16,6 +16,8 @@ static MUTE_STATE: Mutex<Option<SystemMuteState>> = Mutex::new(None);
 
 const GUARD_FILE_NAME: &str = "audio_mute_guard.json";
 
+const DEFAULT_DUCK_VOLUME_PERCENT: f64 = 20.0;
+
 fn guard_path(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
     Ok(app_data_dir.join(GUARD_FILE_NAME))
@@ -54,8 +56,36 @@ fn mute_while_recording_enabled(app: &AppHandle) -> bool {
         .unwrap_or(true)
 }
 
+/// Opt-in: lower the output volume instead of muting it while recording.
+fn duck_while_recording_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "duckSystemAudioWhileRecording".to_string())
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Ducked volume as a fraction of the current one (`duckVolumePercent`, default 20).
+fn duck_level(app: &AppHandle) -> f32 {
+    super::settings::get_setting(app.clone(), "duckVolumePercent".to_string())
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_f64())
+        .unwrap_or(DEFAULT_DUCK_VOLUME_PERCENT)
+        .clamp(0.0, 100.0) as f32
+        / 100.0
+}
+
+fn duck_system_audio(state: &SystemMuteState, level: f32) -> Result<(), String> {
+    let volume = state
+        .volume
+        .ok_or_else(|| "Current output volume is unknown; cannot duck".to_string())?;
+    platform::set_output_volume(state, volume * level)
+}
+
 pub fn start_system_mute(app: &AppHandle) -> Result<(), String> {
-    if !mute_while_recording_enabled(app) {
+    let duck = duck_while_recording_enabled(app);
+    if !duck && !mute_while_recording_enabled(app) {
         return Ok(());
     }
 
@@ -69,14 +99,22 @@ pub fn start_system_mute(app: &AppHandle) -> Result<(), String> {
     let state = platform::capture_state()?;
     write_guard_file(app, &state)?;
 
-    if let Err(err) = platform::mute_system_audio() {
+    // The guard file is written first so a crash while ducked/muted is restored on next launch.
+    let result = if duck {
+        duck_system_audio(&state, duck_level(app))
+    } else {
+        platform::mute_system_audio()
+    };
+    if let Err(err) = result {
         remove_guard_file(app);
         return Err(err);
     }
 
     eprintln!(
-        "[audio-mute] system output muted; was_muted={}, volume={:?}",
-        state.was_muted, state.volume
+        "[audio-mute] system output {}; was_muted={}, volume={:?}",
+        if duck { "ducked" } else { "muted" },
+        state.was_muted,
+        state.volume
     );
     *guard = Some(state);
     Ok(())
@@ -212,6 +250,13 @@ mod platform {
             .map_err(|err| format!("Failed to mute system output: {err}"))
     }
 
+    pub fn set_output_volume(_state: &SystemMuteState, volume: f32) -> Result<(), String> {
+        let _com = ComGuard::initialize()?;
+        let endpoint = endpoint_volume()?;
+        unsafe { endpoint.SetMasterVolumeLevelScalar(volume.clamp(0.0, 1.0), null()) }
+            .map_err(|err| format!("Failed to set output volume: {err}"))
+    }
+
     pub fn restore_system_audio(state: &SystemMuteState) -> Result<(), String> {
         let _com = ComGuard::initialize()?;
         let endpoint = endpoint_volume()?;
@@ -266,6 +311,15 @@ mod platform {
         osascript("set volume output muted true").map(|_| ())
     }
 
+    /// `volume` is on osascript's 0-100 scale, same as the captured state.
+    pub fn set_output_volume(_state: &SystemMuteState, volume: f32) -> Result<(), String> {
+        let script = format!(
+            "set volume output volume {}",
+            volume.clamp(0.0, 100.0).round()
+        );
+        osascript(&script).map(|_| ())
+    }
+
     pub fn restore_system_audio(state: &SystemMuteState) -> Result<(), String> {
         if let Some(volume) = state.volume {
             let script = format!(
@@ -340,6 +394,20 @@ mod platform {
             .map(|_| ())
     }
 
+    pub fn set_output_volume(state: &SystemMuteState, volume: f32) -> Result<(), String> {
+        match state.backend.as_str() {
+            "linux-wpctl" => {
+                let value = volume.clamp(0.0, 1.5).to_string();
+                run("wpctl", &["set-volume", "@DEFAULT_AUDIO_SINK@", &value]).map(|_| ())
+            }
+            "linux-pactl" => {
+                let value = format!("{}%", (volume.clamp(0.0, 1.5) * 100.0).round());
+                run("pactl", &["set-sink-volume", "@DEFAULT_SINK@", &value]).map(|_| ())
+            }
+            other => Err(format!("Unsupported Linux audio backend: {other}")),
+        }
+    }
+
     pub fn restore_system_audio(state: &SystemMuteState) -> Result<(), String> {
         match state.backend.as_str() {
             "linux-wpctl" => {
@@ -395,6 +463,10 @@ mod platform {
         Ok(())
     }
 
+    pub fn set_output_volume(_state: &SystemMuteState, _volume: f32) -> Result<(), String> {
+        Ok(())
+    }
+
     pub fn restore_system_audio(_state: &SystemMuteState) -> Result<(), String> {
         Ok(())
     }
@@ -5,22 +5,127 @@ use serde::Serialize;
 use std::borrow::Cow;
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 use std::process::Command;
+use std::sync::Mutex;
 #[cfg(target_os = "macos")]
-use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+use crate::clipboard_listener::{ClipboardHistory, ClipboardUpdate};
+
 #[cfg(target_os = "macos")]
 #[link(name = "ApplicationServices", kind = "framework")]
 extern "C" {
     fn AXIsProcessTrusted() -> bool;
+    fn AXIsProcessTrustedWithOptions(options: *const std::ffi::c_void) -> bool;
+    static kAXTrustedCheckOptionPrompt: *const std::ffi::c_void;
+    fn AXUIElementCreateSystemWide() -> *const std::ffi::c_void;
+    fn AXUIElementCopyAttributeValue(
+        element: *const std::ffi::c_void,
+        attribute: *const std::ffi::c_void,
+        value: *mut *const std::ffi::c_void,
+    ) -> i32;
+    fn AXUIElementIsAttributeSettable(
+        element: *const std::ffi::c_void,
+        attribute: *const std::ffi::c_void,
+        settable: *mut u8,
+    ) -> i32;
+    fn AXUIElementSetAttributeValue(
+        element: *const std::ffi::c_void,
+        attribute: *const std::ffi::c_void,
+        value: *const std::ffi::c_void,
+    ) -> i32;
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFBooleanTrue: *const std::ffi::c_void;
+    static kCFTypeDictionaryKeyCallBacks: u8;
+    static kCFTypeDictionaryValueCallBacks: u8;
+    fn CFDictionaryCreate(
+        allocator: *const std::ffi::c_void,
+        keys: *const *const std::ffi::c_void,
+        values: *const *const std::ffi::c_void,
+        num_values: isize,
+        key_callbacks: *const u8,
+        value_callbacks: *const u8,
+    ) -> *const std::ffi::c_void;
+    fn CFRelease(cf: *const std::ffi::c_void);
 }
 
 const PASTE_PRE_DELAY_MS: u64 = 140;
 #[cfg(target_os = "macos")]
 const PASTE_RESTORE_DELAY_MS: u64 = 260;
+const MAX_PASTE_DELAY_MS: u64 = 5000;
+
+/// A delay setting in milliseconds, clamped to 0..=`MAX_PASTE_DELAY_MS`.
+fn paste_delay_setting(app: &AppHandle, key: &str, default_ms: u64) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_f64())
+        .map_or(default_ms, |ms| {
+            ms.clamp(0.0, MAX_PASTE_DELAY_MS as f64) as u64
+        });
+    Duration::from_millis(ms)
+}
+
+/// `restoreClipboard` (default on): put the user's previous clipboard back after pasting.
+#[cfg(target_os = "macos")]
+fn restore_clipboard_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "restoreClipboard".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Images bigger than this (raw RGBA) aren't preserved around a paste.
+#[cfg(target_os = "macos")]
+const MAX_SNAPSHOT_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// The clipboard's contents before a paste, to put back afterwards.
+#[cfg(target_os = "macos")]
+enum ClipboardSnapshot {
+    Text(String),
+    Image(ImageData<'static>),
+}
+
+/// Capture the clipboard's text, or its image when it holds no text.
+#[cfg(target_os = "macos")]
+fn snapshot_clipboard(app: &AppHandle) -> Option<ClipboardSnapshot> {
+    if let Ok(text) = app.clipboard().read_text() {
+        return Some(ClipboardSnapshot::Text(text));
+    }
+    let image = Clipboard::new().ok()?.get_image().ok()?;
+    if image.bytes.len() > MAX_SNAPSHOT_IMAGE_BYTES {
+        eprintln!(
+            "[clipboard] not preserving {}x{} clipboard image ({} bytes)",
+            image.width,
+            image.height,
+            image.bytes.len()
+        );
+        return None;
+    }
+    Some(ClipboardSnapshot::Image(image.to_owned_img()))
+}
+
+#[cfg(target_os = "macos")]
+fn restore_clipboard(app: &AppHandle, snapshot: ClipboardSnapshot) {
+    let restored = match snapshot {
+        ClipboardSnapshot::Text(text) => {
+            app.clipboard().write_text(text).map_err(|e| e.to_string())
+        }
+        ClipboardSnapshot::Image(image) => Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_image(image))
+            .map_err(|e| e.to_string()),
+    };
+    if let Err(err) = restored {
+        eprintln!("[clipboard] failed to restore the previous clipboard: {err}");
+    }
+}
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -66,13 +171,14 @@ fn open_accessibility_settings_best_effort() {
 }
 
 #[cfg(target_os = "macos")]
-fn simulate_paste_with_applescript() -> Result<(), String> {
-    let output = Command::new("osascript")
+async fn simulate_paste_with_applescript() -> Result<(), String> {
+    let output = tokio::process::Command::new("osascript")
         .args([
             "-e",
             "tell application \"System Events\" to key code 9 using command down",
         ])
         .output()
+        .await
         .map_err(|e| format!("Failed to launch osascript: {e}"))?;
 
     if output.status.success() {
@@ -125,25 +231,63 @@ fn simulate_paste_with_enigo() -> Result<(), String> {
     Ok(())
 }
 
-fn simulate_paste_best_effort(app: &AppHandle) -> Result<(), String> {
+fn click_key_with_enigo(key: Key, count: usize) -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+    for _ in 0..count {
+        enigo
+            .key(key, enigo::Direction::Click)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Press `key` `count` times in the focused app.
+pub async fn click_key(app: &AppHandle, key: Key, count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        ensure_accessibility_permission()?;
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
+        app.run_on_main_thread(move || {
+            let _ = tx.send(click_key_with_enigo(key, count));
+        })
+        .map_err(|e| e.to_string())?;
+
+        return rx
+            .await
+            .map_err(|e| format!("Failed to receive key result: {e}"))?;
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app;
+        click_key_with_enigo(key, count)
+    }
+}
+
+async fn simulate_paste_best_effort(app: &AppHandle) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
         ensure_accessibility_permission()?;
 
-        if let Err(err) = simulate_paste_with_applescript() {
+        if let Err(err) = simulate_paste_with_applescript().await {
             eprintln!("[clipboard] AppleScript paste failed, falling back to Enigo: {err}");
         } else {
             return Ok(());
         }
 
-        let (tx, rx) = mpsc::channel::<Result<(), String>>();
+        let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
         app.run_on_main_thread(move || {
             let _ = tx.send(simulate_paste_with_enigo());
         })
         .map_err(|e| e.to_string())?;
 
         return rx
-            .recv()
+            .await
             .map_err(|e| format!("Failed to receive paste result: {e}"))?;
     }
 
@@ -254,6 +398,40 @@ pub fn check_accessibility_permission(prompt: Option<bool>) -> Result<bool, Stri
     }
 }
 
+/// Asks macOS to show its Accessibility prompt for Typefree. The system only
+/// shows the dialog once per app; afterwards the user has to use System Settings.
+#[tauri::command]
+pub fn request_accessibility_permission() -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let granted = unsafe {
+            let keys = [kAXTrustedCheckOptionPrompt];
+            let values = [kCFBooleanTrue];
+            let options = CFDictionaryCreate(
+                std::ptr::null(),
+                keys.as_ptr(),
+                values.as_ptr(),
+                1,
+                &kCFTypeDictionaryKeyCallBacks,
+                &kCFTypeDictionaryValueCallBacks,
+            );
+            if options.is_null() {
+                return Err("Failed to build accessibility prompt options".to_string());
+            }
+            let granted = AXIsProcessTrustedWithOptions(options);
+            CFRelease(options);
+            granted
+        };
+        eprintln!("[clipboard] accessibility prompt requested (trusted: {granted})");
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        Ok(())
+    }
+}
+
 fn decode_data_url(data_url: &str) -> Result<Vec<u8>, String> {
     let trimmed = data_url.trim();
     let payload = match trimmed.find(',') {
@@ -272,6 +450,131 @@ pub fn write_clipboard(text: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Read the `text/html` flavor of the clipboard, if any.
+#[tauri::command]
+pub fn read_clipboard_html() -> Result<Option<String>, String> {
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    match clipboard.get().html() {
+        Ok(html) => Ok(Some(html)),
+        Err(arboard::Error::ContentNotAvailable) => Ok(None),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Write HTML together with a plain-text fallback so both rich and plain editors can paste.
+#[tauri::command]
+pub fn write_clipboard_html(html: String, fallback_text: String) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        write_clipboard_html_macos(&html, &fallback_text)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+        clipboard
+            .set_html(html, Some(fallback_text))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// arboard wraps HTML in its own `<html><head>` document on macOS; writing through
+/// NSPasteboard keeps the markup exactly as given.
+#[cfg(target_os = "macos")]
+fn write_clipboard_html_macos(html: &str, fallback_text: &str) -> Result<(), String> {
+    use objc2::exception;
+    use objc2_app_kit::{NSPasteboard, NSPasteboardTypeHTML, NSPasteboardTypeString};
+    use objc2_foundation::NSString;
+    use std::panic::AssertUnwindSafe;
+
+    let result = exception::catch(AssertUnwindSafe(|| {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        let html = NSString::from_str(html);
+        let text = NSString::from_str(fallback_text);
+        unsafe {
+            pasteboard.setString_forType(&html, NSPasteboardTypeHTML)
+                && pasteboard.setString_forType(&text, NSPasteboardTypeString)
+        }
+    }));
+
+    match result {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Failed to write HTML to the pasteboard".to_string()),
+        Err(exc) => Err(format!(
+            "Objective-C exception while writing HTML to the pasteboard: {:?}",
+            exc
+        )),
+    }
+}
+
+/// Identifier of the app that will receive a paste: the bundle identifier on macOS, the
+/// lowercased executable name (e.g. `slack.exe`) on Windows. `None` where unsupported.
+pub fn get_frontmost_app() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2::exception;
+        use objc2_app_kit::NSWorkspace;
+        use std::panic::AssertUnwindSafe;
+
+        exception::catch(AssertUnwindSafe(|| {
+            NSWorkspace::sharedWorkspace()
+                .frontmostApplication()
+                .and_then(|app| app.bundleIdentifier())
+                .map(|id| id.to_string())
+        }))
+        .ok()
+        .flatten()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::core::PWSTR;
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Threading::{
+            OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+            PROCESS_QUERY_LIMITED_INFORMATION,
+        };
+        use windows::Win32::UI::WindowsAndMessaging::{
+            GetForegroundWindow, GetWindowThreadProcessId,
+        };
+
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.is_invalid() {
+                return None;
+            }
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+            if pid == 0 {
+                return None;
+            }
+
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let mut buf = [0u16; 1024];
+            let mut len = buf.len() as u32;
+            let queried = QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(buf.as_mut_ptr()),
+                &mut len,
+            );
+            let _ = CloseHandle(process);
+            queried.ok()?;
+
+            let path = String::from_utf16_lossy(&buf[..len as usize]);
+            std::path::Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        None
+    }
+}
+
 fn copy_text_fallback(app: &AppHandle, text: &str) -> Result<(), String> {
     let plugin_result = app.clipboard().write_text(text.to_string());
     if plugin_result.is_ok() {
@@ -282,7 +585,11 @@ fn copy_text_fallback(app: &AppHandle, text: &str) -> Result<(), String> {
     clipboard.set_text(text).map_err(|e| e.to_string())
 }
 
-fn paste_clipboard_text(app: &AppHandle, text: &str, manual_shortcut: &str) -> Result<(), String> {
+async fn paste_clipboard_text(
+    app: &AppHandle,
+    text: &str,
+    manual_shortcut: &str,
+) -> Result<(), String> {
     let clipboard = app.clipboard();
     eprintln!("[clipboard] paste_text len={}", text.len());
 
@@ -294,9 +601,10 @@ fn paste_clipboard_text(app: &AppHandle, text: &str, manual_shortcut: &str) -> R
         })
         .map_err(|e| format!("Failed to write to clipboard: {e}"))?;
 
-    thread::sleep(Duration::from_millis(PASTE_PRE_DELAY_MS));
+    // `pasteDelayMs`: give the clipboard change time to land before sending the shortcut.
+    tokio::time::sleep(paste_delay_setting(app, "pasteDelayMs", PASTE_PRE_DELAY_MS)).await;
 
-    if let Err(err) = simulate_paste_best_effort(app) {
+    if let Err(err) = simulate_paste_best_effort(app).await {
         #[cfg(target_os = "macos")]
         if err.contains("Accessibility permission") {
             open_accessibility_settings_best_effort();
@@ -319,29 +627,231 @@ pub fn read_clipboard() -> Result<String, String> {
 }
 
 #[tauri::command]
-pub fn paste_text(app: AppHandle, text: String) -> Result<(), String> {
+pub async fn paste_text(app: AppHandle, text: String) -> Result<(), String> {
     if text.trim().is_empty() {
         return Ok(());
     }
 
     #[cfg(target_os = "macos")]
     {
-        let previous_clipboard_text = app.clipboard().read_text().ok();
-        paste_clipboard_text(&app, &text, "Cmd+V")?;
-        thread::sleep(Duration::from_millis(PASTE_RESTORE_DELAY_MS));
-        if let Some(previous) = previous_clipboard_text {
-            let _ = app.clipboard().write_text(previous);
+        paste_restoring_clipboard(
+            || {
+                restore_clipboard_enabled(&app)
+                    .then(|| snapshot_clipboard(&app))
+                    .flatten()
+            },
+            || paste_clipboard_text(&app, &text, "Cmd+V"),
+            // `clipboardRestoreDelayMs`: the target app has to read the clipboard first.
+            paste_delay_setting(&app, "clipboardRestoreDelayMs", PASTE_RESTORE_DELAY_MS),
+            |previous| restore_clipboard(&app, previous),
+        )
+        .await
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        paste_clipboard_text(&app, &text, "Ctrl+V").await
+    }
+}
+
+/// Take a snapshot, paste, and once `restore_delay` has passed put the snapshot back.
+/// A failed paste leaves the text on the clipboard for the user to paste manually.
+#[cfg(any(target_os = "macos", test))]
+async fn paste_restoring_clipboard<S, F>(
+    snapshot: impl FnOnce() -> Option<S>,
+    paste: impl FnOnce() -> F,
+    restore_delay: Duration,
+    restore: impl FnOnce(S),
+) -> Result<(), String>
+where
+    F: std::future::Future<Output = Result<(), String>>,
+{
+    let previous = snapshot();
+    paste().await?;
+    if let Some(previous) = previous {
+        tokio::time::sleep(restore_delay).await;
+        restore(previous);
+    }
+    Ok(())
+}
+
+/// Characters per synthesized text event when typing; longer runs drop characters in
+/// some apps.
+const TYPING_CHUNK_CHARS: usize = 24;
+const TYPING_CHUNK_DELAY: Duration = Duration::from_millis(15);
+
+/// A run of characters to type, or a line break to send as Return.
+enum TypingStep {
+    Text(String),
+    Return,
+}
+
+fn typing_steps(text: &str) -> Vec<TypingStep> {
+    let mut steps = Vec::new();
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            steps.push(TypingStep::Return);
+        }
+        let chars: Vec<char> = line.trim_end_matches('\r').chars().collect();
+        for chunk in chars.chunks(TYPING_CHUNK_CHARS) {
+            steps.push(TypingStep::Text(chunk.iter().collect()));
         }
+    }
+    steps
+}
 
-        Ok(())
+fn type_step_with_enigo(step: &TypingStep) -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| e.to_string())?;
+    match step {
+        TypingStep::Text(chunk) => enigo.text(chunk),
+        TypingStep::Return => enigo.key(Key::Return, enigo::Direction::Click),
+    }
+    .map_err(|e| e.to_string())
+}
+
+async fn type_step(app: &AppHandle, step: TypingStep) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
+        app.run_on_main_thread(move || {
+            let _ = tx.send(type_step_with_enigo(&step));
+        })
+        .map_err(|e| e.to_string())?;
+
+        return rx
+            .await
+            .map_err(|e| format!("Failed to receive typing result: {e}"))?;
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        paste_clipboard_text(&app, &text, "Ctrl+V")
+        let _ = app;
+        type_step_with_enigo(&step)
     }
 }
 
+/// Type `text` into the focused app with synthesized key events, for targets that ignore
+/// the paste shortcut (VNC sessions, some terminals, secure input fields). Newlines are
+/// sent as Return.
+#[tauri::command]
+pub async fn paste_text_by_typing(app: AppHandle, text: String) -> Result<(), String> {
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    #[cfg(target_os = "macos")]
+    ensure_accessibility_permission()?;
+
+    for (index, step) in typing_steps(&text).into_iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(TYPING_CHUNK_DELAY).await;
+        }
+        type_step(&app, step).await?;
+    }
+    Ok(())
+}
+
+/// How `insert_text` delivered text to the focused app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertMethod {
+    Accessibility,
+    Paste,
+}
+
+impl InsertMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Accessibility => "accessibility",
+            Self::Paste => "paste",
+        }
+    }
+}
+
+/// Insert `text` at the caret. With `accessibility` set, the Accessibility API is tried
+/// first; elements that don't accept it (and other platforms) fall back to `paste_text`.
+pub async fn insert_text(
+    app: &AppHandle,
+    text: &str,
+    accessibility: bool,
+) -> Result<InsertMethod, String> {
+    if accessibility {
+        match insert_text_via_accessibility(app, text).await {
+            Ok(true) => return Ok(InsertMethod::Accessibility),
+            Ok(false) => {
+                eprintln!("[clipboard] focused element doesn't accept AX insertion; pasting")
+            }
+            Err(err) => eprintln!("[clipboard] AX insertion failed ({}); pasting", err),
+        }
+    }
+    paste_text(app.clone(), text.to_string())
+        .await
+        .map(|_| InsertMethod::Paste)
+}
+
+/// Set `AXSelectedText` on the focused element (on the main thread). `Ok(false)` when
+/// there is no focused element or it doesn't accept the attribute.
+#[cfg(target_os = "macos")]
+async fn insert_text_via_accessibility(app: &AppHandle, text: &str) -> Result<bool, String> {
+    if text.trim().is_empty() {
+        return Ok(true);
+    }
+    ensure_accessibility_permission()?;
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<bool>();
+    let text = text.to_string();
+    app.run_on_main_thread(move || {
+        let _ = tx.send(unsafe { set_focused_selected_text(&text) });
+    })
+    .map_err(|e| e.to_string())?;
+
+    rx.await
+        .map_err(|e| format!("Failed to receive insertion result: {e}"))
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn insert_text_via_accessibility(_app: &AppHandle, _text: &str) -> Result<bool, String> {
+    Ok(false)
+}
+
+/// Replace the selection (an empty selection being the caret) of the system-wide focused
+/// element with `text`.
+#[cfg(target_os = "macos")]
+unsafe fn set_focused_selected_text(text: &str) -> bool {
+    use objc2::rc::Retained;
+    use objc2_foundation::NSString;
+
+    // NSString is toll-free bridged to CFString.
+    let as_cf = |string: &Retained<NSString>| Retained::as_ptr(string).cast::<std::ffi::c_void>();
+    let focused_attribute = NSString::from_str("AXFocusedUIElement");
+    let selected_text_attribute = NSString::from_str("AXSelectedText");
+    let value = NSString::from_str(text);
+
+    let system_wide = AXUIElementCreateSystemWide();
+    if system_wide.is_null() {
+        return false;
+    }
+    let mut focused: *const std::ffi::c_void = std::ptr::null();
+    let result =
+        AXUIElementCopyAttributeValue(system_wide, as_cf(&focused_attribute), &mut focused);
+    CFRelease(system_wide);
+    if result != 0 || focused.is_null() {
+        return false;
+    }
+
+    let mut settable: u8 = 0;
+    let inserted =
+        AXUIElementIsAttributeSettable(focused, as_cf(&selected_text_attribute), &mut settable)
+            == 0
+            && settable != 0
+            && AXUIElementSetAttributeValue(
+                focused,
+                as_cf(&selected_text_attribute),
+                as_cf(&value),
+            ) == 0;
+    CFRelease(focused);
+    inserted
+}
+
 #[tauri::command]
 pub fn write_clipboard_image(data_url: String) -> Result<(), String> {
     let png_bytes = decode_data_url(&data_url)?;
@@ -363,8 +873,97 @@ pub fn write_clipboard_image(data_url: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn paste_image(app: AppHandle, data_url: String) -> Result<(), String> {
+pub async fn paste_image(app: AppHandle, data_url: String) -> Result<(), String> {
     write_clipboard_image(data_url)?;
-    thread::sleep(Duration::from_millis(50));
-    simulate_paste_best_effort(&app)
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    simulate_paste_best_effort(&app).await
+}
+
+#[tauri::command]
+pub fn get_clipboard_history(app: AppHandle) -> Result<Vec<ClipboardUpdate>, String> {
+    let history = app.state::<Mutex<ClipboardHistory>>();
+    let history = history.lock().map_err(|e| e.to_string())?;
+    Ok(history.items())
+}
+
+#[tauri::command]
+pub fn clear_clipboard_history(app: AppHandle) -> Result<(), String> {
+    {
+        let history = app.state::<Mutex<ClipboardHistory>>();
+        let mut history = history.lock().map_err(|e| e.to_string())?;
+        history.clear();
+    }
+    let _ = app.emit(
+        "clipboard-history-updated",
+        serde_json::json!({ "count": 0 }),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub fn pause_clipboard_monitoring(app: AppHandle) -> Result<(), String> {
+    crate::clipboard_listener::pause_monitoring(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn resume_clipboard_monitoring(app: AppHandle) -> Result<(), String> {
+    crate::clipboard_listener::resume_monitoring(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::time::Instant;
+
+    const RESTORE_DELAY: Duration = Duration::from_millis(20);
+
+    fn run_paste(
+        snapshot: Option<&'static str>,
+        pasted: Result<(), String>,
+    ) -> (Result<(), String>, Vec<String>) {
+        let events = RefCell::new(Vec::new());
+        let pasted_at = RefCell::new(None);
+        let result = tauri::async_runtime::block_on(paste_restoring_clipboard(
+            || {
+                events.borrow_mut().push("snapshot".to_string());
+                snapshot
+            },
+            || async {
+                events.borrow_mut().push("paste".to_string());
+                *pasted_at.borrow_mut() = Some(Instant::now());
+                pasted
+            },
+            RESTORE_DELAY,
+            |previous| {
+                let waited = pasted_at.borrow().unwrap().elapsed();
+                assert!(waited >= RESTORE_DELAY, "restored after {waited:?}");
+                events.borrow_mut().push(format!("restore {previous}"));
+            },
+        ));
+        (result, events.into_inner())
+    }
+
+    #[test]
+    fn restores_the_snapshot_after_pasting() {
+        let (result, events) = run_paste(Some("before"), Ok(()));
+        assert_eq!(result, Ok(()));
+        assert_eq!(events, ["snapshot", "paste", "restore before"]);
+    }
+
+    #[test]
+    fn failed_paste_keeps_the_text_on_the_clipboard() {
+        let (result, events) = run_paste(Some("before"), Err("no permission".to_string()));
+        assert_eq!(result, Err("no permission".to_string()));
+        assert_eq!(events, ["snapshot", "paste"]);
+    }
+
+    #[test]
+    fn nothing_to_restore_without_a_snapshot() {
+        let (result, events) = run_paste(None, Ok(()));
+        assert_eq!(result, Ok(()));
+        assert_eq!(events, ["snapshot", "paste"]);
+    }
 }
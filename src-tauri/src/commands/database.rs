@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::clipboard_listener::ClipboardUpdate;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Transcription {
     pub id: i64,
@@ -14,6 +16,62 @@ pub struct Transcription {
     pub processing_method: String,
     pub agent_name: Option<String>,
     pub error: Option<String>,
+    pub audio_path: Option<String>,
+    /// Dictation profile that was active for the target app.
+    pub profile: Option<String>,
+    /// Milliseconds from the end of recording until the text was delivered.
+    pub latency_ms: Option<i64>,
+    pub word_count: Option<i64>,
+    /// Length of the recording, for dictations made by the backend pipeline.
+    pub audio_seconds: Option<f64>,
+}
+
+/// Dictations and words on one local calendar day (`YYYY-MM-DD`).
+#[derive(Debug, Serialize, Clone)]
+pub struct DailyDictationStats {
+    pub day: String,
+    pub sessions: i64,
+    pub words: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DictationStats {
+    pub sessions: i64,
+    pub total_words: i64,
+    pub total_audio_seconds: f64,
+    pub average_latency_ms: Option<f64>,
+    /// Consecutive days with at least one dictation, ending today or yesterday.
+    pub current_streak_days: i64,
+    pub by_day: Vec<DailyDictationStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PromptTemplate {
+    pub id: i64,
+    pub name: String,
+    pub system_prompt: String,
+    pub user_template: String,
+    pub created_at: String,
+}
+
+/// Token usage for one provider/model pair, summed over all recorded requests.
+#[derive(Debug, Serialize, Clone)]
+pub struct AiUsageByModel {
+    pub provider: String,
+    pub model: String,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AiUsageSummary {
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+    pub by_model: Vec<AiUsageByModel>,
 }
 
 pub struct Database {
@@ -51,10 +109,109 @@ pub fn init_database(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>>
         [],
     )?;
 
+    // Added after the initial schema; older databases need the column backfilled.
+    let has_audio_path = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcriptions') WHERE name = 'audio_path'")?
+        .exists([])?;
+    if !has_audio_path {
+        conn.execute("ALTER TABLE transcriptions ADD COLUMN audio_path TEXT", [])?;
+    }
+    let has_profile = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcriptions') WHERE name = 'profile'")?
+        .exists([])?;
+    if !has_profile {
+        conn.execute("ALTER TABLE transcriptions ADD COLUMN profile TEXT", [])?;
+    }
+    let has_latency = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcriptions') WHERE name = 'latency_ms'")?
+        .exists([])?;
+    if !has_latency {
+        conn.execute(
+            "ALTER TABLE transcriptions ADD COLUMN latency_ms INTEGER",
+            [],
+        )?;
+    }
+    let has_word_count = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcriptions') WHERE name = 'word_count'")?
+        .exists([])?;
+    if !has_word_count {
+        conn.execute(
+            "ALTER TABLE transcriptions ADD COLUMN word_count INTEGER",
+            [],
+        )?;
+        backfill_word_counts(&conn)?;
+    }
+    let has_audio_seconds = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcriptions') WHERE name = 'audio_seconds'")?
+        .exists([])?;
+    if !has_audio_seconds {
+        conn.execute(
+            "ALTER TABLE transcriptions ADD COLUMN audio_seconds REAL",
+            [],
+        )?;
+    }
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS clipboard_history (
+            id INTEGER PRIMARY KEY,
+            item_id TEXT UNIQUE,
+            item_type TEXT,
+            content TEXT,
+            ts_ms INTEGER
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS prompt_templates (
+            id INTEGER PRIMARY KEY,
+            name TEXT UNIQUE,
+            system_prompt TEXT,
+            user_template TEXT,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ai_usage (
+            id INTEGER PRIMARY KEY,
+            provider TEXT NOT NULL,
+            model TEXT NOT NULL,
+            input_tokens INTEGER NOT NULL,
+            output_tokens INTEGER NOT NULL,
+            cost_usd REAL NOT NULL,
+            ts DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
     app.manage(Database::new(db_path.to_str().unwrap())?);
     Ok(())
 }
 
+/// Rows saved before `word_count` existed; counted once so stats cover the whole history.
+fn backfill_word_counts(conn: &Connection) -> rusqlite::Result<()> {
+    let rows = conn
+        .prepare(
+            "SELECT id, COALESCE(processed_text, original_text) FROM transcriptions
+             WHERE word_count IS NULL",
+        )?
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, text) in rows {
+        tx.execute(
+            "UPDATE transcriptions SET word_count = ?1 WHERE id = ?2",
+            params![super::text_expander::count_words(&text) as i64, id],
+        )?;
+    }
+    tx.commit()
+}
+
 /// Save a new transcription
 #[tauri::command]
 pub fn db_save_transcription(
@@ -63,17 +220,21 @@ pub fn db_save_transcription(
     processed: Option<String>,
     method: Option<String>,
     agent_name: Option<String>,
+    audio_path: Option<String>,
+    profile: Option<String>,
 ) -> Result<i64, String> {
     let db = app.state::<Database>();
     let conn = db.conn.lock().map_err(|e| e.to_string())?;
 
     let is_processed = processed.is_some();
     let processing_method = method.clone().unwrap_or_else(|| "none".to_string());
+    let word_count =
+        super::text_expander::count_words(processed.as_deref().unwrap_or(&text)) as i64;
 
     conn.execute(
-        "INSERT INTO transcriptions (original_text, processed_text, is_processed, processing_method, agent_name)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![text, processed, is_processed, processing_method, agent_name],
+        "INSERT INTO transcriptions (original_text, processed_text, is_processed, processing_method, agent_name, audio_path, profile, word_count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![text, processed, is_processed, processing_method, agent_name, audio_path, profile, word_count],
     ).map_err(|e| e.to_string())?;
 
     let id = conn.last_insert_rowid();
@@ -81,7 +242,7 @@ pub fn db_save_transcription(
     // Get the saved transcription to emit
     let transcription = conn
         .query_row(
-            "SELECT id, timestamp, original_text, processed_text, is_processed, processing_method, agent_name, error 
+            "SELECT id, timestamp, original_text, processed_text, is_processed, processing_method, agent_name, error, audio_path, profile, latency_ms, word_count, audio_seconds
              FROM transcriptions WHERE id = ?1",
             [id],
             |row| {
@@ -94,6 +255,11 @@ pub fn db_save_transcription(
                     processing_method: row.get(5)?,
                     agent_name: row.get(6)?,
                     error: row.get(7)?,
+                    audio_path: row.get(8)?,
+                    profile: row.get(9)?,
+                    latency_ms: row.get(10)?,
+                    word_count: row.get(11)?,
+                    audio_seconds: row.get(12)?,
                 })
             },
         )
@@ -105,6 +271,51 @@ pub fn db_save_transcription(
     Ok(id)
 }
 
+/// Attach an error to a saved transcription (e.g. a post-processing step that failed
+/// after the raw text was already kept).
+pub fn set_transcription_error(app: &AppHandle, id: i64, error: &str) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE transcriptions SET error = ?1 WHERE id = ?2",
+        params![error, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Record how long a dictation took from the end of recording to delivery.
+pub fn set_transcription_latency(app: &AppHandle, id: i64, latency_ms: u64) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE transcriptions SET latency_ms = ?1 WHERE id = ?2",
+        params![latency_ms as i64, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Record the delivered text's word count and the recording length of a dictation.
+pub fn set_transcription_stats(
+    app: &AppHandle,
+    id: i64,
+    word_count: usize,
+    audio_seconds: Option<f64>,
+) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE transcriptions SET word_count = ?1, audio_seconds = ?2 WHERE id = ?3",
+        params![word_count as i64, audio_seconds, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 /// Get transcriptions with optional limit
 #[tauri::command]
 pub fn db_get_transcriptions(
@@ -116,7 +327,7 @@ pub fn db_get_transcriptions(
 
     let limit = limit.unwrap_or(100);
     let mut stmt = conn
-        .prepare("SELECT id, timestamp, original_text, processed_text, is_processed, processing_method, agent_name, error 
+        .prepare("SELECT id, timestamp, original_text, processed_text, is_processed, processing_method, agent_name, error, audio_path, profile, latency_ms, word_count, audio_seconds
                   FROM transcriptions ORDER BY timestamp DESC LIMIT ?1")
         .map_err(|e| e.to_string())?;
 
@@ -131,6 +342,11 @@ pub fn db_get_transcriptions(
                 processing_method: row.get(5)?,
                 agent_name: row.get(6)?,
                 error: row.get(7)?,
+                audio_path: row.get(8)?,
+                profile: row.get(9)?,
+                latency_ms: row.get(10)?,
+                word_count: row.get(11)?,
+                audio_seconds: row.get(12)?,
             })
         })
         .map_err(|e| e.to_string())?
@@ -140,6 +356,24 @@ pub fn db_get_transcriptions(
     Ok(transcriptions)
 }
 
+/// Get the kept recording for a transcription, if `keepRecordings` was on and the file
+/// has not been pruned since
+#[tauri::command]
+pub fn db_get_recording_path(app: AppHandle, id: i64) -> Result<Option<String>, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let audio_path: Option<String> = conn
+        .query_row(
+            "SELECT audio_path FROM transcriptions WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(audio_path.filter(|path| std::path::Path::new(path).exists()))
+}
+
 /// Delete a single transcription by ID
 #[tauri::command]
 pub fn db_delete_transcription(app: AppHandle, id: i64) -> Result<(), String> {
@@ -169,3 +403,331 @@ pub fn db_clear_transcriptions(app: AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+fn clipboard_persistence_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "persistClipboardHistory".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save a clipboard item. A no-op unless the user opted in via `persistClipboardHistory`,
+/// since clipboard contents are often sensitive.
+#[tauri::command]
+pub fn db_save_clipboard_item(app: AppHandle, item: ClipboardUpdate) -> Result<(), String> {
+    if !clipboard_persistence_enabled(&app) || item.content.is_empty() {
+        return Ok(());
+    }
+
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO clipboard_history (item_id, item_type, content, ts_ms)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![item.id, item.item_type, item.content, item.ts_ms as i64],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Get persisted clipboard items, newest first, optionally filtered by type ("text"/"image")
+#[tauri::command]
+pub fn db_get_clipboard_history(
+    app: AppHandle,
+    limit: Option<i32>,
+    item_type: Option<String>,
+) -> Result<Vec<ClipboardUpdate>, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let limit = limit.unwrap_or(100);
+    let mut stmt = conn
+        .prepare(
+            "SELECT item_id, item_type, content, ts_ms FROM clipboard_history
+             WHERE ?1 IS NULL OR item_type = ?1
+             ORDER BY ts_ms DESC LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let items = stmt
+        .query_map(params![item_type, limit], |row| {
+            Ok(ClipboardUpdate {
+                id: row.get(0)?,
+                item_type: row.get(1)?,
+                content: row.get(2)?,
+                ts_ms: row.get::<_, i64>(3)? as u128,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(items)
+}
+
+fn prompt_template_from_row(row: &rusqlite::Row) -> rusqlite::Result<PromptTemplate> {
+    Ok(PromptTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        system_prompt: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+        user_template: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+        created_at: row.get(4)?,
+    })
+}
+
+/// Save a new reasoning prompt template; names must be unique
+#[tauri::command]
+pub fn db_save_prompt_template(
+    app: AppHandle,
+    name: String,
+    system_prompt: String,
+    user_template: String,
+) -> Result<i64, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO prompt_templates (name, system_prompt, user_template) VALUES (?1, ?2, ?3)",
+        params![name, system_prompt, user_template],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// Get all prompt templates, sorted by name
+#[tauri::command]
+pub fn db_get_prompt_templates(app: AppHandle) -> Result<Vec<PromptTemplate>, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, system_prompt, user_template, created_at
+             FROM prompt_templates ORDER BY name COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let templates = stmt
+        .query_map([], prompt_template_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(templates)
+}
+
+/// Get a single prompt template by ID
+pub fn get_prompt_template(app: &AppHandle, id: i64) -> Result<PromptTemplate, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        "SELECT id, name, system_prompt, user_template, created_at
+         FROM prompt_templates WHERE id = ?1",
+        [id],
+        prompt_template_from_row,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => format!("Prompt template {} not found", id),
+        e => e.to_string(),
+    })
+}
+
+pub fn get_prompt_template_by_name(app: &AppHandle, name: &str) -> Result<PromptTemplate, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        "SELECT id, name, system_prompt, user_template, created_at
+         FROM prompt_templates WHERE name = ?1",
+        [name],
+        prompt_template_from_row,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => format!("Prompt template '{}' not found", name),
+        e => e.to_string(),
+    })
+}
+
+/// Update an existing prompt template
+#[tauri::command]
+pub fn db_update_prompt_template(
+    app: AppHandle,
+    id: i64,
+    name: String,
+    system_prompt: String,
+    user_template: String,
+) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let updated = conn
+        .execute(
+            "UPDATE prompt_templates SET name = ?1, system_prompt = ?2, user_template = ?3
+             WHERE id = ?4",
+            params![name, system_prompt, user_template, id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err(format!("Prompt template {} not found", id));
+    }
+    Ok(())
+}
+
+/// Delete a prompt template by ID
+#[tauri::command]
+pub fn db_delete_prompt_template(app: AppHandle, id: i64) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM prompt_templates WHERE id = ?1", [id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Log the token usage of one reasoning request
+pub fn record_ai_usage(
+    app: &AppHandle,
+    provider: &str,
+    model: &str,
+    usage: &super::reasoning::TokenUsage,
+) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO ai_usage (provider, model, input_tokens, output_tokens, cost_usd)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            provider,
+            model,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.estimated_cost_usd
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Get cumulative reasoning token usage and estimated cost, overall and per model
+#[tauri::command]
+pub fn db_get_ai_usage_summary(app: AppHandle) -> Result<AiUsageSummary, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT provider, model, COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(cost_usd)
+             FROM ai_usage GROUP BY provider, model ORDER BY SUM(cost_usd) DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let by_model = stmt
+        .query_map([], |row| {
+            Ok(AiUsageByModel {
+                provider: row.get(0)?,
+                model: row.get(1)?,
+                requests: row.get(2)?,
+                input_tokens: row.get(3)?,
+                output_tokens: row.get(4)?,
+                cost_usd: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(AiUsageSummary {
+        requests: by_model.iter().map(|m| m.requests).sum(),
+        input_tokens: by_model.iter().map(|m| m.input_tokens).sum(),
+        output_tokens: by_model.iter().map(|m| m.output_tokens).sum(),
+        cost_usd: by_model.iter().map(|m| m.cost_usd).sum(),
+        by_model,
+    })
+}
+
+/// Consecutive days ending today (or yesterday, while today is still open) in a list of
+/// "days ago" offsets sorted ascending.
+fn current_streak(days_ago: &[i64]) -> i64 {
+    let start = match days_ago.first() {
+        Some(&first @ (0 | 1)) => first,
+        _ => return 0,
+    };
+    days_ago
+        .iter()
+        .zip(start..)
+        .take_while(|(day, expected)| **day == *expected)
+        .count() as i64
+}
+
+/// Dictation totals over the last `range_days` local days (all time when omitted), with
+/// per-day buckets and the current streak.
+#[tauri::command]
+pub fn db_get_dictation_stats(
+    app: AppHandle,
+    range_days: Option<u32>,
+) -> Result<DictationStats, String> {
+    let db = app.state::<Database>();
+    let conn = db.conn.lock().map_err(|e| e.to_string())?;
+
+    let since = range_days.map(|days| format!("-{} days", days));
+    let (sessions, total_words, total_audio_seconds, average_latency_ms) = conn
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(word_count), 0), COALESCE(SUM(audio_seconds), 0),
+                    AVG(latency_ms)
+             FROM transcriptions
+             WHERE ?1 IS NULL OR date(timestamp, 'localtime') > date('now', 'localtime', ?1)",
+            [&since],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let by_day = conn
+        .prepare(
+            "SELECT date(timestamp, 'localtime') AS day, COUNT(*), COALESCE(SUM(word_count), 0)
+             FROM transcriptions
+             WHERE ?1 IS NULL OR date(timestamp, 'localtime') > date('now', 'localtime', ?1)
+             GROUP BY day ORDER BY day",
+        )
+        .map_err(|e| e.to_string())?
+        .query_map([&since], |row| {
+            Ok(DailyDictationStats {
+                day: row.get(0)?,
+                sessions: row.get(1)?,
+                words: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    // The streak looks at the whole history, not just the requested range.
+    let days_ago = conn
+        .prepare(
+            "SELECT DISTINCT CAST(julianday('now', 'localtime', 'start of day')
+                 - julianday(timestamp, 'localtime', 'start of day') AS INTEGER) AS days_ago
+             FROM transcriptions ORDER BY days_ago",
+        )
+        .map_err(|e| e.to_string())?
+        .query_map([], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(DictationStats {
+        sessions,
+        total_words,
+        total_audio_seconds,
+        average_latency_ms,
+        current_streak_days: current_streak(&days_ago),
+        by_day,
+    })
+}
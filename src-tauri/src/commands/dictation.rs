@@ -1,6 +1,145 @@
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use enigo::Key;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+
+use super::dictation_session::{
+    self, Active, ErrorMessage, NoData, ProgressStage, SessionStage, Text,
+};
+use super::recording::PermissionStatus;
+use super::sound_feedback::FeedbackSound;
+use super::text_expander::VoiceCommand;
+
+/// Text delivered by the last successful dictation in this session.
+#[derive(Default)]
+pub struct LastTranscription(Mutex<Option<String>>);
+
+fn ensure_last_transcription(app: &AppHandle) {
+    if app.try_state::<LastTranscription>().is_none() {
+        app.manage(LastTranscription::default());
+    }
+}
+
+fn last_transcription(app: &AppHandle) -> Option<String> {
+    ensure_last_transcription(app);
+    app.state::<LastTranscription>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn set_last_transcription(app: &AppHandle, text: &str) {
+    ensure_last_transcription(app);
+    *app.state::<LastTranscription>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(text.to_string());
+}
+
+fn take_last_transcription(app: &AppHandle) -> Option<String> {
+    ensure_last_transcription(app);
+    app.state::<LastTranscription>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+}
+
+/// Backspace over the previous dictation (`scratch that`). Only the last one can be
+/// scratched, so it's forgotten once deleted.
+async fn scratch_last_transcription(app: &AppHandle) -> Result<(), String> {
+    match take_last_transcription(app) {
+        Some(previous) => {
+            super::clipboard::click_key(app, Key::Backspace, previous.chars().count()).await
+        }
+        None => Ok(()),
+    }
+}
+
+/// Press Return for `new line` / `new paragraph`.
+async fn press_return_for(app: &AppHandle, command: VoiceCommand) -> Result<(), String> {
+    let count = match command {
+        VoiceCommand::NewLine => 1,
+        VoiceCommand::NewParagraph => 2,
+        VoiceCommand::ScratchThat => 0,
+    };
+    super::clipboard::click_key(app, Key::Return, count).await
+}
+
+/// The pending auto-hide of the main window; cancelled when a recording starts or another
+/// result is shown.
+#[derive(Default)]
+pub struct AutoHide(Mutex<Option<CancellationToken>>);
+
+fn ensure_auto_hide(app: &AppHandle) {
+    if app.try_state::<AutoHide>().is_none() {
+        app.manage(AutoHide::default());
+    }
+}
+
+/// Cancel the pending auto-hide, if any, and put `next` in its place.
+fn replace_auto_hide(app: &AppHandle, next: Option<CancellationToken>) {
+    ensure_auto_hide(app);
+    let state = app.state::<AutoHide>();
+    let mut pending = state
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(previous) = std::mem::replace(&mut *pending, next) {
+        previous.cancel();
+    }
+}
+
+fn cancel_auto_hide(app: &AppHandle) {
+    replace_auto_hide(app, None);
+}
+
+/// `autoHideAfterPasteMs`: hide the main window this long after a pasted result. 0 (the
+/// default) leaves it visible.
+fn auto_hide_delay(app: &AppHandle) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), "autoHideAfterPasteMs".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    Duration::from_millis(ms)
+}
+
+fn schedule_auto_hide(app: &AppHandle) {
+    let delay = auto_hide_delay(app);
+    if delay.is_zero() {
+        return;
+    }
+    let token = CancellationToken::new();
+    replace_auto_hide(app, Some(token.clone()));
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::select! {
+            _ = token.cancelled() => return,
+            _ = tokio::time::sleep(delay) => {}
+        }
+        if let Some(main_window) = app.get_webview_window("main") {
+            if let Err(err) = super::window::hide_window(main_window.as_ref().window()) {
+                eprintln!("[dictation] failed to auto-hide main window: {}", err);
+            }
+        }
+    });
+}
+
+/// Payload of `backend-dictation-result`.
+#[derive(Clone, Serialize)]
+pub(crate) struct DictationResultPayload {
+    text: String,
+    /// True when the text was re-delivered by `repeat_last_dictation`.
+    repeated: bool,
+    /// How the text was delivered: `accessibility`, `paste`, `typing` or `clipboard` (left
+    /// there for the user to paste).
+    method: &'static str,
+}
 
 fn get_setting_string(app: &AppHandle, key: &str) -> Option<String> {
     super::settings::get_setting(app.clone(), key.to_string())
@@ -9,7 +148,179 @@ fn get_setting_string(app: &AppHandle, key: &str) -> Option<String> {
         .and_then(|v| v.as_str().map(|s| s.to_string()))
 }
 
-#[cfg(target_os = "macos")]
+/// Dictation actions that can be bound to their own global hotkey.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DictationAction {
+    Toggle,
+    Start,
+    Stop,
+    Cancel,
+    /// Cancel whatever dictation is doing (recording or processing) and discard it.
+    CancelDictation,
+    PasteLast,
+    RepeatLast,
+    CycleLanguage,
+}
+
+impl DictationAction {
+    pub fn parse(action: &str) -> Result<Self, String> {
+        match action.trim().to_ascii_lowercase().as_str() {
+            "toggle" => Ok(Self::Toggle),
+            "start" => Ok(Self::Start),
+            "stop" => Ok(Self::Stop),
+            "cancel" => Ok(Self::Cancel),
+            "cancel-dictation" => Ok(Self::CancelDictation),
+            "paste-last" => Ok(Self::PasteLast),
+            "repeat-last" => Ok(Self::RepeatLast),
+            "cycle-language" => Ok(Self::CycleLanguage),
+            other => Err(format!(
+                "Unknown hotkey action '{}'. Expected toggle, start, stop, cancel, cancel-dictation, paste-last, repeat-last or cycle-language.",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Cancel => "cancel",
+            Self::CancelDictation => "cancel-dictation",
+            Self::PasteLast => "paste-last",
+            Self::RepeatLast => "repeat-last",
+            Self::CycleLanguage => "cycle-language",
+        }
+    }
+}
+
+/// Paste the most recent transcription at the cursor: this session's last result, or
+/// (right after launch) the newest saved one, processed text when available.
+#[tauri::command]
+pub async fn paste_last_transcription(app: AppHandle) -> Result<(), String> {
+    if let Some(text) = last_transcription(&app) {
+        return super::clipboard::paste_text(app, text).await;
+    }
+    let last = super::database::db_get_transcriptions(app.clone(), Some(1))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No transcription to paste yet".to_string())?;
+    let text = last
+        .processed_text
+        .filter(|text| !text.trim().is_empty())
+        .unwrap_or(last.original_text);
+    super::clipboard::paste_text(app, text).await
+}
+
+/// Paste the last transcript of this session again, e.g. after it landed in the wrong window.
+#[tauri::command]
+pub async fn repeat_last_dictation(app: AppHandle) -> Result<(), String> {
+    let text = last_transcription(&app)
+        .ok_or_else(|| "No dictation to repeat in this session".to_string())?;
+    let method =
+        super::clipboard::insert_text(&app, &text, accessibility_insert_enabled(&app)).await?;
+    dictation_session::emit(
+        &app,
+        "backend-dictation-result",
+        DictationResultPayload {
+            text,
+            repeated: true,
+            method: method.as_str(),
+        },
+    );
+    Ok(())
+}
+
+/// Per-app overrides from the `dictationProfiles` setting, keyed by the frontmost app
+/// (bundle identifier on macOS, executable name on Windows) with a `default` fallback.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct DictationProfile {
+    #[serde(skip)]
+    name: String,
+    /// Overrides the `pasteMode` setting.
+    paste_mode: Option<String>,
+    /// Agent (prompt template) to run; an empty string disables `postProcessWithAgent`.
+    post_process_agent: Option<String>,
+    /// Processing mode override, e.g. `direct` for raw text.
+    processing_mode: Option<String>,
+    language: Option<String>,
+}
+
+/// How a finished transcription reaches the focused app (`pasteMode` setting).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PasteMode {
+    /// Put the text on the clipboard, send the paste shortcut, then restore the clipboard.
+    Paste,
+    /// Leave the text on the clipboard for the user to paste themselves.
+    CopyOnly,
+    /// Type the text with synthesized key events, for apps that ignore the paste shortcut.
+    Type,
+}
+
+impl PasteMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "paste" => Some(Self::Paste),
+            "copy_only" | "copy" => Some(Self::CopyOnly),
+            "type" => Some(Self::Type),
+            _ => None,
+        }
+    }
+}
+
+/// `autoPaste` (default on): off leaves each transcription on the clipboard for review.
+fn auto_paste_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "autoPaste".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// `insertMode`: `accessibility` inserts through the macOS Accessibility API where the
+/// focused field supports it; anything else (the default) pastes via the clipboard.
+fn accessibility_insert_enabled(app: &AppHandle) -> bool {
+    get_setting_string(app, "insertMode")
+        .is_some_and(|mode| mode.trim().eq_ignore_ascii_case("accessibility"))
+}
+
+fn configured_paste_mode(app: &AppHandle) -> PasteMode {
+    super::settings::get_setting(app.clone(), "pasteMode".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().and_then(PasteMode::parse))
+        .unwrap_or(PasteMode::Paste)
+}
+
+fn resolve_dictation_profile(app: &AppHandle) -> Option<DictationProfile> {
+    let profiles = super::settings::get_setting(app.clone(), "dictationProfiles".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_object().cloned())?;
+    let frontmost = super::clipboard::get_frontmost_app();
+
+    let (name, value) = frontmost
+        .as_deref()
+        .and_then(|app_id| {
+            profiles
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(app_id))
+        })
+        .or_else(|| profiles.get_key_value("default"))?;
+
+    match serde_json::from_value::<DictationProfile>(value.clone()) {
+        Ok(profile) => Some(DictationProfile {
+            name: name.clone(),
+            ..profile
+        }),
+        Err(err) => {
+            eprintln!("[dictation] invalid dictation profile '{}': {}", name, err);
+            None
+        }
+    }
+}
+
 fn resolve_provider_model_language(app: &AppHandle) -> (String, Option<String>, Option<String>) {
     let provider = get_setting_string(app, "cloudTranscriptionProvider")
         .unwrap_or_else(|| "zai".to_string())
@@ -43,50 +354,236 @@ fn resolve_provider_model_language(app: &AppHandle) -> (String, Option<String>,
     (provider, model, language)
 }
 
-#[cfg(target_os = "macos")]
-const DEBOUNCE: Duration = Duration::from_millis(30);
+const DEFAULT_LANGUAGE_CYCLE: [&str; 3] = ["en", "zh", "auto"];
+
+/// Languages the cycle-language hotkey steps through (`languageCycle` setting).
+fn language_cycle(app: &AppHandle) -> Vec<String> {
+    let configured: Vec<String> =
+        super::settings::get_setting(app.clone(), "languageCycle".to_string())
+            .ok()
+            .flatten()
+            .and_then(|v| v.as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+    if configured.is_empty() {
+        DEFAULT_LANGUAGE_CYCLE
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    } else {
+        configured
+    }
+}
+
+/// Payload of `dictation-language-changed`.
+#[derive(Clone, Serialize)]
+struct LanguageChangedPayload {
+    language: String,
+}
+
+/// Switch `preferredLanguage` to the next entry of the language cycle. The next
+/// transcription picks it up through `resolve_provider_model_language`.
+fn cycle_language(app: &AppHandle) {
+    let cycle = language_cycle(app);
+    let current = get_setting_string(app, "preferredLanguage")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "auto".to_string());
+    let next = cycle
+        .iter()
+        .position(|lang| lang.eq_ignore_ascii_case(&current))
+        .map(|i| (i + 1) % cycle.len())
+        .unwrap_or(0);
+    let language = cycle[next].clone();
+
+    if let Err(err) = super::settings::set_setting(
+        app.clone(),
+        "preferredLanguage".to_string(),
+        serde_json::Value::String(language.clone()),
+    ) {
+        eprintln!(
+            "[dictation] failed to save language '{}': {}",
+            language, err
+        );
+        return;
+    }
+    eprintln!("[dictation] language {} -> {}", current, language);
+
+    let label = if language.eq_ignore_ascii_case("auto") {
+        "Auto".to_string()
+    } else {
+        language.to_uppercase()
+    };
+    crate::overlay::flash_overlay_notice(app, &label);
+    let _ = app.emit(
+        "dictation-language-changed",
+        LanguageChangedPayload { language },
+    );
+}
+
+const DEFAULT_HOTKEY_DEBOUNCE_MS: u64 = 30;
+const MAX_HOTKEY_DEBOUNCE_MS: u64 = 1000;
+// Stopping a recording a few milliseconds after it started is almost always key chatter,
+// so presses that would stop one get a longer window.
+const STOP_DEBOUNCE_FACTOR: u32 = 4;
+
+// A press while the key is still down counts as key repeat only this soon after the last
+// one, so a lost release can't block the hotkey for good.
+const KEY_REPEAT_TIMEOUT: Duration = Duration::from_secs(1);
 
-#[cfg(target_os = "macos")]
 const START_FEEDBACK_DELAY: Duration = Duration::from_millis(450);
 
-#[cfg(target_os = "macos")]
+// Off unless set: detection delays every tap-to-start by the window.
+const DEFAULT_DOUBLE_TAP_WINDOW_MS: u64 = 0;
+
+const DEFAULT_LOCK_DOUBLE_TAP_MS: u64 = 350;
+
+/// Watchdog slack for stopping the recorder, text processing and pasting.
+const PROCESSING_HEADROOM: Duration = Duration::from_secs(15);
+
+const DEFAULT_RELEASE_CHECK_MS: u64 = 2000;
+
+const DEFAULT_LONG_PRESS_THRESHOLD_MS: u64 = 500;
+
+const DEFAULT_MIN_SPEECH_ENERGY_DB: f64 = -60.0;
+
+const DEFAULT_MIN_RECORDING_SECONDS: f64 = 0.5;
+
+// Speech has to clear the calibrated room noise by this much to count.
+const NOISE_FLOOR_MARGIN_DB: f64 = 6.0;
+
 #[derive(Debug)]
 enum Command {
     Input {
         hotkey_string: String,
         is_pressed: bool,
         push_to_talk: bool,
+        // Releases are delivered for this input, so a long hold can switch to push-to-talk.
+        detect_hold: bool,
+    },
+    Action(DictationAction),
+    /// The double-tap window opened by the first tap (identified by `tap_id`) has elapsed.
+    TapWindowElapsed {
+        tap_id: u64,
+        hotkey_string: String,
+    },
+    /// A quick push-to-talk tap (identified by `tap_id`) wasn't followed by a second tap
+    /// that would lock the recording.
+    LockWindowElapsed {
+        tap_id: u64,
+        hotkey_string: String,
+    },
+    /// The long-press threshold for the press identified by `hold_id` has elapsed.
+    HoldElapsed {
+        hold_id: u64,
+        hotkey_string: String,
+    },
+    /// The stop-and-transcribe task identified by `processing_id` has ended; `succeeded`
+    /// when its text was pasted.
+    ProcessingFinished {
+        processing_id: u64,
+        succeeded: bool,
+    },
+    /// Leave continuous mode: transcribe the current segment (if any) without restarting.
+    StopContinuous,
+    /// `processingTimeoutSeconds` passed without the task identified by `processing_id`
+    /// finishing.
+    ProcessingTimedOut {
+        processing_id: u64,
+    },
+    /// Manual recovery: abandon whatever is in flight and return to idle.
+    Reset,
+    /// The user cancelled the transcription: abandon processing, if that's the stage.
+    CancelProcessing,
+    /// Time to confirm the push-to-talk key (watch `watch_id`) is still physically down.
+    ReleaseCheck {
+        watch_id: u64,
+        hotkey_string: String,
+        push_to_talk: bool,
     },
-    ProcessingFinished,
 }
 
-#[cfg(target_os = "macos")]
 #[derive(Debug)]
 enum Stage {
     Idle,
     Recording,
+    Paused,
     Processing,
 }
 
+impl Stage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Recording => "recording",
+            Self::Paused => "paused",
+            Self::Processing => "processing",
+        }
+    }
+}
+
 /// Coordinates hotkey events so we don't race recording/transcription across threads.
-#[cfg(target_os = "macos")]
 struct DictationCoordinator {
     tx: tokio::sync::mpsc::UnboundedSender<Command>,
 }
 
-#[cfg(target_os = "macos")]
 struct FinishGuard {
     tx: tokio::sync::mpsc::UnboundedSender<Command>,
+    processing_id: u64,
+    succeeded: bool,
 }
 
-#[cfg(target_os = "macos")]
 impl Drop for FinishGuard {
     fn drop(&mut self) {
-        let _ = self.tx.send(Command::ProcessingFinished);
+        let _ = self.tx.send(Command::ProcessingFinished {
+            processing_id: self.processing_id,
+            succeeded: self.succeeded,
+        });
+    }
+}
+
+/// The in-flight stop-and-transcribe task, kept so the watchdog, a reset or a superseding
+/// press can abort it (which also drops its HTTP requests).
+#[derive(Default)]
+struct ProcessingTask {
+    id: u64,
+    handle: Option<tauri::async_runtime::JoinHandle<()>>,
+}
+
+impl ProcessingTask {
+    /// Stop the recording, transcribe it in the background and arm the watchdog.
+    fn start(&mut self, app: &AppHandle, tx: &tokio::sync::mpsc::UnboundedSender<Command>) {
+        self.id += 1;
+        let processing_id = self.id;
+        self.handle = Some(stop_and_transcribe(app.clone(), tx.clone(), processing_id));
+
+        let timeout = processing_timeout(app);
+        if timeout.is_zero() {
+            return;
+        }
+        let tx = tx.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            let _ = tx.send(Command::ProcessingTimedOut { processing_id });
+        });
+    }
+
+    /// Whether `processing_id` is the task still in flight (stale ids come from aborted runs).
+    fn is_current(&self, processing_id: u64) -> bool {
+        self.handle.is_some() && processing_id == self.id
+    }
+
+    fn abort(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
     }
 }
 
-#[cfg(target_os = "macos")]
 impl DictationCoordinator {
     fn new(app: AppHandle) -> Self {
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Command>();
@@ -95,119 +592,820 @@ impl DictationCoordinator {
         tauri::async_runtime::spawn(async move {
             let mut stage = Stage::Idle;
             let mut last_press: Option<Instant> = None;
+            // Taps seen in the current double-tap window; `tap_id` invalidates stale timers.
+            let mut tap_count: u32 = 0;
+            let mut tap_id: u64 = 0;
+            // Long-press state: a hold past the threshold makes this session push-to-talk.
+            let mut key_held = false;
+            let mut hold_id: u64 = 0;
+            let mut session_push_to_talk = false;
+            let mut processing = ProcessingTask::default();
+            // Continuous mode: each successful transcription starts the next recording.
+            let mut continuous_active = false;
+            // Push-to-talk: `key_down` filters key repeat, a quick tap opens the lock window
+            // (`ptt_pressed_at`), and a second tap inside it locks the session until next press.
+            let mut key_down = false;
+            let mut ptt_pressed_at: Option<Instant> = None;
+            let mut session_locked = false;
+            // Escape is bound (by us) only while a recording can be cancelled.
+            let mut cancel_shortcut_bound = false;
+            // A press during processing with `pressDuringProcessing` = `queue`: start the next
+            // recording once the current one has been delivered.
+            let mut pending_start = false;
+            // Bumped for every held-key recording and on every release, invalidating the
+            // release checks armed for the previous one.
+            let mut release_watch_id: u64 = 0;
 
             while let Some(cmd) = rx.recv().await {
-                match cmd {
-                    Command::Input {
-                        hotkey_string,
-                        is_pressed,
-                        push_to_talk,
-                    } => {
-                        // Keep our stage in sync with the actual recorder state (UI can start/stop too).
-                        let recording_active = super::recording::is_native_recording_active();
-                        match (&stage, recording_active) {
-                            (Stage::Idle, true) => stage = Stage::Recording,
-                            (Stage::Recording, false) => stage = Stage::Idle,
-                            _ => {}
-                        }
+                'command: {
+                    match cmd {
+                        Command::Input {
+                            hotkey_string,
+                            is_pressed,
+                            push_to_talk,
+                            detect_hold,
+                        } => {
+                            sync_stage(&mut stage);
 
-                        // Debounce rapid-fire press events (key repeat / double-tap).
-                        // Releases always pass through for push-to-talk.
-                        if is_pressed {
-                            let now = Instant::now();
-                            if last_press.map_or(false, |t| now.duration_since(t) < DEBOUNCE) {
-                                eprintln!(
-                                    "[dictation] debounced press for '{}' (stage={:?})",
-                                    hotkey_string, stage
-                                );
-                                continue;
+                            // Drop key repeat and chatter. Inputs that deliver releases are
+                            // filtered by key state; toggle presses are also debounced, since a
+                            // bouncing switch delivers full press/release pairs. Push-to-talk
+                            // isn't, or a bounce on release would swallow the next real press.
+                            let previous_press = last_press;
+                            if is_pressed {
+                                let now = Instant::now();
+                                let since_last = last_press.map(|t| now.duration_since(t));
+                                let key_repeat = (push_to_talk || detect_hold)
+                                    && key_down
+                                    && since_last.is_some_and(|d| d < KEY_REPEAT_TIMEOUT);
+                                let chatter = !push_to_talk
+                                    && since_last
+                                        .is_some_and(|d| d < hotkey_debounce(&app, &stage));
+                                last_press = Some(now);
+                                if key_repeat || chatter {
+                                    log::debug!(
+                                        "[dictation] debounced press for '{}' (stage={:?})",
+                                        hotkey_string,
+                                        stage
+                                    );
+                                    break 'command;
+                                }
+                                key_down = true;
+                            } else {
+                                key_down = false;
+                                release_watch_id += 1;
                             }
-                            last_press = Some(now);
-                        }
 
-                        if push_to_talk {
-                            if is_pressed && matches!(stage, Stage::Idle) {
-                                eprintln!(
-                                    "[dictation] start (push-to-talk) via '{}'",
-                                    hotkey_string
-                                );
+                            if continuous_active && is_pressed {
+                                let _ = tx_for_tasks.send(Command::StopContinuous);
+                                break 'command;
+                            }
+
+                            if matches!(stage, Stage::Processing) {
+                                if is_pressed {
+                                    match press_during_processing(&app) {
+                                        PressDuringProcessing::Ignore => {}
+                                        PressDuringProcessing::Queue => {
+                                            eprintln!(
+                                                "[dictation] queued start via '{}'",
+                                                hotkey_string
+                                            );
+                                            pending_start = true;
+                                            break 'command;
+                                        }
+                                        PressDuringProcessing::Cancel => {
+                                            eprintln!(
+                                                "[dictation] cancel processing and restart via '{}'",
+                                                hotkey_string
+                                            );
+                                            processing.abort();
+                                            abandon_processing(&app);
+                                            dictation_session::finish_session("superseded");
+                                            stage = Stage::Idle;
+                                            session_push_to_talk = false;
+                                            if let Err(err) = start_recording(&app).await {
+                                                eprintln!("[dictation] start failed: {}", err);
+                                                emit_dictation_error(&app, err);
+                                            } else {
+                                                stage = Stage::Recording;
+                                            }
+                                            break 'command;
+                                        }
+                                    }
+                                } else if push_to_talk && std::mem::take(&mut pending_start) {
+                                    // The push-to-talk key is already up; nothing left to record.
+                                    eprintln!(
+                                        "[dictation] dropped queued start (released) via '{}'",
+                                        hotkey_string
+                                    );
+                                }
+                            }
+
+                            if push_to_talk {
+                                let recording = matches!(stage, Stage::Recording | Stage::Paused);
+                                if is_pressed && matches!(stage, Stage::Idle) {
+                                    eprintln!(
+                                        "[dictation] start (push-to-talk) via '{}'",
+                                        hotkey_string
+                                    );
+                                    if let Err(err) = start_recording(&app).await {
+                                        eprintln!("[dictation] start failed: {}", err);
+                                        emit_dictation_error(&app, err);
+                                    } else {
+                                        stage = Stage::Recording;
+                                        ptt_pressed_at = Some(Instant::now());
+                                        tap_count = 0;
+                                        release_watch_id += 1;
+                                        arm_release_check(
+                                            &app,
+                                            &tx_for_tasks,
+                                            release_watch_id,
+                                            hotkey_string.clone(),
+                                            true,
+                                        );
+                                    }
+                                } else if is_pressed && recording && session_locked {
+                                    eprintln!("[dictation] stop (locked) via '{}'", hotkey_string);
+                                    stage = Stage::Processing;
+                                    processing.start(&app, &tx_for_tasks);
+                                } else if is_pressed && recording && tap_count == 1 {
+                                    tap_count = 0;
+                                    tap_id += 1;
+                                    session_locked = true;
+                                    eprintln!("[dictation] locked via '{}'", hotkey_string);
+                                    crate::overlay::set_recording_locked(&app, true);
+                                } else if !is_pressed && recording && !session_locked {
+                                    let window = lock_double_tap_window(&app);
+                                    let tap_elapsed = ptt_pressed_at
+                                        .take()
+                                        .map(|t| t.elapsed())
+                                        .filter(|elapsed| *elapsed < window);
+                                    if let Some(elapsed) = tap_elapsed {
+                                        // Quick tap: keep recording in case a second tap locks it.
+                                        tap_count = 1;
+                                        tap_id += 1;
+                                        let tx = tx_for_tasks.clone();
+                                        let pending_tap = tap_id;
+                                        tauri::async_runtime::spawn(async move {
+                                            tokio::time::sleep(window - elapsed).await;
+                                            let _ = tx.send(Command::LockWindowElapsed {
+                                                tap_id: pending_tap,
+                                                hotkey_string,
+                                            });
+                                        });
+                                    } else {
+                                        eprintln!(
+                                            "[dictation] stop (push-to-talk) via '{}'",
+                                            hotkey_string
+                                        );
+                                        stage = Stage::Processing;
+                                        processing.start(&app, &tx_for_tasks);
+                                    }
+                                }
+                            } else if is_pressed {
+                                let window = double_tap_window(&app);
+                                let threshold = long_press_threshold(&app);
+                                if detect_hold
+                                    && !threshold.is_zero()
+                                    && matches!(stage, Stage::Idle)
+                                {
+                                    key_held = true;
+                                    hold_id += 1;
+                                    let tx = tx_for_tasks.clone();
+                                    let pending_hold = hold_id;
+                                    let hotkey_string = hotkey_string.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        tokio::time::sleep(threshold).await;
+                                        let _ = tx.send(Command::HoldElapsed {
+                                            hold_id: pending_hold,
+                                            hotkey_string,
+                                        });
+                                    });
+                                }
+                                match stage {
+                                    Stage::Idle if !window.is_zero() => {
+                                        let within_window =
+                                            previous_press.is_some_and(|t| t.elapsed() <= window);
+                                        tap_id += 1;
+                                        if tap_count == 1 && within_window {
+                                            tap_count = 0;
+                                            eprintln!(
+                                                "[dictation] double tap via '{}'",
+                                                hotkey_string
+                                            );
+                                            let _ = app.emit("backend-double-tap-hotkey", ());
+                                        } else {
+                                            // Wait out the window before starting, in case this is
+                                            // the first half of a double tap.
+                                            tap_count = 1;
+                                            let tx = tx_for_tasks.clone();
+                                            let pending_tap = tap_id;
+                                            tauri::async_runtime::spawn(async move {
+                                                tokio::time::sleep(window).await;
+                                                let _ = tx.send(Command::TapWindowElapsed {
+                                                    tap_id: pending_tap,
+                                                    hotkey_string,
+                                                });
+                                            });
+                                        }
+                                    }
+                                    Stage::Idle => {
+                                        eprintln!(
+                                            "[dictation] start (tap) via '{}'",
+                                            hotkey_string
+                                        );
+                                        if let Err(err) = start_recording(&app).await {
+                                            eprintln!("[dictation] start failed: {}", err);
+                                            emit_dictation_error(&app, err);
+                                        } else {
+                                            stage = Stage::Recording;
+                                        }
+                                    }
+                                    // Stopping while paused transcribes what was recorded so far.
+                                    Stage::Recording | Stage::Paused => {
+                                        eprintln!("[dictation] stop (tap) via '{}'", hotkey_string);
+                                        stage = Stage::Processing;
+                                        processing.start(&app, &tx_for_tasks);
+                                    }
+                                    Stage::Processing => {
+                                        eprintln!(
+                                            "[dictation] ignoring press while processing via '{}'",
+                                            hotkey_string
+                                        );
+                                    }
+                                }
+                            } else {
+                                key_held = false;
+                                if std::mem::take(&mut session_push_to_talk)
+                                    && matches!(stage, Stage::Recording | Stage::Paused)
+                                {
+                                    eprintln!(
+                                        "[dictation] stop (hold release) via '{}'",
+                                        hotkey_string
+                                    );
+                                    stage = Stage::Processing;
+                                    processing.start(&app, &tx_for_tasks);
+                                }
+                            }
+                        }
+                        Command::Action(action) => {
+                            sync_stage(&mut stage);
+                            match (action, &stage) {
+                                (DictationAction::Start, Stage::Idle) => {
+                                    eprintln!("[dictation] start (action hotkey)");
+                                    if let Err(err) = start_recording(&app).await {
+                                        eprintln!("[dictation] start failed: {}", err);
+                                        emit_dictation_error(&app, err);
+                                    } else {
+                                        stage = Stage::Recording;
+                                    }
+                                }
+                                (DictationAction::Stop, Stage::Recording | Stage::Paused) => {
+                                    eprintln!("[dictation] stop (action hotkey)");
+                                    stage = Stage::Processing;
+                                    processing.start(&app, &tx_for_tasks);
+                                }
+                                (DictationAction::Cancel, Stage::Recording | Stage::Paused) => {
+                                    eprintln!("[dictation] cancel (action hotkey)");
+                                    cancel_recording(&app, &stage).await;
+                                    stage = Stage::Idle;
+                                }
+                                (
+                                    DictationAction::CancelDictation,
+                                    Stage::Recording | Stage::Paused,
+                                ) => {
+                                    eprintln!("[dictation] cancel-dictation (stage={:?})", stage);
+                                    cancel_recording(&app, &stage).await;
+                                    stage = Stage::Idle;
+                                    session_push_to_talk = false;
+                                }
+                                (DictationAction::CancelDictation, Stage::Processing) => {
+                                    eprintln!("[dictation] cancel-dictation (stage={:?})", stage);
+                                    processing.abort();
+                                    pending_start = false;
+                                    discard_processing(&app);
+                                    stage = Stage::Idle;
+                                    session_push_to_talk = false;
+                                }
+                                (DictationAction::CycleLanguage, _) => cycle_language(&app),
+                                _ => {
+                                    eprintln!(
+                                        "[dictation] ignoring action {:?} (stage={:?})",
+                                        action, stage
+                                    );
+                                }
+                            }
+                        }
+                        Command::TapWindowElapsed {
+                            tap_id: elapsed_tap,
+                            hotkey_string,
+                        } => {
+                            if elapsed_tap != tap_id || tap_count != 1 {
+                                break 'command;
+                            }
+                            tap_count = 0;
+                            sync_stage(&mut stage);
+                            if matches!(stage, Stage::Idle) {
+                                eprintln!("[dictation] start (tap) via '{}'", hotkey_string);
                                 if let Err(err) = start_recording(&app).await {
                                     eprintln!("[dictation] start failed: {}", err);
-                                    let _ = app.emit("backend-dictation-error", err);
+                                    emit_dictation_error(&app, err);
                                 } else {
                                     stage = Stage::Recording;
                                 }
-                            } else if !is_pressed && matches!(stage, Stage::Recording) {
+                            }
+                        }
+                        Command::LockWindowElapsed {
+                            tap_id: elapsed_tap,
+                            hotkey_string,
+                        } => {
+                            if elapsed_tap != tap_id || tap_count != 1 {
+                                break 'command;
+                            }
+                            tap_count = 0;
+                            sync_stage(&mut stage);
+                            if matches!(stage, Stage::Recording | Stage::Paused) && !session_locked
+                            {
                                 eprintln!(
                                     "[dictation] stop (push-to-talk) via '{}'",
                                     hotkey_string
                                 );
                                 stage = Stage::Processing;
-                                stop_and_transcribe(app.clone(), tx_for_tasks.clone());
+                                processing.start(&app, &tx_for_tasks);
+                            }
+                        }
+                        Command::HoldElapsed {
+                            hold_id: elapsed_hold,
+                            hotkey_string,
+                        } => {
+                            if elapsed_hold != hold_id || !key_held {
+                                break 'command;
                             }
-                        } else if is_pressed {
+                            sync_stage(&mut stage);
                             match stage {
-                                Stage::Idle => {
-                                    eprintln!("[dictation] start (tap) via '{}'", hotkey_string);
+                                // Still waiting out a possible double tap: start right away instead.
+                                Stage::Idle if tap_count == 1 => {
+                                    tap_count = 0;
+                                    eprintln!("[dictation] start (hold) via '{}'", hotkey_string);
                                     if let Err(err) = start_recording(&app).await {
                                         eprintln!("[dictation] start failed: {}", err);
-                                        let _ = app.emit("backend-dictation-error", err);
-                                    } else {
-                                        stage = Stage::Recording;
+                                        emit_dictation_error(&app, err);
+                                        break 'command;
                                     }
+                                    stage = Stage::Recording;
                                 }
-                                Stage::Recording => {
-                                    eprintln!("[dictation] stop (tap) via '{}'", hotkey_string);
-                                    stage = Stage::Processing;
-                                    stop_and_transcribe(app.clone(), tx_for_tasks.clone());
+                                Stage::Recording => {}
+                                _ => break 'command,
+                            }
+                            eprintln!("[dictation] push-to-talk hold via '{}'", hotkey_string);
+                            session_push_to_talk = true;
+                            release_watch_id += 1;
+                            arm_release_check(
+                                &app,
+                                &tx_for_tasks,
+                                release_watch_id,
+                                hotkey_string.clone(),
+                                false,
+                            );
+                            dictation_session::emit(&app, "backend-dictation-push-hold", NoData {});
+                        }
+                        Command::ProcessingFinished {
+                            processing_id,
+                            succeeded,
+                        } => {
+                            if !processing.is_current(processing_id) {
+                                break 'command;
+                            }
+                            processing.handle = None;
+                            stage = Stage::Idle;
+                            session_push_to_talk = false;
+
+                            let queued = std::mem::take(&mut pending_start);
+                            if queued && !succeeded {
+                                eprintln!("[dictation] dropped queued start (processing failed)");
+                            }
+                            if succeeded && (continuous_active || queued) {
+                                eprintln!(
+                                    "[dictation] start ({})",
+                                    if queued { "queued" } else { "continuous" }
+                                );
+                                if let Err(err) = start_recording(&app).await {
+                                    eprintln!("[dictation] start failed: {}", err);
+                                    emit_dictation_error(&app, err);
+                                } else {
+                                    stage = Stage::Recording;
                                 }
-                                Stage::Processing => {
+                            }
+                        }
+                        Command::StopContinuous => {
+                            if !continuous_active {
+                                break 'command;
+                            }
+                            eprintln!("[dictation] stop continuous (stage={:?})", stage);
+                            continuous_active = false;
+                            dictation_session::emit(
+                                &app,
+                                "backend-dictation-continuous-active",
+                                Active { active: false },
+                            );
+                            sync_stage(&mut stage);
+                            if matches!(stage, Stage::Recording | Stage::Paused) {
+                                stage = Stage::Processing;
+                                processing.start(&app, &tx_for_tasks);
+                            }
+                        }
+                        Command::ProcessingTimedOut { processing_id } => {
+                            if !processing.is_current(processing_id) {
+                                break 'command;
+                            }
+                            let seconds = processing_timeout(&app).as_secs();
+                            eprintln!("[dictation] processing timed out after {}s", seconds);
+                            processing.abort();
+                            pending_start = false;
+                            abandon_processing(&app);
+                            emit_dictation_error(
+                                &app,
+                                format!(
+                                    "Transcription did not finish within {} seconds and was cancelled",
+                                    seconds
+                                ),
+                            );
+                            stage = Stage::Idle;
+                            session_push_to_talk = false;
+                        }
+                        Command::ReleaseCheck {
+                            watch_id,
+                            hotkey_string,
+                            push_to_talk,
+                        } => {
+                            if watch_id != release_watch_id || !key_down || session_locked {
+                                break 'command;
+                            }
+                            sync_stage(&mut stage);
+                            if !matches!(stage, Stage::Recording | Stage::Paused) {
+                                break 'command;
+                            }
+                            match super::hotkey::is_hotkey_physically_down(&hotkey_string) {
+                                Some(true) => arm_release_check(
+                                    &app,
+                                    &tx_for_tasks,
+                                    watch_id,
+                                    hotkey_string,
+                                    push_to_talk,
+                                ),
+                                Some(false) => {
                                     eprintln!(
-                                        "[dictation] ignoring press while processing via '{}'",
+                                        "[dictation] release of '{}' was lost, stopping",
                                         hotkey_string
                                     );
+                                    dictation_session::emit(
+                                        &app,
+                                        "dictation-release-recovered",
+                                        ReleaseRecovered {
+                                            hotkey: &hotkey_string,
+                                        },
+                                    );
+                                    // Replay the release so it takes the normal stop path.
+                                    let _ = tx_for_tasks.send(Command::Input {
+                                        hotkey_string,
+                                        is_pressed: false,
+                                        push_to_talk,
+                                        detect_hold: true,
+                                    });
                                 }
+                                // Key state can't be read here; nothing to watch.
+                                None => {}
                             }
                         }
+                        Command::CancelProcessing => {
+                            if !matches!(stage, Stage::Processing) {
+                                break 'command;
+                            }
+                            eprintln!("[dictation] cancel transcription");
+                            processing.abort();
+                            pending_start = false;
+                            discard_processing(&app);
+                            stage = Stage::Idle;
+                            session_push_to_talk = false;
+                        }
+                        Command::Reset => {
+                            eprintln!("[dictation] reset (stage={:?})", stage);
+                            processing.abort();
+                            pending_start = false;
+                            if matches!(stage, Stage::Recording | Stage::Paused) {
+                                cancel_recording(&app, &stage).await;
+                            } else {
+                                abandon_processing(&app);
+                                dictation_session::finish_session("reset");
+                            }
+                            stage = Stage::Idle;
+                            session_push_to_talk = false;
+                            tap_count = 0;
+                            key_held = false;
+                            key_down = false;
+                        }
                     }
-                    Command::ProcessingFinished => {
-                        stage = Stage::Idle;
+                }
+                let recording = matches!(stage, Stage::Recording | Stage::Paused);
+                if recording && !continuous_active && continuous_mode_enabled(&app) {
+                    continuous_active = true;
+                    dictation_session::emit(
+                        &app,
+                        "backend-dictation-continuous-active",
+                        Active { active: true },
+                    );
+                } else if matches!(stage, Stage::Idle) && continuous_active {
+                    // Cancelled, failed or reset: don't leave the loop armed.
+                    continuous_active = false;
+                    dictation_session::emit(
+                        &app,
+                        "backend-dictation-continuous-active",
+                        Active { active: false },
+                    );
+                }
+                if !recording {
+                    ptt_pressed_at = None;
+                    if std::mem::take(&mut session_locked) {
+                        crate::overlay::set_recording_locked(&app, false);
                     }
                 }
+                sync_cancel_shortcut(&app, &stage, &mut cancel_shortcut_bound);
             }
         });
 
         Self { tx }
     }
 
-    fn send_input(&self, hotkey_string: &str, is_pressed: bool, push_to_talk: bool) {
+    fn send_reset(&self) {
+        let _ = self.tx.send(Command::Reset);
+    }
+
+    fn send_cancel_processing(&self) {
+        let _ = self.tx.send(Command::CancelProcessing);
+    }
+
+    fn send_action(&self, action: DictationAction) {
+        let _ = self.tx.send(Command::Action(action));
+    }
+
+    fn send_input(
+        &self,
+        hotkey_string: &str,
+        is_pressed: bool,
+        push_to_talk: bool,
+        detect_hold: bool,
+    ) {
         let _ = self.tx.send(Command::Input {
             hotkey_string: hotkey_string.to_string(),
             is_pressed,
             push_to_talk,
+            detect_hold,
+        });
+    }
+}
+
+/// `pushToTalkReleaseCheckMs`: how often a held push-to-talk recording confirms the key is
+/// still down, to recover from a key-up the OS never delivered. 0 disables the check.
+fn release_check_interval(app: &AppHandle) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), "pushToTalkReleaseCheckMs".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_RELEASE_CHECK_MS);
+    Duration::from_millis(ms)
+}
+
+/// Schedule the next `ReleaseCheck` for `watch_id`.
+fn arm_release_check(
+    app: &AppHandle,
+    tx: &tokio::sync::mpsc::UnboundedSender<Command>,
+    watch_id: u64,
+    hotkey_string: String,
+    push_to_talk: bool,
+) {
+    let interval = release_check_interval(app);
+    if interval.is_zero() {
+        return;
+    }
+    let tx = tx.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(interval).await;
+        let _ = tx.send(Command::ReleaseCheck {
+            watch_id,
+            hotkey_string,
+            push_to_talk,
         });
+    });
+}
+
+/// Payload fields of `dictation-release-recovered`.
+#[derive(Clone, Serialize)]
+struct ReleaseRecovered<'a> {
+    hotkey: &'a str,
+}
+
+/// What a hotkey press does while a transcription is still being processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PressDuringProcessing {
+    Ignore,
+    /// Start the next recording as soon as the current one has been delivered.
+    Queue,
+    /// Abort the in-flight transcription and start recording right away.
+    Cancel,
+}
+
+/// `pressDuringProcessing`: `ignore` (default), `queue` or `cancel`.
+fn press_during_processing(app: &AppHandle) -> PressDuringProcessing {
+    match get_setting_string(app, "pressDuringProcessing")
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("queue") => PressDuringProcessing::Queue,
+        Some("cancel") => PressDuringProcessing::Cancel,
+        _ => PressDuringProcessing::Ignore,
+    }
+}
+
+/// `hotkeyDebounceMs` (default 30, at most 1000): presses this soon after the previous one
+/// are dropped as chatter. A press that would stop a recording uses four times the window.
+fn hotkey_debounce(app: &AppHandle, stage: &Stage) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), "hotkeyDebounceMs".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_HOTKEY_DEBOUNCE_MS)
+        .min(MAX_HOTKEY_DEBOUNCE_MS);
+    let window = Duration::from_millis(ms);
+    match stage {
+        Stage::Recording | Stage::Paused => window * STOP_DEBOUNCE_FACTOR,
+        Stage::Idle | Stage::Processing => window,
+    }
+}
+
+/// `doubleTapWindowMs`: two taps within this window emit `backend-double-tap-hotkey` instead
+/// of toggling dictation. 0 (the default) disables detection and the start delay it implies.
+fn double_tap_window(app: &AppHandle) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), "doubleTapWindowMs".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_DOUBLE_TAP_WINDOW_MS);
+    Duration::from_millis(ms)
+}
+
+/// `continuousDictationMode`: after each successful transcription, start recording again
+/// until the hotkey is pressed.
+fn continuous_mode_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "continuousDictationMode".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// `processingTimeoutSeconds`: give up on a stop-and-transcribe task that hasn't finished
+/// after this long, so a hung provider can't wedge dictation. 0 disables the watchdog.
+/// Unset, the budget covers every transcription retry and fallback timing out plus each
+/// post-processing step, so the watchdog never cuts a legitimate retry short.
+fn processing_timeout(app: &AppHandle) -> Duration {
+    if let Some(secs) =
+        super::settings::get_setting(app.clone(), "processingTimeoutSeconds".to_string())
+            .ok()
+            .flatten()
+            .and_then(|v| v.as_u64())
+    {
+        return Duration::from_secs(secs);
+    }
+    let steps = super::postprocessing::configured_pipeline(app).len().max(1) as u32;
+    super::transcription::worst_case_transcription_time(app)
+        + super::postprocessing::STEP_TIMEOUT * steps
+        + PROCESSING_HEADROOM
+}
+
+/// `doubleTapMs`: in push-to-talk mode, a second tap within this window of the first press
+/// locks the recording on (toggle mode) until the next press. 0 disables locking.
+fn lock_double_tap_window(app: &AppHandle) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), "doubleTapMs".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_LOCK_DOUBLE_TAP_MS);
+    Duration::from_millis(ms)
+}
+
+/// `longPressThresholdMs`: holding the hotkey longer than this in tap mode records until
+/// release (push-to-talk for that session). 0 disables hold detection.
+fn long_press_threshold(app: &AppHandle) -> Duration {
+    let ms = super::settings::get_setting(app.clone(), "longPressThresholdMs".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_LONG_PRESS_THRESHOLD_MS);
+    Duration::from_millis(ms)
+}
+
+/// `minSpeechEnergyDb`: recordings quieter than this (RMS dBFS) are not transcribed.
+/// Without an explicit value, the calibrated noise floor (plus a margin) is used.
+fn min_speech_energy_db(app: &AppHandle) -> f32 {
+    let setting = |key: &str| {
+        super::settings::get_setting(app.clone(), key.to_string())
+            .ok()
+            .flatten()
+            .and_then(|v| v.as_f64())
+    };
+    setting("minSpeechEnergyDb")
+        .or_else(|| setting("calibratedNoiseFloorDb").map(|db| db + NOISE_FLOOR_MARGIN_DB))
+        .unwrap_or(DEFAULT_MIN_SPEECH_ENERGY_DB) as f32
+}
+
+/// `minRecordingSeconds`: shorter recordings (accidental taps) are discarded untranscribed.
+fn min_recording_seconds(app: &AppHandle) -> f64 {
+    super::settings::get_setting(app.clone(), "minRecordingSeconds".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_MIN_RECORDING_SECONDS)
+}
+
+/// Keep our stage in sync with the actual recorder state (UI can start/stop too).
+fn sync_stage(stage: &mut Stage) {
+    let recording_active = super::recording::is_native_recording_active();
+    let recording_paused = super::recording::is_native_recording_paused();
+    match (&*stage, recording_active, recording_paused) {
+        (Stage::Idle, true, false) => *stage = Stage::Recording,
+        (Stage::Idle | Stage::Recording, true, true) => *stage = Stage::Paused,
+        (Stage::Paused, true, false) => *stage = Stage::Recording,
+        (Stage::Recording | Stage::Paused, false, _) => *stage = Stage::Idle,
+        _ => {}
+    }
+}
+
+/// Bind Escape while recording or paused and release it otherwise.
+fn sync_cancel_shortcut(app: &AppHandle, stage: &Stage, bound: &mut bool) {
+    let wanted = matches!(stage, Stage::Recording | Stage::Paused);
+    if wanted == *bound {
+        return;
+    }
+    if wanted {
+        match super::hotkey::register_cancel_shortcut(app) {
+            Ok(registered) => *bound = registered,
+            Err(err) => eprintln!("[dictation] {}", err),
+        }
+    } else {
+        if let Err(err) = super::hotkey::unregister_cancel_shortcut(app) {
+            eprintln!("[dictation] {}", err);
+        }
+        *bound = false;
     }
 }
 
-#[cfg(target_os = "macos")]
 fn is_push_to_talk(app: &AppHandle) -> bool {
     get_setting_string(app, "activationMode")
         .map(|mode| mode.trim().eq_ignore_ascii_case("push"))
         .unwrap_or(false)
 }
 
-#[cfg(target_os = "macos")]
+const MICROPHONE_PERMISSION_DENIED: &str = "Microphone permission denied — open System Settings -> Privacy & Security -> Microphone and enable Typefree.";
+const MICROPHONE_PERMISSION_RESTRICTED: &str = "Microphone access is restricted by a device policy (such as MDM or Screen Time) — ask your administrator to allow it.";
+
+async fn ensure_microphone_permission() -> Result<(), String> {
+    // A denied recorder still "records", producing a silent/empty WAV, so check up front.
+    let granted = match super::recording::microphone_permission() {
+        PermissionStatus::Granted => true,
+        PermissionStatus::NotDetermined => {
+            super::recording::request_microphone_permission().await?
+        }
+        PermissionStatus::Denied => false,
+        PermissionStatus::Restricted => {
+            eprintln!("[dictation] microphone access restricted by policy");
+            return Err(MICROPHONE_PERMISSION_RESTRICTED.to_string());
+        }
+    };
+    if granted {
+        Ok(())
+    } else {
+        eprintln!("[dictation] microphone permission denied");
+        Err(MICROPHONE_PERMISSION_DENIED.to_string())
+    }
+}
+
+/// Begins a new dictation session; on failure the caller's error event closes it.
 async fn start_recording(app: &AppHandle) -> Result<(), String> {
+    dictation_session::begin_session();
+    ensure_microphone_permission().await?;
+
     crate::overlay::show_recording_overlay(app, crate::overlay::OverlayState::Recording);
 
-    let _ = app.emit("backend-dictation-start-feedback", ());
+    cancel_auto_hide(app);
+    dictation_session::emit(app, "backend-dictation-start-feedback", NoData {});
+    super::sound_feedback::play_feedback(app, FeedbackSound::Start);
     tokio::time::sleep(START_FEEDBACK_DELAY).await;
 
     if let Err(err) = super::audio_ducking::start_system_mute(app) {
         eprintln!("[dictation] failed to mute system audio: {}", err);
     }
 
-    let started = match super::recording::start_native_recording().await {
+    let started = match super::recording::start_native_recording(app.clone()).await {
         Ok(started) => started,
         Err(err) => {
             let _ = super::audio_ducking::stop_system_mute(app);
@@ -220,86 +1418,475 @@ async fn start_recording(app: &AppHandle) -> Result<(), String> {
         crate::overlay::hide_recording_overlay(app);
         return Err("Failed to start native recording".to_string());
     }
+    dictation_session::mark(SessionStage::RecordStart);
 
-    let _ = app.emit("backend-dictation-processing", false);
-    let _ = app.emit("backend-dictation-recording", true);
+    dictation_session::emit(
+        app,
+        "backend-dictation-processing",
+        Active { active: false },
+    );
+    dictation_session::emit(app, "backend-dictation-recording", Active { active: true });
     Ok(())
 }
 
-#[cfg(target_os = "macos")]
-fn stop_and_transcribe(app: AppHandle, tx: tokio::sync::mpsc::UnboundedSender<Command>) {
+/// Report a failed dictation; this ends the session.
+fn emit_dictation_error(app: &AppHandle, message: String) {
+    dictation_session::emit(
+        app,
+        "backend-dictation-error",
+        ErrorMessage { message: &message },
+    );
+    dictation_session::finish_session("failed");
+    super::sound_feedback::play_feedback(app, FeedbackSound::Error);
+}
+
+/// Payload fields of `backend-dictation-cancelled`: the stage the cancel interrupted.
+#[derive(Clone, Serialize)]
+struct Cancelled {
+    stage: &'static str,
+}
+
+async fn cancel_recording(app: &AppHandle, interrupted: &Stage) {
+    if let Err(err) = super::recording::cancel_native_recording().await {
+        eprintln!("[dictation] cancel failed: {}", err);
+    }
+    let _ = super::audio_ducking::stop_system_mute(app);
+    dictation_session::emit(app, "backend-dictation-recording", Active { active: false });
+    dictation_session::emit(
+        app,
+        "backend-dictation-cancelled",
+        Cancelled {
+            stage: interrupted.as_str(),
+        },
+    );
+    dictation_session::finish_session("cancelled");
+    crate::overlay::hide_recording_overlay(app);
+}
+
+/// Payload fields of `backend-dictation-too-short`.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TooShort {
+    duration_seconds: f64,
+    min_seconds: f64,
+}
+
+/// Discard an aborted processing task at the user's request.
+fn discard_processing(app: &AppHandle) {
+    abandon_processing(app);
+    dictation_session::emit(
+        app,
+        "backend-dictation-cancelled",
+        Cancelled {
+            stage: Stage::Processing.as_str(),
+        },
+    );
+    dictation_session::finish_session("cancelled");
+}
+
+/// UI cleanup for a processing task that was aborted rather than finished.
+fn abandon_processing(app: &AppHandle) {
+    let _ = super::audio_ducking::stop_system_mute(app);
+    dictation_session::emit(app, "backend-dictation-recording", Active { active: false });
+    dictation_session::emit(
+        app,
+        "backend-dictation-processing",
+        Active { active: false },
+    );
+    crate::overlay::hide_recording_overlay(app);
+}
+
+fn stop_and_transcribe(
+    app: AppHandle,
+    tx: tokio::sync::mpsc::UnboundedSender<Command>,
+    processing_id: u64,
+) -> tauri::async_runtime::JoinHandle<()> {
     tauri::async_runtime::spawn(async move {
-        let _guard = FinishGuard { tx };
+        // Resolve before anything can steal focus from the app we're about to paste into.
+        let profile = resolve_dictation_profile(&app);
+        if let Some(profile) = &profile {
+            eprintln!("[dictation] using profile '{}'", profile.name);
+        }
+        let profile_name = profile.as_ref().map(|profile| profile.name.clone());
+        // Aborting the task (cancel-on-press, watchdog, reset) drops its in-flight requests,
+        // but it may be between awaits when that happens: only deliver results while the
+        // session it was started for is still the current one.
+        let session_id = dictation_session::current_id();
+        let superseded = || {
+            let superseded = !dictation_session::is_current(session_id.as_deref());
+            if superseded {
+                eprintln!("[dictation] dropping result of superseded session");
+            }
+            superseded
+        };
+
+        let mut guard = FinishGuard {
+            tx,
+            processing_id,
+            succeeded: false,
+        };
 
-        let result = match super::recording::stop_native_recording().await {
-            Ok(result) => result,
+        // Judge by the recorder's own clock, which excludes pauses and start-up latency.
+        let status = super::recording::get_recording_status();
+        let min_seconds = min_recording_seconds(&app);
+        if status.active && status.elapsed_seconds < min_seconds {
+            eprintln!(
+                "[dictation] recording too short ({:.2}s < {:.2}s), discarding",
+                status.elapsed_seconds, min_seconds
+            );
+            if let Err(err) = super::recording::cancel_native_recording().await {
+                eprintln!("[dictation] cancel failed: {}", err);
+            }
+            abandon_processing(&app);
+            dictation_session::emit(
+                &app,
+                "backend-dictation-too-short",
+                TooShort {
+                    duration_seconds: status.elapsed_seconds,
+                    min_seconds,
+                },
+            );
+            dictation_session::finish_session("too-short");
+            return;
+        }
+
+        dictation_session::progress(&app, ProgressStage::FinalizingAudio, None);
+        let result = match super::recording::stop_native_recording(app.clone()).await {
+            Ok(result) => {
+                dictation_session::mark(SessionStage::RecordStop);
+                result
+            }
             Err(err) => {
                 let _ = super::audio_ducking::stop_system_mute(&app);
-                let _ = app.emit("backend-dictation-recording", false);
-                let _ = app.emit("backend-dictation-processing", false);
-                let _ = app.emit("backend-dictation-error", err.clone());
+                dictation_session::emit(
+                    &app,
+                    "backend-dictation-recording",
+                    Active { active: false },
+                );
+                dictation_session::emit(
+                    &app,
+                    "backend-dictation-processing",
+                    Active { active: false },
+                );
+                emit_dictation_error(&app, err.clone());
                 crate::overlay::hide_recording_overlay(&app);
                 return;
             }
         };
         let _ = super::audio_ducking::stop_system_mute(&app);
-        let _ = app.emit("backend-dictation-recording", false);
-        let _ = app.emit("backend-dictation-processing", true);
+        dictation_session::emit(
+            &app,
+            "backend-dictation-recording",
+            Active { active: false },
+        );
+        super::sound_feedback::play_feedback(&app, FeedbackSound::Stop);
+
+        // Skip the API call for silent captures (e.g. a hardware-muted mic); transcribing
+        // them costs money and tends to come back empty or hallucinated.
+        let min_energy_db = min_speech_energy_db(&app);
+        if let Some(level_db) = result.level_db.filter(|level| *level < min_energy_db) {
+            eprintln!(
+                "[dictation] no speech detected (level {:.1} dBFS < {:.1} dBFS)",
+                level_db, min_energy_db
+            );
+            dictation_session::emit(
+                &app,
+                "backend-dictation-processing",
+                Active { active: false },
+            );
+            emit_dictation_error(
+                &app,
+                format!(
+                    "No audio detected — check your microphone (level {:.1} dBFS, minimum {:.1} dBFS)",
+                    level_db, min_energy_db
+                ),
+            );
+            crate::overlay::hide_recording_overlay(&app);
+            return;
+        }
+
+        dictation_session::emit(
+            &app,
+            "backend-dictation-processing",
+            Active { active: true },
+        );
         crate::overlay::show_recording_overlay(&app, crate::overlay::OverlayState::Transcribing);
 
         let (provider, model, language) = resolve_provider_model_language(&app);
-        let raw_text = match super::transcription::transcribe_audio(
+        let language = profile
+            .as_ref()
+            .and_then(|profile| profile.language.clone())
+            .map(|language| language.trim().to_string())
+            .filter(|language| !language.is_empty() && language != "auto")
+            .or(language);
+        dictation_session::mark(SessionStage::TranscribeStart);
+        let transcribed = super::transcription::transcribe_audio(
             app.clone(),
-            result.audio_data,
-            provider,
-            model,
-            language,
+            result.audio_data.clone(),
+            provider.clone(),
+            model.clone(),
+            language.clone(),
         )
-        .await
-        {
-            Ok(text) => text,
+        .await;
+        if superseded() {
+            return;
+        }
+        let raw_text = match transcribed {
+            Ok(text) => {
+                dictation_session::mark(SessionStage::TranscribeEnd);
+                text
+            }
+            // `cancel_transcription` has the coordinator discard this task and report the
+            // cancel; only tidy up in case this ran first.
+            Err(err) if err == super::transcription::TRANSCRIPTION_CANCELLED => {
+                abandon_processing(&app);
+                return;
+            }
             Err(err) => {
-                let _ = app.emit("backend-dictation-processing", false);
-                let _ = app.emit("backend-dictation-error", err.clone());
+                dictation_session::emit(
+                    &app,
+                    "backend-dictation-processing",
+                    Active { active: false },
+                );
+                let err = if err == super::transcription::NO_CONNECTION_ERROR {
+                    match super::pending_recordings::enqueue_recording(
+                        &app,
+                        result.audio_data,
+                        provider,
+                        model,
+                        language,
+                    ) {
+                        Ok(_) => "No internet connection. The recording was saved and will be \
+                                  transcribed when you're back online."
+                            .to_string(),
+                        Err(queue_err) => {
+                            eprintln!("[dictation] failed to queue recording: {}", queue_err);
+                            err
+                        }
+                    }
+                } else {
+                    err
+                };
+                emit_dictation_error(&app, err.clone());
                 crate::overlay::hide_recording_overlay(&app);
                 return;
             }
         };
-        crate::overlay::show_recording_overlay(&app, crate::overlay::OverlayState::Processing);
-        let outcome =
-            super::postprocessing::postprocess_transcription(app.clone(), raw_text.clone()).await;
-        let _ = super::database::db_save_transcription(
+
+        let paste_mode = profile
+            .as_ref()
+            .and_then(|profile| profile.paste_mode.as_deref())
+            .and_then(PasteMode::parse)
+            .unwrap_or_else(|| configured_paste_mode(&app));
+        // With `autoPaste` off the text waits on the clipboard (and for `paste-last`).
+        let auto_paste = auto_paste_enabled(&app);
+        // Voice commands act on the focused app, so they only run when the text is inserted.
+        let inserts = auto_paste && paste_mode != PasteMode::CopyOnly;
+        let (raw_text, voice_command) = super::text_expander::take_voice_command(&app, &raw_text);
+        if let Some(command) = voice_command {
+            eprintln!("[dictation] voice command: {:?}", command);
+        }
+        if raw_text.trim().is_empty() {
+            if let Some(command) = voice_command {
+                // A bare command: nothing to transcribe, save or paste.
+                if inserts {
+                    let executed = match command {
+                        VoiceCommand::ScratchThat => scratch_last_transcription(&app).await,
+                        _ => press_return_for(&app, command).await,
+                    };
+                    if let Err(err) = executed {
+                        eprintln!("[dictation] voice command {:?} failed: {}", command, err);
+                    }
+                }
+                guard.succeeded = true;
+                dictation_session::emit(
+                    &app,
+                    "backend-dictation-processing",
+                    Active { active: false },
+                );
+                dictation_session::finish_session("succeeded");
+                crate::overlay::hide_recording_overlay(&app);
+                return;
+            }
+        }
+
+        dictation_session::progress(&app, ProgressStage::PostProcessing, None);
+        let agent_name = match profile
+            .as_ref()
+            .and_then(|profile| profile.post_process_agent.clone())
+        {
+            Some(name) => Some(name.trim().to_string()).filter(|name| !name.is_empty()),
+            None => super::postprocessing::configured_agent(&app),
+        };
+        // A profile's agent (or its empty agent, which disables post-processing) takes
+        // precedence over the pipeline; the pipeline over `postProcessWithAgent`.
+        let pipeline = match profile
+            .as_ref()
+            .and_then(|profile| profile.post_process_agent.as_ref())
+        {
+            Some(_) => Vec::new(),
+            None => super::postprocessing::configured_pipeline(&app),
+        };
+        // (processed text, method, agent, error) of the post-processing step.
+        let (processed, method, agent, error) = match agent_name {
+            _ if !pipeline.is_empty() => {
+                let outcome =
+                    super::postprocessing::postprocess_with_pipeline(&app, &pipeline, &raw_text)
+                        .await;
+                let error = (!outcome.errors.is_empty()).then(|| outcome.errors.join("; "));
+                let method = outcome.method();
+                (outcome.processed, method, None, error)
+            }
+            Some(agent_name) => {
+                let agent =
+                    super::postprocessing::postprocess_with_agent(&app, agent_name, &raw_text)
+                        .await;
+                (
+                    agent.processed,
+                    "reasoning".to_string(),
+                    Some(agent.agent_name),
+                    agent.error,
+                )
+            }
+            None => {
+                let outcome = super::postprocessing::postprocess_transcription(
+                    app.clone(),
+                    raw_text.clone(),
+                    profile
+                        .as_ref()
+                        .and_then(|profile| profile.processing_mode.clone()),
+                )
+                .await;
+                (Some(outcome.text), outcome.method, None, None)
+            }
+        };
+        if superseded() {
+            return;
+        }
+
+        // Expand before saving so history holds exactly what gets pasted.
+        let final_text = processed.as_deref().unwrap_or(&raw_text);
+        let final_text = super::text_expander::apply_text_commands(&app, final_text);
+        let final_text = super::text_expander::apply_text_substitutions(&app, &final_text);
+        let final_text = super::text_expander::apply_number_format(&app, &final_text);
+        let final_text = super::text_expander::apply_abbreviations(&app, &final_text);
+        let final_text = super::text_expander::apply_smart_formatting(&app, &final_text);
+
+        let processed = (processed.is_some() || final_text != raw_text).then(|| final_text.clone());
+        let saved_id = super::database::db_save_transcription(
             app.clone(),
             raw_text,
-            Some(outcome.text.clone()),
-            Some(outcome.method.clone()),
-            None,
-        );
+            processed,
+            Some(method),
+            agent,
+            result.audio_path,
+            profile_name,
+        )
+        .ok();
+        if let (Some(id), Some(err)) = (saved_id, error.as_deref()) {
+            let _ = super::database::set_transcription_error(&app, id, err);
+        }
 
-        if let Err(err) = super::clipboard::paste_text(app.clone(), outcome.text.clone()) {
-            let _ = app.emit("backend-dictation-processing", false);
-            let _ = app.emit("backend-dictation-error", err);
-            crate::overlay::hide_recording_overlay(&app);
+        if superseded() {
             return;
         }
+        dictation_session::progress(&app, ProgressStage::Pasting, None);
+        if inserts && voice_command == Some(VoiceCommand::ScratchThat) {
+            if let Err(err) = scratch_last_transcription(&app).await {
+                eprintln!("[dictation] scratch that failed: {}", err);
+            }
+        }
+        let delivered = match paste_mode {
+            PasteMode::Type if auto_paste => {
+                super::clipboard::paste_text_by_typing(app.clone(), final_text.clone())
+                    .await
+                    .map(|_| "typing")
+            }
+            PasteMode::Paste if auto_paste => {
+                super::clipboard::insert_text(&app, &final_text, accessibility_insert_enabled(&app))
+                    .await
+                    .map(super::clipboard::InsertMethod::as_str)
+            }
+            _ => super::clipboard::write_clipboard(final_text.clone()).map(|_| "clipboard"),
+        };
+        let method = match delivered {
+            Ok(method) => method,
+            Err(err) => {
+                dictation_session::emit(
+                    &app,
+                    "backend-dictation-processing",
+                    Active { active: false },
+                );
+                emit_dictation_error(&app, err);
+                crate::overlay::hide_recording_overlay(&app);
+                return;
+            }
+        };
 
-        let _ = app.emit("backend-dictation-result", outcome.text);
+        dictation_session::mark(SessionStage::PasteEnd);
+        if let Some(command) = voice_command.filter(|_| inserts) {
+            if let Err(err) = press_return_for(&app, command).await {
+                eprintln!("[dictation] voice command {:?} failed: {}", command, err);
+            }
+        }
 
-        let _ = app.emit("backend-dictation-processing", false);
+        if !auto_paste {
+            dictation_session::emit(
+                &app,
+                "backend-dictation-ready-to-paste",
+                Text { text: &final_text },
+            );
+        } else if paste_mode == PasteMode::CopyOnly {
+            dictation_session::emit(&app, "backend-dictation-copied", Text { text: &final_text });
+        }
+        set_last_transcription(&app, &final_text);
+        if let Some(id) = saved_id {
+            let word_count = super::text_expander::count_words(&final_text);
+            let _ = super::database::set_transcription_stats(
+                &app,
+                id,
+                word_count,
+                result.duration_seconds,
+            );
+        }
+        dictation_session::emit(
+            &app,
+            "backend-dictation-result",
+            DictationResultPayload {
+                text: final_text,
+                repeated: false,
+                method,
+            },
+        );
+        super::sound_feedback::play_feedback(&app, FeedbackSound::Success);
+        guard.succeeded = true;
+        if inserts {
+            schedule_auto_hide(&app);
+        }
+
+        dictation_session::emit(
+            &app,
+            "backend-dictation-processing",
+            Active { active: false },
+        );
+        if let (Some(latency_ms), Some(id)) =
+            (dictation_session::finish_session("succeeded"), saved_id)
+        {
+            let _ = super::database::set_transcription_latency(&app, id, latency_ms);
+        }
         crate::overlay::hide_recording_overlay(&app);
-    });
+    })
 }
 
-#[cfg(target_os = "macos")]
 pub fn init_dictation_coordinator(app: &AppHandle) {
     if app.try_state::<DictationCoordinator>().is_some() {
         return;
     }
+    ensure_last_transcription(app);
     app.manage(DictationCoordinator::new(app.clone()));
 }
 
 /// Called by the global-hotkey callback. Keep this fast and non-panicking.
-#[cfg(target_os = "macos")]
 pub fn handle_hotkey_event(
     app: AppHandle,
     hotkey_string: String,
@@ -310,24 +1897,50 @@ pub fn handle_hotkey_event(
         init_dictation_coordinator(&app);
     }
     let push_to_talk = push_to_talk_override.unwrap_or_else(|| is_push_to_talk(&app));
+    // Overrides come from press-only triggers (double-press mode, action hotkeys), which
+    // never deliver the release needed to end a hold.
+    let detect_hold = push_to_talk_override.is_none() && !push_to_talk;
+    if let Some(coordinator) = app.try_state::<DictationCoordinator>() {
+        coordinator.send_input(&hotkey_string, is_pressed, push_to_talk, detect_hold);
+    } else {
+        eprintln!("[dictation] coordinator unavailable");
+    }
+}
+
+/// Called for hotkeys bound to a specific action. `Toggle` behaves like a tap on the
+/// dictation hotkey; `PasteLast` and `RepeatLast` are handled by the hotkey module directly.
+pub fn handle_action_event(app: AppHandle, hotkey_string: String, action: DictationAction) {
+    if action == DictationAction::Toggle {
+        handle_hotkey_event(app, hotkey_string, true, Some(false));
+        return;
+    }
+    if app.try_state::<DictationCoordinator>().is_none() {
+        init_dictation_coordinator(&app);
+    }
     if let Some(coordinator) = app.try_state::<DictationCoordinator>() {
-        coordinator.send_input(&hotkey_string, is_pressed, push_to_talk);
+        coordinator.send_action(action);
     } else {
         eprintln!("[dictation] coordinator unavailable");
     }
 }
 
-#[cfg(not(target_os = "macos"))]
-pub fn init_dictation_coordinator(_app: &AppHandle) {
-    // no-op
+/// Return the coordinator to idle if it is waiting on a transcription.
+pub fn cancel_dictation_processing(app: &AppHandle) {
+    if let Some(coordinator) = app.try_state::<DictationCoordinator>() {
+        coordinator.send_cancel_processing();
+    }
 }
 
-#[cfg(not(target_os = "macos"))]
-pub fn handle_hotkey_event(
-    _app: AppHandle,
-    _hotkey_string: String,
-    _is_pressed: bool,
-    _push_to_talk_override: Option<bool>,
-) {
-    // no-op
+/// Manual recovery when dictation looks stuck: abort any in-flight transcription, discard
+/// an active recording and return the coordinator to idle.
+#[tauri::command]
+pub fn reset_dictation_state(app: AppHandle) -> Result<(), String> {
+    if app.try_state::<DictationCoordinator>().is_none() {
+        init_dictation_coordinator(&app);
+    }
+    let coordinator = app
+        .try_state::<DictationCoordinator>()
+        .ok_or_else(|| "Dictation coordinator unavailable".to_string())?;
+    coordinator.send_reset();
+    Ok(())
 }
@@ -0,0 +1,231 @@
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Wall-clock milliseconds (since the Unix epoch) at which the session reached each stage.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTimings {
+    pub record_start: Option<u64>,
+    pub record_stop: Option<u64>,
+    pub transcribe_start: Option<u64>,
+    pub transcribe_end: Option<u64>,
+    pub paste_end: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SessionStage {
+    RecordStart,
+    RecordStop,
+    TranscribeStart,
+    TranscribeEnd,
+    PasteEnd,
+}
+
+/// Finer-grained steps of processing, reported as `backend-dictation-progress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStage {
+    FinalizingAudio,
+    Uploading,
+    WaitingForProvider,
+    PostProcessing,
+    Pasting,
+}
+
+impl ProgressStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::FinalizingAudio => "finalizing-audio",
+            Self::Uploading => "uploading",
+            Self::WaitingForProvider => "waiting-for-provider",
+            Self::PostProcessing => "post-processing",
+            Self::Pasting => "pasting",
+        }
+    }
+}
+
+/// Byte counts attached to `uploading` progress events.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadDetail {
+    pub sent_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone)]
+struct DictationSession {
+    id: String,
+    timings: SessionTimings,
+    progress: Option<ProgressStage>,
+}
+
+/// The dictation in progress; events emitted between sessions carry no id.
+static CURRENT_SESSION: Mutex<Option<DictationSession>> = Mutex::new(None);
+
+fn current_session() -> std::sync::MutexGuard<'static, Option<DictationSession>> {
+    CURRENT_SESSION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Envelope for every `backend-dictation-*` event: the session it belongs to, the stage
+/// timestamps so far and the event's own fields.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DictationEvent<T: Serialize> {
+    session_id: Option<String>,
+    timings: SessionTimings,
+    #[serde(flatten)]
+    data: T,
+}
+
+/// Payload fields of on/off events (`recording`, `processing`, `paused`, ...).
+#[derive(Clone, Serialize)]
+pub struct Active {
+    pub active: bool,
+}
+
+/// Payload fields of events that carry text (`copied`, `partial`).
+#[derive(Clone, Serialize)]
+pub struct Text<'a> {
+    pub text: &'a str,
+}
+
+/// Payload fields of `backend-dictation-error`.
+#[derive(Clone, Serialize)]
+pub struct ErrorMessage<'a> {
+    pub message: &'a str,
+}
+
+/// Payload of events that carry nothing beyond the session (`start-feedback`, `cancelled`).
+#[derive(Clone, Serialize)]
+pub struct NoData {}
+
+/// Payload fields of `backend-dictation-progress`.
+#[derive(Clone, Serialize)]
+struct Progress {
+    stage: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<UploadDetail>,
+}
+
+/// Start a new session with a fresh id, replacing any that was left open.
+pub fn begin_session() -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    *current_session() = Some(DictationSession {
+        id: id.clone(),
+        timings: SessionTimings::default(),
+        progress: None,
+    });
+    id
+}
+
+/// Id of the open session, if any.
+pub fn current_id() -> Option<String> {
+    current_session().as_ref().map(|session| session.id.clone())
+}
+
+/// Whether `session_id` (from `current_id`) is still the open session, i.e. it hasn't been
+/// finished or replaced since.
+pub fn is_current(session_id: Option<&str>) -> bool {
+    current_session()
+        .as_ref()
+        .map(|session| session.id.as_str())
+        == session_id
+}
+
+pub fn mark(stage: SessionStage) {
+    let mut session = current_session();
+    let Some(session) = session.as_mut() else {
+        return;
+    };
+    let timings = &mut session.timings;
+    let slot = match stage {
+        SessionStage::RecordStart => &mut timings.record_start,
+        SessionStage::RecordStop => &mut timings.record_stop,
+        SessionStage::TranscribeStart => &mut timings.transcribe_start,
+        SessionStage::TranscribeEnd => &mut timings.transcribe_end,
+        SessionStage::PasteEnd => &mut timings.paste_end,
+    };
+    *slot = Some(now_ms());
+}
+
+/// Emit `event` tagged with the current session id and timings.
+pub fn emit<T: Serialize + Clone>(app: &AppHandle, event: &str, data: T) {
+    let (session_id, timings) = match current_session().as_ref() {
+        Some(session) => (Some(session.id.clone()), session.timings.clone()),
+        None => (None, SessionTimings::default()),
+    };
+    let _ = app.emit(
+        event,
+        DictationEvent {
+            session_id,
+            timings,
+            data,
+        },
+    );
+}
+
+/// Report a processing step. Only sent while a session is open, so transcriptions
+/// requested by the renderer stay quiet; the overlay follows each change of stage.
+pub fn progress(app: &AppHandle, stage: ProgressStage, detail: Option<UploadDetail>) {
+    let stage_changed = {
+        let mut session = current_session();
+        let Some(session) = session.as_mut() else {
+            return;
+        };
+        session.progress.replace(stage) != Some(stage)
+    };
+    if stage_changed {
+        crate::overlay::update_recording_overlay(
+            app,
+            crate::overlay::OverlayState::Progress(stage.as_str().to_string()),
+        );
+    }
+    emit(
+        app,
+        "backend-dictation-progress",
+        Progress {
+            stage: stage.as_str(),
+            detail,
+        },
+    );
+}
+
+/// Milliseconds from `from` to `to`, when both stages were reached.
+fn span(from: Option<u64>, to: Option<u64>) -> Option<u64> {
+    Some(to?.saturating_sub(from?))
+}
+
+fn format_span(ms: Option<u64>) -> String {
+    ms.map_or_else(|| "-".to_string(), |ms| format!("{}ms", ms))
+}
+
+/// Close the current session and log its latency breakdown on one line. Returns the
+/// total latency (recording stop to paste), which is what the user waits through.
+pub fn finish_session(outcome: &str) -> Option<u64> {
+    let session = current_session().take()?;
+    let t = &session.timings;
+    let total = span(t.record_stop, t.paste_end);
+    eprintln!(
+        "[dictation] session {} {}: recorded {}, stop->transcribe {}, transcribe {}, \
+         transcribe->paste {}, total {}",
+        session.id,
+        outcome,
+        format_span(span(t.record_start, t.record_stop)),
+        format_span(span(t.record_stop, t.transcribe_start)),
+        format_span(span(t.transcribe_start, t.transcribe_end)),
+        format_span(span(t.transcribe_end, t.paste_end)),
+        format_span(total),
+    );
+    total
+}
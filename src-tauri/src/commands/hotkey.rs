@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
@@ -5,10 +6,104 @@ use tauri::Manager;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
+use super::dictation::DictationAction;
+
 const DOUBLE_PRESS_WINDOW: Duration = Duration::from_millis(320);
 
 static HOTKEY_REGISTRATION_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
+/// Shortcuts the OS already owns; registering them either fails or breaks the system action.
+#[cfg(target_os = "macos")]
+const RESERVED_SHORTCUTS: &[(Option<Modifiers>, Code, &str)] = &[
+    (Some(Modifiers::META), Code::Space, "Spotlight (Cmd+Space)"),
+    (
+        Some(Modifiers::CONTROL),
+        Code::Space,
+        "input source switching (Ctrl+Space)",
+    ),
+    (
+        Some(Modifiers::CONTROL.union(Modifiers::META)),
+        Code::Space,
+        "Emoji & Symbols (Ctrl+Cmd+Space)",
+    ),
+    (
+        Some(Modifiers::META),
+        Code::Tab,
+        "the app switcher (Cmd+Tab)",
+    ),
+    (
+        Some(Modifiers::META),
+        Code::Backquote,
+        "window switching (Cmd+`)",
+    ),
+    (Some(Modifiers::META), Code::KeyQ, "Quit (Cmd+Q)"),
+    (
+        Some(Modifiers::CONTROL.union(Modifiers::META)),
+        Code::KeyQ,
+        "Lock Screen (Ctrl+Cmd+Q)",
+    ),
+    (
+        Some(Modifiers::META.union(Modifiers::ALT)),
+        Code::Escape,
+        "Force Quit (Cmd+Option+Esc)",
+    ),
+    (
+        Some(Modifiers::META.union(Modifiers::SHIFT)),
+        Code::Digit3,
+        "screenshot (Cmd+Shift+3)",
+    ),
+    (
+        Some(Modifiers::META.union(Modifiers::SHIFT)),
+        Code::Digit4,
+        "screenshot (Cmd+Shift+4)",
+    ),
+    (
+        Some(Modifiers::META.union(Modifiers::SHIFT)),
+        Code::Digit5,
+        "screenshot (Cmd+Shift+5)",
+    ),
+];
+
+#[cfg(target_os = "windows")]
+const RESERVED_SHORTCUTS: &[(Option<Modifiers>, Code, &str)] = &[
+    (Some(Modifiers::META), Code::KeyL, "Lock (Win+L)"),
+    (
+        Some(Modifiers::CONTROL.union(Modifiers::ALT)),
+        Code::Delete,
+        "Ctrl+Alt+Del",
+    ),
+    (
+        Some(Modifiers::CONTROL.union(Modifiers::SHIFT)),
+        Code::Escape,
+        "Task Manager (Ctrl+Shift+Esc)",
+    ),
+    (
+        Some(Modifiers::ALT),
+        Code::Tab,
+        "the app switcher (Alt+Tab)",
+    ),
+    (Some(Modifiers::ALT), Code::F4, "Close window (Alt+F4)"),
+    (Some(Modifiers::META), Code::Tab, "Task View (Win+Tab)"),
+    (Some(Modifiers::META), Code::KeyD, "Show desktop (Win+D)"),
+    (Some(Modifiers::META), Code::KeyE, "File Explorer (Win+E)"),
+    (Some(Modifiers::META), Code::KeyR, "Run (Win+R)"),
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const RESERVED_SHORTCUTS: &[(Option<Modifiers>, Code, &str)] = &[
+    (
+        Some(Modifiers::ALT),
+        Code::Tab,
+        "the window switcher (Alt+Tab)",
+    ),
+    (Some(Modifiers::ALT), Code::F4, "Close window (Alt+F4)"),
+    (
+        Some(Modifiers::CONTROL.union(Modifiers::ALT)),
+        Code::Delete,
+        "Ctrl+Alt+Del",
+    ),
+];
+
 #[derive(Default)]
 struct DictationHotkeyGestureState {
     last_press_at: Mutex<Option<Instant>>,
@@ -19,6 +114,12 @@ struct ClipboardHotkeyGestureState {
     last_press_at: Mutex<Option<Instant>>,
 }
 
+/// Shortcuts registered through `register_action_hotkey`, keyed by action name.
+#[derive(Default)]
+struct HotkeyRegistry {
+    shortcuts: Mutex<HashMap<String, Shortcut>>,
+}
+
 #[derive(Clone, Copy)]
 enum DictationTriggerMode {
     Single,
@@ -29,6 +130,7 @@ enum DictationTriggerMode {
 enum HotkeyAction {
     Dictation { trigger_mode: DictationTriggerMode },
     Clipboard,
+    Action(DictationAction),
 }
 
 #[derive(Clone, Debug, serde::Serialize)]
@@ -70,7 +172,6 @@ fn is_push_to_talk(app: &AppHandle) -> bool {
         .unwrap_or(false)
 }
 
-#[cfg(target_os = "macos")]
 fn is_volcengine_transcription(app: &AppHandle) -> bool {
     get_setting_string(app, "cloudTranscriptionProvider")
         .map(|provider| provider.trim().eq_ignore_ascii_case("volcengine"))
@@ -101,6 +202,12 @@ fn ensure_dictation_hotkey_gesture_state(app: &AppHandle) {
     }
 }
 
+fn ensure_hotkey_registry(app: &AppHandle) {
+    if app.try_state::<HotkeyRegistry>().is_none() {
+        app.manage(HotkeyRegistry::default());
+    }
+}
+
 fn ensure_clipboard_hotkey_gesture_state(app: &AppHandle) {
     if app.try_state::<ClipboardHotkeyGestureState>().is_none() {
         app.manage(ClipboardHotkeyGestureState::default());
@@ -113,29 +220,21 @@ fn dispatch_dictation_hotkey_event(
     is_pressed: bool,
     force_tap_mode: bool,
 ) {
-    #[cfg(target_os = "macos")]
-    {
-        if is_volcengine_transcription(&app_handle) {
-            let _ = hotkey_label;
-            emit_renderer_dictation_hotkey_event(app_handle, is_pressed, force_tap_mode);
-            return;
-        }
-
-        // On macOS, run hotkey dictation in the backend so it keeps working even if
-        // the renderer/webview is throttled while another app is fullscreen.
+    // Run hotkey dictation in the backend wherever we can record natively, so it keeps
+    // working even if the renderer/webview is throttled while another app (or a game) is
+    // fullscreen. Volcengine streaming still runs in the renderer.
+    if super::recording::native_recording_supported() && !is_volcengine_transcription(&app_handle) {
         super::dictation::handle_hotkey_event(
             app_handle,
             hotkey_label,
             is_pressed,
             force_tap_mode.then_some(false),
         );
+        return;
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        let _ = hotkey_label;
-        emit_renderer_dictation_hotkey_event(app_handle, is_pressed, force_tap_mode);
-    }
+    let _ = hotkey_label;
+    emit_renderer_dictation_hotkey_event(app_handle, is_pressed, force_tap_mode);
 }
 
 fn handle_dictation_hotkey_event(
@@ -206,6 +305,60 @@ fn handle_clipboard_hotkey_event(app_handle: AppHandle, is_pressed: bool) {
     }
 }
 
+fn handle_action_hotkey_event(
+    app_handle: AppHandle,
+    hotkey_label: String,
+    action: DictationAction,
+    is_pressed: bool,
+) {
+    if !is_pressed {
+        return;
+    }
+
+    if matches!(
+        action,
+        DictationAction::PasteLast | DictationAction::RepeatLast
+    ) {
+        tauri::async_runtime::spawn(async move {
+            let result = if action == DictationAction::PasteLast {
+                super::dictation::paste_last_transcription(app_handle.clone()).await
+            } else {
+                super::dictation::repeat_last_dictation(app_handle.clone()).await
+            };
+            if let Err(err) = result {
+                super::logging::component_log(
+                    &app_handle,
+                    "hotkey",
+                    "error",
+                    &format!("{} failed: {}", action.as_str(), err),
+                );
+            }
+        });
+        return;
+    }
+
+    // Language cycling only touches settings, so the coordinator handles it on every path.
+    if action == DictationAction::CycleLanguage
+        || (super::recording::native_recording_supported()
+            && !is_volcengine_transcription(&app_handle))
+    {
+        super::dictation::handle_action_event(app_handle, hotkey_label, action);
+        return;
+    }
+
+    let _ = hotkey_label;
+    let event = match action {
+        DictationAction::Toggle => "toggle-dictation",
+        DictationAction::Start => "start-dictation",
+        DictationAction::Stop => "stop-dictation",
+        DictationAction::Cancel | DictationAction::CancelDictation => "cancel-dictation",
+        DictationAction::PasteLast
+        | DictationAction::RepeatLast
+        | DictationAction::CycleLanguage => return,
+    };
+    let _ = app_handle.emit(event, ());
+}
+
 fn handle_hotkey_event(
     app_handle: AppHandle,
     hotkey_label: String,
@@ -217,6 +370,9 @@ fn handle_hotkey_event(
             handle_dictation_hotkey_event(app_handle, hotkey_label, trigger_mode, is_pressed)
         }
         HotkeyAction::Clipboard => handle_clipboard_hotkey_event(app_handle, is_pressed),
+        HotkeyAction::Action(action) => {
+            handle_action_hotkey_event(app_handle, hotkey_label, action, is_pressed)
+        }
     }
 }
 
@@ -244,7 +400,7 @@ fn validate_hotkey(
     key_code: Code,
 ) -> Result<(), String> {
     match action {
-        HotkeyAction::Dictation { .. } => {
+        HotkeyAction::Dictation { .. } | HotkeyAction::Action(_) => {
             let has_non_shift_modifier = modifiers.contains(Modifiers::CONTROL)
                 || modifiers.contains(Modifiers::ALT)
                 || modifiers.contains(Modifiers::META);
@@ -272,28 +428,54 @@ fn validate_hotkey(
     }
 }
 
-fn register_shortcut(
-    app: &AppHandle,
-    hotkey: &str,
-    action: HotkeyAction,
-) -> HotkeyRegistrationStatus {
-    let (modifiers, key_code) = match parse_hotkey(hotkey) {
-        Ok(parsed) => parsed,
-        Err(err) => return error_status(err),
-    };
+/// Description of the OS shortcut `modifiers`+`code` would collide with, if any.
+fn is_system_reserved_shortcut(modifiers: Modifiers, code: Code) -> Option<&'static str> {
+    RESERVED_SHORTCUTS
+        .iter()
+        .find(|(reserved_modifiers, reserved_code, _)| {
+            *reserved_code == code
+                && reserved_modifiers.unwrap_or_else(Modifiers::empty) == modifiers
+        })
+        .map(|(_, _, name)| *name)
+}
 
-    if let Err(err) = validate_hotkey(action, modifiers, key_code) {
-        return error_status(err);
+fn ensure_not_reserved(modifiers: Modifiers, code: Code) -> Result<(), String> {
+    match is_system_reserved_shortcut(modifiers, code) {
+        Some(name) => Err(format!("This hotkey conflicts with {}", name)),
+        None => Ok(()),
     }
+}
 
-    let shortcut = if modifiers.is_empty() {
+fn build_shortcut(hotkey: &str, action: HotkeyAction) -> Result<Shortcut, String> {
+    let (modifiers, key_code) = parse_hotkey(hotkey)?;
+    validate_hotkey(action, modifiers, key_code)?;
+    ensure_not_reserved(modifiers, key_code)?;
+
+    Ok(if modifiers.is_empty() {
         Shortcut::new(None, key_code)
     } else {
         Shortcut::new(Some(modifiers), key_code)
-    };
+    })
+}
+
+fn register_shortcut(
+    app: &AppHandle,
+    hotkey: &str,
+    action: HotkeyAction,
+) -> HotkeyRegistrationStatus {
+    match build_shortcut(hotkey, action) {
+        Ok(shortcut) => bind_shortcut(app, shortcut, hotkey.to_string(), action),
+        Err(err) => error_status(err),
+    }
+}
 
+fn bind_shortcut(
+    app: &AppHandle,
+    shortcut: Shortcut,
+    hotkey_label: String,
+    action: HotkeyAction,
+) -> HotkeyRegistrationStatus {
     let app_handle = app.clone();
-    let hotkey_label = hotkey.to_string();
     let manager = app.global_shortcut();
 
     match manager.on_shortcut(shortcut, move |_app, _shortcut, event| {
@@ -303,9 +485,19 @@ fn register_shortcut(
         let app_for_callback = app_handle.clone();
         tauri::async_runtime::spawn(async move {
             if is_pressed {
-                eprintln!("[hotkey] pressed: {}", hotkey_label);
+                super::logging::component_log(
+                    &app_for_callback,
+                    "hotkey",
+                    "info",
+                    &format!("pressed: {}", hotkey_label),
+                );
             } else {
-                eprintln!("[hotkey] released: {}", hotkey_label);
+                super::logging::component_log(
+                    &app_for_callback,
+                    "hotkey",
+                    "info",
+                    &format!("released: {}", hotkey_label),
+                );
             }
             handle_hotkey_event(app_for_callback, hotkey_label, action, is_pressed);
         });
@@ -315,6 +507,41 @@ fn register_shortcut(
     }
 }
 
+/// Bind Escape to cancel the dictation in progress. Returns `false` when Escape is already
+/// bound (e.g. as the user's cancel action hotkey), in which case it must be left alone.
+pub fn register_cancel_shortcut(app: &AppHandle) -> Result<bool, String> {
+    let shortcut = Shortcut::new(None, Code::Escape);
+    let manager = app.global_shortcut();
+    if manager.is_registered(shortcut) {
+        return Ok(false);
+    }
+
+    let app_handle = app.clone();
+    manager
+        .on_shortcut(shortcut, move |_app, _shortcut, event| {
+            if event.state != ShortcutState::Pressed {
+                return;
+            }
+            let app_for_callback = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                super::dictation::handle_action_event(
+                    app_for_callback,
+                    "Escape".to_string(),
+                    DictationAction::Cancel,
+                );
+            });
+        })
+        .map_err(|e| format!("Failed to register Escape: {}", e))?;
+    Ok(true)
+}
+
+/// Release Escape once the recording it cancels has ended, so it isn't swallowed globally.
+pub fn unregister_cancel_shortcut(app: &AppHandle) -> Result<(), String> {
+    app.global_shortcut()
+        .unregister(Shortcut::new(None, Code::Escape))
+        .map_err(|e| format!("Failed to unregister Escape: {}", e))
+}
+
 fn normalize_hotkey(hotkey: Option<String>) -> Option<String> {
     hotkey.and_then(|value| {
         let trimmed = value.trim().to_string();
@@ -337,6 +564,41 @@ fn parse_dictation_trigger_mode(mode: Option<String>) -> DictationTriggerMode {
     }
 }
 
+/// Re-bind action hotkeys after `unregister_all`; ones that now clash are dropped.
+fn rebind_action_hotkeys(app: &AppHandle) {
+    ensure_hotkey_registry(app);
+    let registry = app.state::<HotkeyRegistry>();
+    let mut shortcuts = registry
+        .shortcuts
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    shortcuts.retain(|name, shortcut| {
+        let Ok(action) = DictationAction::parse(name) else {
+            return false;
+        };
+        let status = bind_shortcut(
+            app,
+            *shortcut,
+            shortcut.into_string(),
+            HotkeyAction::Action(action),
+        );
+        if !status.success {
+            super::logging::component_log(
+                app,
+                "hotkey",
+                "warn",
+                &format!(
+                    "dropping '{}' hotkey: {}",
+                    name,
+                    status.message.unwrap_or_default()
+                ),
+            );
+        }
+        status.success
+    });
+}
+
 fn register_hotkeys_impl(
     app: &AppHandle,
     dictation_hotkey: Option<String>,
@@ -379,6 +641,8 @@ fn register_hotkeys_impl(
         None => ok_status(None),
     };
 
+    rebind_action_hotkeys(app);
+
     HotkeyRegistrationResult {
         dictation,
         clipboard,
@@ -388,6 +652,9 @@ fn register_hotkeys_impl(
 /// Register a global hotkey for dictation toggle
 #[tauri::command]
 pub async fn register_hotkey(app: AppHandle, hotkey: String) -> Result<bool, String> {
+    if let Ok((modifiers, key_code)) = parse_hotkey(hotkey.trim()) {
+        ensure_not_reserved(modifiers, key_code)?;
+    }
     let result = register_hotkeys_impl(&app, Some(hotkey), None, None);
     Ok(result.dictation.success)
 }
@@ -408,15 +675,267 @@ pub async fn register_hotkeys(
     ))
 }
 
+/// Register a hotkey for a single dictation action (`toggle`, `start`, `stop`, `cancel`,
+/// `paste-last` or `repeat-last`) alongside the existing hotkeys. Replaces any previous hotkey for that action.
+#[tauri::command]
+pub async fn register_action_hotkey(
+    app: AppHandle,
+    hotkey: String,
+    action: String,
+) -> Result<bool, String> {
+    let action = DictationAction::parse(&action)?;
+    let hotkey = normalize_hotkey(Some(hotkey)).ok_or_else(|| "Hotkey is empty".to_string())?;
+    let shortcut = build_shortcut(&hotkey, HotkeyAction::Action(action))?;
+
+    let _registration_guard = HOTKEY_REGISTRATION_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    ensure_hotkey_registry(&app);
+    let registry = app.state::<HotkeyRegistry>();
+    let mut shortcuts = registry
+        .shortcuts
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if let Some(previous) = shortcuts.remove(action.as_str()) {
+        let _ = app.global_shortcut().unregister(previous);
+    }
+
+    let status = bind_shortcut(&app, shortcut, hotkey, HotkeyAction::Action(action));
+    if status.success {
+        shortcuts.insert(action.as_str().to_string(), shortcut);
+    } else {
+        super::logging::component_log(
+            &app,
+            "hotkey",
+            "error",
+            &format!(
+                "failed to register '{}' hotkey: {}",
+                action.as_str(),
+                status.message.unwrap_or_default()
+            ),
+        );
+    }
+    Ok(status.success)
+}
+
 /// Unregister all global hotkeys
 #[tauri::command]
 pub async fn unregister_hotkeys(app: AppHandle) -> Result<(), String> {
     let manager = app.global_shortcut();
     manager.unregister_all().map_err(|e| e.to_string())?;
+    if let Some(registry) = app.try_state::<HotkeyRegistry>() {
+        if let Ok(mut shortcuts) = registry.shortcuts.lock() {
+            shortcuts.clear();
+        }
+    }
     Ok(())
 }
 
 /// Parse hotkey string into modifiers and key code
+/// Whether the main key of `hotkey` is physically held down right now, read straight from
+/// the OS so a key-up the global shortcut never delivered can be noticed. `None` where the
+/// platform (or key) can't be queried.
+pub fn is_hotkey_physically_down(hotkey: &str) -> Option<bool> {
+    let (_, key_code) = parse_hotkey(hotkey).ok()?;
+    key_state(key_code)
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn CGEventSourceKeyState(state_id: i32, key: u16) -> bool;
+}
+
+#[cfg(target_os = "macos")]
+fn key_state(key_code: Code) -> Option<bool> {
+    // kCGEventSourceStateCombinedSessionState
+    const COMBINED_SESSION_STATE: i32 = 0;
+    let virtual_key = mac_virtual_key(key_code)?;
+    Some(unsafe { CGEventSourceKeyState(COMBINED_SESSION_STATE, virtual_key) })
+}
+
+#[cfg(target_os = "windows")]
+fn key_state(key_code: Code) -> Option<bool> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+    let virtual_key = windows_virtual_key(key_code)?;
+    // The high bit is set while the key is down.
+    Some(unsafe { GetAsyncKeyState(virtual_key) } < 0)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn key_state(_key_code: Code) -> Option<bool> {
+    None
+}
+
+/// `kVK_*` virtual key codes for the keys `parse_hotkey` accepts.
+#[cfg(target_os = "macos")]
+fn mac_virtual_key(key_code: Code) -> Option<u16> {
+    let virtual_key = match key_code {
+        Code::Backspace => 0x33,
+        Code::Tab => 0x30,
+        Code::Enter => 0x24,
+        Code::Escape => 0x35,
+        Code::Space => 0x31,
+        Code::PageUp => 0x74,
+        Code::PageDown => 0x79,
+        Code::End => 0x77,
+        Code::Home => 0x73,
+        Code::ArrowLeft => 0x7B,
+        Code::ArrowUp => 0x7E,
+        Code::ArrowRight => 0x7C,
+        Code::ArrowDown => 0x7D,
+        Code::Insert => 0x72,
+        Code::Delete => 0x75,
+        Code::Digit0 => 0x1D,
+        Code::Digit1 => 0x12,
+        Code::Digit2 => 0x13,
+        Code::Digit3 => 0x14,
+        Code::Digit4 => 0x15,
+        Code::Digit5 => 0x17,
+        Code::Digit6 => 0x16,
+        Code::Digit7 => 0x1A,
+        Code::Digit8 => 0x1C,
+        Code::Digit9 => 0x19,
+        Code::KeyA => 0x00,
+        Code::KeyB => 0x0B,
+        Code::KeyC => 0x08,
+        Code::KeyD => 0x02,
+        Code::KeyE => 0x0E,
+        Code::KeyF => 0x03,
+        Code::KeyG => 0x05,
+        Code::KeyH => 0x04,
+        Code::KeyI => 0x22,
+        Code::KeyJ => 0x26,
+        Code::KeyK => 0x28,
+        Code::KeyL => 0x25,
+        Code::KeyM => 0x2E,
+        Code::KeyN => 0x2D,
+        Code::KeyO => 0x1F,
+        Code::KeyP => 0x23,
+        Code::KeyQ => 0x0C,
+        Code::KeyR => 0x0F,
+        Code::KeyS => 0x01,
+        Code::KeyT => 0x11,
+        Code::KeyU => 0x20,
+        Code::KeyV => 0x09,
+        Code::KeyW => 0x0D,
+        Code::KeyX => 0x07,
+        Code::KeyY => 0x10,
+        Code::KeyZ => 0x06,
+        Code::F1 => 0x7A,
+        Code::F2 => 0x78,
+        Code::F3 => 0x63,
+        Code::F4 => 0x76,
+        Code::F5 => 0x60,
+        Code::F6 => 0x61,
+        Code::F7 => 0x62,
+        Code::F8 => 0x64,
+        Code::F9 => 0x65,
+        Code::F10 => 0x6D,
+        Code::F11 => 0x67,
+        Code::F12 => 0x6F,
+        Code::Semicolon => 0x29,
+        Code::Equal => 0x18,
+        Code::Comma => 0x2B,
+        Code::Minus => 0x1B,
+        Code::Period => 0x2F,
+        Code::Slash => 0x2C,
+        Code::Backquote => 0x32,
+        Code::BracketLeft => 0x21,
+        Code::Backslash => 0x2A,
+        Code::BracketRight => 0x1E,
+        Code::Quote => 0x27,
+        _ => return None,
+    };
+    Some(virtual_key)
+}
+
+/// `VK_*` virtual key codes for the keys `parse_hotkey` accepts.
+#[cfg(target_os = "windows")]
+fn windows_virtual_key(key_code: Code) -> Option<i32> {
+    let virtual_key = match key_code {
+        Code::Backspace => 0x08,
+        Code::Tab => 0x09,
+        Code::Enter => 0x0D,
+        Code::Escape => 0x1B,
+        Code::Space => 0x20,
+        Code::PageUp => 0x21,
+        Code::PageDown => 0x22,
+        Code::End => 0x23,
+        Code::Home => 0x24,
+        Code::ArrowLeft => 0x25,
+        Code::ArrowUp => 0x26,
+        Code::ArrowRight => 0x27,
+        Code::ArrowDown => 0x28,
+        Code::Insert => 0x2D,
+        Code::Delete => 0x2E,
+        Code::Digit0 => 0x30,
+        Code::Digit1 => 0x31,
+        Code::Digit2 => 0x32,
+        Code::Digit3 => 0x33,
+        Code::Digit4 => 0x34,
+        Code::Digit5 => 0x35,
+        Code::Digit6 => 0x36,
+        Code::Digit7 => 0x37,
+        Code::Digit8 => 0x38,
+        Code::Digit9 => 0x39,
+        Code::KeyA => 0x41,
+        Code::KeyB => 0x42,
+        Code::KeyC => 0x43,
+        Code::KeyD => 0x44,
+        Code::KeyE => 0x45,
+        Code::KeyF => 0x46,
+        Code::KeyG => 0x47,
+        Code::KeyH => 0x48,
+        Code::KeyI => 0x49,
+        Code::KeyJ => 0x4A,
+        Code::KeyK => 0x4B,
+        Code::KeyL => 0x4C,
+        Code::KeyM => 0x4D,
+        Code::KeyN => 0x4E,
+        Code::KeyO => 0x4F,
+        Code::KeyP => 0x50,
+        Code::KeyQ => 0x51,
+        Code::KeyR => 0x52,
+        Code::KeyS => 0x53,
+        Code::KeyT => 0x54,
+        Code::KeyU => 0x55,
+        Code::KeyV => 0x56,
+        Code::KeyW => 0x57,
+        Code::KeyX => 0x58,
+        Code::KeyY => 0x59,
+        Code::KeyZ => 0x5A,
+        Code::F1 => 0x70,
+        Code::F2 => 0x71,
+        Code::F3 => 0x72,
+        Code::F4 => 0x73,
+        Code::F5 => 0x74,
+        Code::F6 => 0x75,
+        Code::F7 => 0x76,
+        Code::F8 => 0x77,
+        Code::F9 => 0x78,
+        Code::F10 => 0x79,
+        Code::F11 => 0x7A,
+        Code::F12 => 0x7B,
+        Code::Semicolon => 0xBA,
+        Code::Equal => 0xBB,
+        Code::Comma => 0xBC,
+        Code::Minus => 0xBD,
+        Code::Period => 0xBE,
+        Code::Slash => 0xBF,
+        Code::Backquote => 0xC0,
+        Code::BracketLeft => 0xDB,
+        Code::Backslash => 0xDC,
+        Code::BracketRight => 0xDD,
+        Code::Quote => 0xDE,
+        _ => return None,
+    };
+    Some(virtual_key)
+}
+
 fn parse_hotkey(hotkey: &str) -> Result<(Modifiers, Code), String> {
     let parts: Vec<&str> = hotkey.split('+').map(|s| s.trim()).collect();
 
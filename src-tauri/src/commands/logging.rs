@@ -1,11 +1,53 @@
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use tauri::AppHandle;
-use tauri::Manager;
+use tauri::{Emitter, Listener, Manager};
 use tauri_plugin_opener::OpenerExt;
 
+const DEFAULT_LOG_MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_LOG_MAX_ROTATED_FILES: usize = 5;
+const DEFAULT_LOG_EXPORT_LINES: usize = 1000;
+const DEFAULT_LOG_RETENTION_DAYS: u64 = 7;
+// Logs bundled into a diagnostic report, when present.
+const DIAGNOSTIC_LOGS: &[&str] = &["renderer.log", "recording.log"];
+const REDACTED: &str = "[REDACTED]";
+// Backend subsystems that get their own file when `logPerComponent` is on.
+const LOG_COMPONENTS: &[&str] = &[
+    "recording",
+    "transcription",
+    "clipboard",
+    "hotkey",
+    "window",
+];
+
+/// Handle used by `log` for call sites (audio threads, provider helpers) that have none.
+static LOG_APP: OnceLock<AppHandle> = OnceLock::new();
+// Settings that decide whether and how a `component_log` line is written to its file.
+const COMPONENT_LOG_SETTINGS: &[&str] = &[
+    "logPerComponent",
+    "minLogLevel",
+    "logLevel",
+    "logMaxFileSizeBytes",
+    "logCompressRotated",
+    "logMaxRotatedFiles",
+];
+
+/// What `component_log` writes to the component files, cached so logging doesn't read
+/// settings.json on every line. Refreshed when one of `COMPONENT_LOG_SETTINGS` changes.
+#[derive(Clone, Copy)]
+struct ComponentLogConfig {
+    enabled: bool,
+    /// `log_severity` of the lowest level written.
+    min_severity: u8,
+    rotation: LogRotationSettings,
+}
+
+pub struct ComponentLogState(Mutex<ComponentLogConfig>);
+
 #[derive(Debug, Deserialize)]
 pub struct RendererLogEntry {
     pub level: String,
@@ -50,12 +92,17 @@ fn now_ms() -> u128 {
         .unwrap_or(0)
 }
 
+/// Cut `value` to at most `max_len` bytes, backing off to a char boundary.
 fn truncate_string(value: String, max_len: usize) -> String {
     if value.len() <= max_len {
         return value;
     }
+    let mut end = max_len;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
     let mut out = value;
-    out.truncate(max_len);
+    out.truncate(end);
     out
 }
 
@@ -121,6 +168,147 @@ fn set_log_level(app: &AppHandle, level: &str) -> Result<(), String> {
     save_settings(&path, &settings)
 }
 
+#[derive(Clone, Copy)]
+struct LogRotationSettings {
+    max_bytes: u64,
+    compress: bool,
+    max_rotated: usize,
+}
+
+fn read_log_rotation_settings(app: &AppHandle) -> Result<LogRotationSettings, String> {
+    Ok(log_rotation_settings(&load_settings(&settings_path(app)?)))
+}
+
+fn log_rotation_settings(
+    settings: &serde_json::Map<String, serde_json::Value>,
+) -> LogRotationSettings {
+    LogRotationSettings {
+        max_bytes: settings
+            .get("logMaxFileSizeBytes")
+            .and_then(|value| value.as_u64())
+            .unwrap_or(DEFAULT_LOG_MAX_FILE_SIZE_BYTES),
+        compress: settings
+            .get("logCompressRotated")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false),
+        max_rotated: settings
+            .get("logMaxRotatedFiles")
+            .and_then(|value| value.as_u64())
+            .map_or(DEFAULT_LOG_MAX_ROTATED_FILES, |value| value as usize),
+    }
+}
+
+fn gzip_file(path: &Path) -> Result<PathBuf, String> {
+    let mut gz_name = path.as_os_str().to_owned();
+    gz_name.push(".gz");
+    let gz_path = PathBuf::from(gz_name);
+
+    let mut input = fs::File::open(path).map_err(|e| e.to_string())?;
+    let output = fs::File::create(&gz_path).map_err(|e| e.to_string())?;
+    let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+    std::io::copy(&mut input, &mut encoder).map_err(|e| e.to_string())?;
+    encoder.finish().map_err(|e| e.to_string())?;
+    fs::remove_file(path).map_err(|e| e.to_string())?;
+    Ok(gz_path)
+}
+
+/// Rotated siblings of `path` (`<stem>.<ts_ms>.log[.gz]`), oldest first.
+fn rotated_logs(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(stem)) = (path.parent(), path.file_stem().and_then(|s| s.to_str())) else {
+        return Vec::new();
+    };
+    let prefix = format!("{stem}.");
+
+    let mut rotated: Vec<(u128, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let rest = name.strip_prefix(&prefix)?;
+                    let ts = rest
+                        .strip_suffix(".log.gz")
+                        .or_else(|| rest.strip_suffix(".log"))?;
+                    Some((ts.parse::<u128>().ok()?, entry.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    rotated.sort_by_key(|(ts, _)| *ts);
+    rotated.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Move `path` aside as `<stem>.<ts_ms>.log` once it exceeds `max_bytes`, optionally gzip
+/// it, and delete the oldest rotated files beyond `max_rotated`.
+fn rotate_log_if_needed(
+    path: &Path,
+    max_bytes: u64,
+    compress: bool,
+    max_rotated: usize,
+) -> Result<(), String> {
+    let len = match path.metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(_) => return Ok(()),
+    };
+    if len <= max_bytes {
+        return Ok(());
+    }
+
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("renderer");
+    let rotated = path.with_file_name(format!("{stem}.{}.log", now_ms()));
+    fs::rename(path, &rotated).map_err(|e| e.to_string())?;
+
+    if compress {
+        // Keep the uncompressed file if compression fails; it still counts toward the limit.
+        if let Err(err) = gzip_file(&rotated) {
+            eprintln!(
+                "[logging] failed to compress {}: {}",
+                rotated.display(),
+                err
+            );
+        }
+    }
+
+    let rotated = rotated_logs(path);
+    let excess = rotated.len().saturating_sub(max_rotated);
+    for old in &rotated[..excess] {
+        if let Err(err) = fs::remove_file(old) {
+            eprintln!("[logging] failed to delete {}: {}", old.display(), err);
+        }
+    }
+
+    Ok(())
+}
+
+/// Numeric severity of a level name; unknown names are treated as `info`.
+fn log_severity(level: &str) -> u8 {
+    match level.trim().to_ascii_lowercase().as_str() {
+        "trace" => 0,
+        "debug" => 1,
+        "warn" | "warning" => 3,
+        "error" | "fatal" => 4,
+        _ => 2,
+    }
+}
+
+/// Lowest level persisted to `renderer.log`: `minLogLevel`, else the debug toggle's
+/// `logLevel` (which defaults to `debug` in dev builds and `info` in release).
+fn read_min_log_level(app: &AppHandle) -> Result<String, String> {
+    let settings = load_settings(&settings_path(app)?);
+    match settings
+        .get("minLogLevel")
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+    {
+        Some(level) => Ok(level),
+        None => read_log_level(app),
+    }
+}
+
 fn is_debug_enabled(level: &str) -> bool {
     matches!(level.to_ascii_lowercase().as_str(), "trace" | "debug")
 }
@@ -135,8 +323,194 @@ fn debug_state(app: &AppHandle) -> Result<DebugState, String> {
     })
 }
 
+fn read_component_log_config(app: &AppHandle) -> ComponentLogConfig {
+    let settings = settings_path(app)
+        .map(|path| load_settings(&path))
+        .unwrap_or_default();
+    ComponentLogConfig {
+        enabled: settings
+            .get("logPerComponent")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        min_severity: read_min_log_level(app)
+            .map_or(log_severity("info"), |level| log_severity(&level)),
+        rotation: log_rotation_settings(&settings),
+    }
+}
+
+fn ensure_component_log_state(app: &AppHandle) {
+    if app.try_state::<ComponentLogState>().is_none() {
+        let config = read_component_log_config(app);
+        app.manage(ComponentLogState(Mutex::new(config)));
+    }
+}
+
+fn component_log_config(app: &AppHandle) -> ComponentLogConfig {
+    ensure_component_log_state(app);
+    *app.state::<ComponentLogState>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn refresh_component_log_config(app: &AppHandle) {
+    ensure_component_log_state(app);
+    let config = read_component_log_config(app);
+    *app.state::<ComponentLogState>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config;
+}
+
+fn append_component_line(
+    app: &AppHandle,
+    component: &str,
+    level: &str,
+    message: &str,
+    rotation: LogRotationSettings,
+) -> Result<(), String> {
+    let dir = logs_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let line = PersistedLogLine {
+        ts_ms: now_ms(),
+        level: level.to_string(),
+        scope: Some(component.to_string()),
+        message: truncate_string(message.to_string(), 8000),
+        meta: None,
+        source: Some("backend".to_string()),
+    };
+    let json = serde_json::to_string(&line).map_err(|e| e.to_string())?;
+
+    let file_path = dir.join(format!("{component}.log"));
+    if let Err(err) = rotate_log_if_needed(
+        &file_path,
+        rotation.max_bytes,
+        rotation.compress,
+        rotation.max_rotated,
+    ) {
+        eprintln!("[logging] failed to rotate {}.log: {}", component, err);
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file_path)
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", json).map_err(|e| e.to_string())
+}
+
+/// Backend log line for `component` (recording, transcription, clipboard, hotkey, window).
+/// Always mirrored to stderr; with `logPerComponent` on it is also appended to
+/// `logs/<component>.log` when at or above the minimum log level.
+pub fn component_log(app: &AppHandle, component: &str, level: &str, message: &str) {
+    eprintln!("[{}] {}", component, message);
+
+    if !LOG_COMPONENTS.contains(&component) {
+        return;
+    }
+    let config = component_log_config(app);
+    if !config.enabled || log_severity(level) < config.min_severity {
+        return;
+    }
+    if let Err(err) = append_component_line(app, component, level, message, config.rotation) {
+        eprintln!("[logging] failed to write {}.log: {}", component, err);
+    }
+}
+
+/// `component_log` for code without an `AppHandle`; stderr only until `init_logging` ran.
+pub fn log(component: &str, level: &str, message: &str) {
+    match LOG_APP.get() {
+        Some(app) => component_log(app, component, level, message),
+        None => eprintln!("[{}] {}", component, message),
+    }
+}
+
+pub fn init_logging(app: &AppHandle) {
+    let _ = LOG_APP.set(app.clone());
+
+    ensure_component_log_state(app);
+    let app_handle = app.clone();
+    app.listen_any("setting-changed", move |event| {
+        let changed = serde_json::from_str::<serde_json::Value>(event.payload())
+            .ok()
+            .and_then(|payload| payload.get("key")?.as_str().map(str::to_string));
+        if changed.is_some_and(|key| COMPONENT_LOG_SETTINGS.contains(&key.as_str())) {
+            refresh_component_log_config(&app_handle);
+        }
+    });
+
+    if let Err(err) = cleanup_old_logs(app.clone()) {
+        eprintln!("[logging] log cleanup failed: {}", err);
+    }
+}
+
+fn read_log_retention_days(app: &AppHandle) -> Result<u64, String> {
+    let settings = load_settings(&settings_path(app)?);
+    Ok(settings
+        .get("logRetentionDays")
+        .and_then(|value| value.as_u64())
+        .unwrap_or(DEFAULT_LOG_RETENTION_DAYS))
+}
+
+/// Delete `*.log` / `*.log.gz` files in the logs folder not modified within
+/// `logRetentionDays`. Returns how many files were removed.
+#[tauri::command]
+pub fn cleanup_old_logs(app: AppHandle) -> Result<u32, String> {
+    let dir = logs_dir(&app)?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let max_age = std::time::Duration::from_secs(read_log_retention_days(&app)? * 24 * 60 * 60);
+    let now = std::time::SystemTime::now();
+    let mut deleted = 0u32;
+    let mut freed_bytes = 0u64;
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !(name.ends_with(".log") || name.ends_with(".log.gz")) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        if age.is_none_or(|age| age <= max_age) {
+            continue;
+        }
+
+        match fs::remove_file(entry.path()) {
+            Ok(()) => {
+                eprintln!("[logging] deleted old log {}", name);
+                deleted += 1;
+                freed_bytes += metadata.len();
+            }
+            Err(err) => eprintln!("[logging] failed to delete {}: {}", name, err),
+        }
+    }
+
+    if deleted > 0 {
+        eprintln!(
+            "[logging] removed {} old log file(s), freed {} bytes",
+            deleted, freed_bytes
+        );
+    }
+    Ok(deleted)
+}
+
 #[tauri::command]
 pub fn write_renderer_log(app: AppHandle, entry: RendererLogEntry) -> Result<(), String> {
+    if log_severity(&entry.level) < log_severity(&read_min_log_level(&app)?) {
+        return Ok(());
+    }
+
     let dir = logs_dir(&app)?;
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let file_path = dir.join("renderer.log");
@@ -155,6 +529,17 @@ pub fn write_renderer_log(app: AppHandle, entry: RendererLogEntry) -> Result<(),
 
     let json = serde_json::to_string(&line).map_err(|e| e.to_string())?;
 
+    // Rotation problems shouldn't cost us the log line itself.
+    let rotation = read_log_rotation_settings(&app)?;
+    if let Err(err) = rotate_log_if_needed(
+        &file_path,
+        rotation.max_bytes,
+        rotation.compress,
+        rotation.max_rotated,
+    ) {
+        eprintln!("[logging] failed to rotate renderer.log: {}", err);
+    }
+
     // 1) Persist to file
     let mut file = OpenOptions::new()
         .create(true)
@@ -171,6 +556,60 @@ pub fn write_renderer_log(app: AppHandle, entry: RendererLogEntry) -> Result<(),
     Ok(())
 }
 
+/// Last `max_lines` (default 1000) entries of `renderer.log`, oldest first, optionally only
+/// those whose `level` matches `level_filter`. Lines that aren't valid JSON are skipped.
+#[tauri::command]
+pub fn read_renderer_logs(
+    app: AppHandle,
+    max_lines: Option<usize>,
+    level_filter: Option<String>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let path = renderer_log_path(&app)?;
+    let file = match fs::File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let max_lines = max_lines.unwrap_or(DEFAULT_LOG_EXPORT_LINES);
+    let level_filter = level_filter
+        .map(|level| level.trim().to_ascii_lowercase())
+        .filter(|level| !level.is_empty());
+
+    let mut entries = VecDeque::with_capacity(max_lines.min(DEFAULT_LOG_EXPORT_LINES));
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        if let Some(filter) = &level_filter {
+            let level = entry.get("level").and_then(|value| value.as_str());
+            if !level.is_some_and(|level| level.eq_ignore_ascii_case(filter)) {
+                continue;
+            }
+        }
+        if entries.len() == max_lines {
+            entries.pop_front();
+        }
+        if max_lines > 0 {
+            entries.push_back(entry);
+        }
+    }
+
+    Ok(entries.into())
+}
+
+#[tauri::command]
+pub fn get_log_file_path(app: AppHandle) -> Result<String, String> {
+    Ok(renderer_log_path(&app)?.to_string_lossy().to_string())
+}
+
+/// Current minimum level, so the renderer can skip building entries that would be dropped.
+#[tauri::command]
+pub fn get_log_level(app: AppHandle) -> Result<String, String> {
+    read_min_log_level(&app)
+}
+
 #[tauri::command]
 pub fn get_debug_state(app: AppHandle) -> Result<DebugState, String> {
     debug_state(&app)
@@ -180,6 +619,7 @@ pub fn get_debug_state(app: AppHandle) -> Result<DebugState, String> {
 pub fn set_debug_logging(app: AppHandle, enabled: bool) -> Result<DebugLoggingResult, String> {
     let level = if enabled { "debug" } else { "info" };
     set_log_level(&app, level)?;
+    refresh_component_log_config(&app);
 
     let path = renderer_log_path(&app)?;
     if let Some(parent) = path.parent() {
@@ -205,3 +645,271 @@ pub fn open_logs_folder(app: AppHandle) -> Result<(), String> {
         .open_path(dir.to_string_lossy().to_string(), None::<String>)
         .map_err(|e| e.to_string())
 }
+
+/// Add `contents` to a zip as a deflated `name` entry dated `modified`.
+fn add_to_zip<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    contents: &[u8],
+    modified: std::time::SystemTime,
+) -> Result<(), String> {
+    let mut options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(contents.len() as u64 >= u64::from(u32::MAX));
+    // Zip dates start in 1980; older files keep the writer's default date.
+    let modified = time::OffsetDateTime::from(modified);
+    if let Ok(modified) = zip::DateTime::try_from(time::PrimitiveDateTime::new(
+        modified.date(),
+        modified.time(),
+    )) {
+        options = options.last_modified_time(modified);
+    }
+    zip.start_file(name, options).map_err(|e| e.to_string())?;
+    zip.write_all(contents).map_err(|e| e.to_string())
+}
+
+/// Replace the value of every setting whose name mentions a key, token, secret or
+/// password, at any depth.
+fn redact_settings(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                let sensitive = ["key", "token", "secret", "password"]
+                    .iter()
+                    .any(|word| key.contains(word));
+                if sensitive && !value.is_null() {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_settings(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_settings),
+        _ => {}
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct MemoryInfo {
+    total_bytes: Option<u64>,
+    available_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct SystemInfo {
+    platform: &'static str,
+    arch: &'static str,
+    os_version: Option<String>,
+    app_version: String,
+    memory: MemoryInfo,
+}
+
+fn os_version() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    let version = std::process::Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    #[cfg(target_os = "windows")]
+    let version = std::process::Command::new("cmd")
+        .args(["/C", "ver"])
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let version = fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                line.strip_prefix("PRETTY_NAME=")
+                    .map(|name| name.trim_matches('"').to_string())
+            })
+        });
+
+    version.filter(|version| !version.is_empty())
+}
+
+fn memory_info() -> MemoryInfo {
+    #[cfg(target_os = "macos")]
+    {
+        let total_bytes = std::process::Command::new("sysctl")
+            .args(["-n", "hw.memsize"])
+            .output()
+            .ok()
+            .and_then(|output| String::from_utf8_lossy(&output.stdout).trim().parse().ok());
+        MemoryInfo {
+            total_bytes,
+            available_bytes: None,
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+        let mut status = MEMORYSTATUSEX {
+            dwLength: std::mem::size_of::<MEMORYSTATUSEX>() as u32,
+            ..Default::default()
+        };
+        match unsafe { GlobalMemoryStatusEx(&mut status) } {
+            Ok(()) => MemoryInfo {
+                total_bytes: Some(status.ullTotalPhys),
+                available_bytes: Some(status.ullAvailPhys),
+            },
+            Err(_) => MemoryInfo {
+                total_bytes: None,
+                available_bytes: None,
+            },
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+        let field = |name: &str| {
+            meminfo.lines().find_map(|line| {
+                let kb = line.strip_prefix(name)?.trim().strip_suffix("kB")?;
+                kb.trim().parse::<u64>().ok().map(|kb| kb * 1024)
+            })
+        };
+        MemoryInfo {
+            total_bytes: field("MemTotal:"),
+            available_bytes: field("MemAvailable:"),
+        }
+    }
+}
+
+fn write_diagnostic_report(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&app_data_dir).map_err(|e| e.to_string())?;
+    let path = app_data_dir.join(format!("diagnostic-{}.zip", now_ms()));
+    let file = fs::File::create(&path).map_err(|e| e.to_string())?;
+    if let Err(err) = write_diagnostic_zip(app, zip::ZipWriter::new(file)) {
+        let _ = fs::remove_file(&path);
+        return Err(err);
+    }
+    Ok(path)
+}
+
+fn write_diagnostic_zip(app: &AppHandle, mut zip: zip::ZipWriter<fs::File>) -> Result<(), String> {
+    let dir = logs_dir(app)?;
+    for name in DIAGNOSTIC_LOGS {
+        let path = dir.join(name);
+        let Ok(contents) = fs::read(&path) else {
+            continue;
+        };
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or_else(|_| std::time::SystemTime::now());
+        add_to_zip(&mut zip, name, &contents, modified)?;
+    }
+
+    let now = std::time::SystemTime::now();
+    let mut settings = serde_json::Value::Object(load_settings(&settings_path(app)?));
+    redact_settings(&mut settings);
+    let settings = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+    add_to_zip(&mut zip, "redacted-settings.json", &settings, now)?;
+
+    let system_info = SystemInfo {
+        platform: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        os_version: os_version(),
+        app_version: app.package_info().version.to_string(),
+        memory: memory_info(),
+    };
+    let system_info = serde_json::to_vec_pretty(&system_info).map_err(|e| e.to_string())?;
+    add_to_zip(&mut zip, "system-info.json", &system_info, now)?;
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Bundle the logs, redacted settings and system details into
+/// `diagnostic-<ts_ms>.zip` in the app data folder and return its path.
+#[tauri::command]
+pub async fn generate_diagnostic_report(app: AppHandle) -> Result<String, String> {
+    let app_for_task = app.clone();
+    let path = tauri::async_runtime::spawn_blocking(move || write_diagnostic_report(&app_for_task))
+        .await
+        .map_err(|e| e.to_string())??;
+    let path = path.to_string_lossy().to_string();
+    let _ = app.emit(
+        "diagnostic-report-generated",
+        serde_json::json!({ "path": path }),
+    );
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn add_to_zip_round_trips_through_a_zip_reader() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let log = "line\n".repeat(10_000);
+        add_to_zip(
+            &mut zip,
+            "app.log",
+            log.as_bytes(),
+            std::time::SystemTime::now(),
+        )
+        .unwrap();
+        add_to_zip(&mut zip, "empty.json", b"", std::time::UNIX_EPOCH).unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut entry = archive.by_name("app.log").unwrap();
+        assert_eq!(entry.compression(), zip::CompressionMethod::Deflated);
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, log);
+        drop(entry);
+        assert_eq!(archive.by_name("empty.json").unwrap().size(), 0);
+    }
+
+    #[test]
+    fn truncate_string_stops_at_a_char_boundary() {
+        // 'é' is two bytes and 'の' three, so byte 8000 falls inside a character.
+        let text = format!("{}é{}", "a".repeat(7999), "の".repeat(10));
+        let cut = truncate_string(text.clone(), 8000);
+        assert_eq!(cut, "a".repeat(7999));
+
+        let cut = truncate_string("ののの".to_string(), 7);
+        assert_eq!(cut, "のの");
+        assert_eq!(truncate_string("short".to_string(), 8000), "short");
+        assert_eq!(truncate_string("é".to_string(), 1), "");
+    }
+
+    #[test]
+    fn component_logs_rotate_like_renderer_log() {
+        let dir = std::env::temp_dir().join(format!("typefree-log-rotation-{}", now_ms()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("recording.log");
+        fs::write(&path, "x".repeat(100)).unwrap();
+
+        rotate_log_if_needed(&path, 200, false, 2).unwrap();
+        assert!(path.exists(), "under the limit");
+
+        for _ in 0..3 {
+            fs::write(&path, "x".repeat(300)).unwrap();
+            rotate_log_if_needed(&path, 200, false, 2).unwrap();
+            assert!(!path.exists());
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let rotated = rotated_logs(&path);
+        assert_eq!(rotated.len(), 2);
+        assert!(rotated.iter().all(|old| old
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("recording.")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,12 +2,19 @@ pub mod audio_ducking;
 pub mod clipboard;
 pub mod database;
 pub mod dictation;
+pub mod dictation_session;
 pub mod hotkey;
 pub mod logging;
+pub mod network;
+pub mod pending_recordings;
 pub mod postprocessing;
 pub mod reasoning;
 pub mod recording;
 pub mod settings;
+pub mod settings_schema;
+pub mod sound_feedback;
+pub mod text_expander;
 pub mod transcription;
+pub mod updater;
 pub mod vocabulary;
 pub mod window;
@@ -0,0 +1,57 @@
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy};
+use tauri::AppHandle;
+
+fn get_setting_string(app: &AppHandle, key: &str) -> Option<String> {
+    super::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().map(|s| s.trim().to_string()))
+        .filter(|s| !s.is_empty())
+}
+
+/// Proxy for outbound HTTP requests from `httpProxyUrl` (or the older `proxyUrl` key).
+///
+/// Supported schemes are `http://`, `https://` and `socks5://` (`socks5h://` resolves DNS
+/// through the proxy), e.g. `http://proxy.corp.com:8080`. WebSocket streaming sessions
+/// connect directly and are not routed through this proxy.
+fn build_proxy(app: &AppHandle) -> Result<Option<Proxy>, String> {
+    let Some(proxy_url) =
+        get_setting_string(app, "httpProxyUrl").or_else(|| get_setting_string(app, "proxyUrl"))
+    else {
+        return Ok(None);
+    };
+
+    let mut proxy = Proxy::all(proxy_url.as_str())
+        .map_err(|e| format!("Invalid proxy URL '{proxy_url}': {e}"))?;
+
+    if let Some(username) = get_setting_string(app, "proxyUsername") {
+        let password = get_setting_string(app, "proxyPassword").unwrap_or_default();
+        proxy = proxy.basic_auth(&username, &password);
+    }
+
+    // Same syntax as the NO_PROXY environment variable: comma-separated hosts,
+    // domain suffixes (".corp.example.com") and CIDR ranges.
+    if let Some(no_proxy) = get_setting_string(app, "noProxyHosts") {
+        proxy = proxy.no_proxy(NoProxy::from_string(&no_proxy));
+    }
+
+    Ok(Some(proxy))
+}
+
+/// Client builder for outbound API calls with the user's proxy settings applied.
+///
+/// Settings are read on every call, so proxy changes take effect on the next request
+/// without restarting the app.
+pub fn http_client_builder(app: &AppHandle) -> Result<ClientBuilder, String> {
+    let builder = Client::builder();
+    match build_proxy(app)? {
+        Some(proxy) => Ok(builder.proxy(proxy)),
+        None => Ok(builder),
+    }
+}
+
+pub fn build_http_client(app: &AppHandle) -> Result<Client, String> {
+    http_client_builder(app)?
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))
+}
@@ -0,0 +1,320 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+
+use super::transcription::{
+    check_network_connectivity, is_retryable_error, TranscriptionError, NO_CONNECTION_ERROR,
+};
+
+/// How often the worker checks whether queued recordings can be transcribed.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Failed transcriptions of one recording before it is set aside.
+const MAX_ATTEMPTS: u32 = 5;
+const PENDING_DIR: &str = "pending-recordings";
+/// Under `PENDING_DIR`: recordings that were given up on, kept for the user.
+const FAILED_DIR: &str = "failed";
+
+/// A recording made while offline, waiting to be transcribed. Each one is stored as
+/// `{id}.wav` plus `{id}.json` with the metadata, so the queue survives restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRecording {
+    pub id: u64,
+    #[serde(skip)]
+    pub audio_data: Vec<u8>,
+    pub provider: String,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    /// Unix time in milliseconds.
+    pub recorded_at: u64,
+    /// Failed transcriptions so far.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Queued recordings, oldest first.
+pub struct PendingQueue(Mutex<Vec<PendingRecording>>);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingTranscribed {
+    /// History entry id, when the text was saved.
+    id: Option<i64>,
+    text: String,
+    recorded_at: u64,
+    remaining: usize,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingFailed {
+    recorded_at: u64,
+    error: String,
+    /// Where the audio was moved to, if that worked.
+    audio_path: Option<String>,
+    remaining: usize,
+}
+
+fn pending_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data_dir.join(PENDING_DIR))
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Read queued recordings left on disk by a previous run.
+fn load_pending(app: &AppHandle) -> Vec<PendingRecording> {
+    let Ok(dir) = pending_dir(app) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut pending: Vec<PendingRecording> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|meta_path| {
+            let loaded = std::fs::read(&meta_path)
+                .map_err(|e| e.to_string())
+                .and_then(|raw| {
+                    serde_json::from_slice::<PendingRecording>(&raw).map_err(|e| e.to_string())
+                })
+                .and_then(|mut recording| {
+                    recording.audio_data = std::fs::read(meta_path.with_extension("wav"))
+                        .map_err(|e| e.to_string())?;
+                    Ok(recording)
+                });
+            loaded
+                .map_err(|err| {
+                    eprintln!(
+                        "[pending] skipping unreadable {}: {}",
+                        meta_path.display(),
+                        err
+                    );
+                })
+                .ok()
+        })
+        .collect();
+    pending.sort_by_key(|recording| recording.id);
+    pending
+}
+
+fn ensure_pending_queue(app: &AppHandle) {
+    if app.try_state::<PendingQueue>().is_none() {
+        app.manage(PendingQueue(Mutex::new(load_pending(app))));
+    }
+}
+
+fn write_metadata(dir: &std::path::Path, recording: &PendingRecording) -> Result<(), String> {
+    let meta = serde_json::to_vec(recording).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(format!("{:06}.json", recording.id)), meta).map_err(|e| e.to_string())
+}
+
+/// Move a recording's audio to `FAILED_DIR` and drop its metadata, so it is no longer
+/// loaded. Returns the new audio path.
+fn move_to_failed(app: &AppHandle, recording: &PendingRecording) -> Result<PathBuf, String> {
+    let dir = pending_dir(app)?;
+    let failed_dir = dir.join(FAILED_DIR);
+    std::fs::create_dir_all(&failed_dir).map_err(|e| e.to_string())?;
+    // Ids restart once the queue is empty; the timestamp keeps names unique.
+    let dest = failed_dir.join(format!("{}-{:06}.wav", recording.recorded_at, recording.id));
+    std::fs::rename(dir.join(format!("{:06}.wav", recording.id)), &dest)
+        .map_err(|e| e.to_string())?;
+    let _ = std::fs::remove_file(dir.join(format!("{:06}.json", recording.id)));
+    Ok(dest)
+}
+
+fn remove_files(app: &AppHandle, id: u64) {
+    let Ok(dir) = pending_dir(app) else {
+        return;
+    };
+    for ext in ["json", "wav"] {
+        let _ = std::fs::remove_file(dir.join(format!("{id:06}.{ext}")));
+    }
+}
+
+/// Save a recording for later transcription. Returns the number of queued recordings.
+pub fn enqueue_recording(
+    app: &AppHandle,
+    audio_data: Vec<u8>,
+    provider: String,
+    model: Option<String>,
+    language: Option<String>,
+) -> Result<usize, String> {
+    ensure_pending_queue(app);
+    let state = app.state::<PendingQueue>();
+    let mut queue = state.0.lock().map_err(|e| e.to_string())?;
+
+    let dir = pending_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let recording = PendingRecording {
+        id: queue.last().map_or(1, |last| last.id + 1),
+        audio_data,
+        provider,
+        model,
+        language,
+        recorded_at: now_ms(),
+        attempts: 0,
+    };
+    std::fs::write(
+        dir.join(format!("{:06}.wav", recording.id)),
+        &recording.audio_data,
+    )
+    .map_err(|e| e.to_string())?;
+    // Written last: a recording without metadata is never loaded.
+    write_metadata(&dir, &recording)?;
+
+    queue.push(recording);
+    Ok(queue.len())
+}
+
+/// Count a failed attempt. Recordings the provider rejected outright (4xx other than 429)
+/// or that failed `MAX_ATTEMPTS` times are moved aside and reported with
+/// `backend-pending-recording-failed`; others stay queued for the next round.
+fn record_failure(app: &AppHandle, recording: &PendingRecording, error: &TranscriptionError) {
+    let attempts = recording.attempts + 1;
+    eprintln!(
+        "[pending] recording {} failed (attempt {}/{}): {}",
+        recording.id, attempts, MAX_ATTEMPTS, error
+    );
+    let rejected = error.status.is_some() && !is_retryable_error(error);
+    let state = app.state::<PendingQueue>();
+    let Ok(mut queue) = state.0.lock() else {
+        return;
+    };
+
+    if !rejected && attempts < MAX_ATTEMPTS {
+        if let Some(queued) = queue.iter_mut().find(|queued| queued.id == recording.id) {
+            queued.attempts = attempts;
+            if let Err(err) = pending_dir(app).and_then(|dir| write_metadata(&dir, queued)) {
+                eprintln!("[pending] failed to save attempt count: {}", err);
+            }
+        }
+        return;
+    }
+
+    let audio_path = move_to_failed(app, recording)
+        .map_err(|err| eprintln!("[pending] failed to move recording aside: {}", err))
+        .ok();
+    if audio_path.is_none() {
+        remove_files(app, recording.id);
+    }
+    queue.retain(|queued| queued.id != recording.id);
+    let _ = app.emit(
+        "backend-pending-recording-failed",
+        PendingFailed {
+            recorded_at: recording.recorded_at,
+            error: error.message.clone(),
+            audio_path: audio_path.map(|path| path.to_string_lossy().to_string()),
+            remaining: queue.len(),
+        },
+    );
+}
+
+/// Transcribe queued recordings in order. Stops early if the connection drops again;
+/// other failures go through `record_failure`.
+async fn drain_queue(app: &AppHandle) {
+    let pending = match app.state::<PendingQueue>().0.lock() {
+        Ok(queue) => queue.clone(),
+        Err(_) => return,
+    };
+
+    for recording in pending {
+        let transcribed = super::transcription::transcribe(
+            app,
+            recording.audio_data.clone(),
+            recording.provider.clone(),
+            recording.model.clone(),
+            recording.language.clone(),
+            // Cancelling a dictation must not abort the queue, and vice versa.
+            CancellationToken::new(),
+        )
+        .await;
+        let text = match transcribed {
+            Ok(text) => text,
+            Err(err) if err.message == NO_CONNECTION_ERROR => break,
+            Err(err) => {
+                record_failure(app, &recording, &err);
+                continue;
+            }
+        };
+
+        let id = if text.trim().is_empty() {
+            None
+        } else {
+            super::database::db_save_transcription(
+                app.clone(),
+                text.clone(),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .map_err(|err| eprintln!("[pending] failed to save transcription: {}", err))
+            .ok()
+        };
+
+        remove_files(app, recording.id);
+        let remaining = match app.state::<PendingQueue>().0.lock() {
+            Ok(mut queue) => {
+                queue.retain(|queued| queued.id != recording.id);
+                queue.len()
+            }
+            Err(_) => 0,
+        };
+        let _ = app.emit(
+            "backend-pending-recording-transcribed",
+            PendingTranscribed {
+                id,
+                text,
+                recorded_at: recording.recorded_at,
+                remaining,
+            },
+        );
+    }
+}
+
+/// Load recordings queued by a previous run and transcribe the queue whenever the
+/// provider of the oldest one can be reached again.
+pub fn start_pending_queue_worker(app: AppHandle) {
+    ensure_pending_queue(&app);
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let next_provider = app
+                .state::<PendingQueue>()
+                .0
+                .lock()
+                .ok()
+                .and_then(|queue| queue.first().map(|recording| recording.provider.clone()));
+            let Some(provider) = next_provider else {
+                continue;
+            };
+            if !check_network_connectivity(&app, &provider).await {
+                continue;
+            }
+            drain_queue(&app).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_pending_recordings_count(app: AppHandle) -> Result<usize, String> {
+    ensure_pending_queue(&app);
+    let state = app.state::<PendingQueue>();
+    let queue = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(queue.len())
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use reqwest::Client;
+use serde::Deserialize;
 use serde_json::{json, Value};
 use tauri::AppHandle;
 
@@ -9,6 +10,9 @@ const OPENAI_BASE: &str = "https://api.openai.com/v1";
 const GROQ_BASE: &str = "https://api.groq.com/openai/v1";
 const DEEPSEEK_BASE: &str = "https://api.deepseek.com";
 const GEMINI_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+const DEFAULT_AGENT_MODEL: &str = "claude-haiku-4-5";
+/// Request timeout for one post-processing call.
+pub const STEP_TIMEOUT: Duration = Duration::from_secs(60);
 
 const VOICE_POLISH_PROMPT: &str = r#"
 # Role
@@ -76,6 +80,43 @@ pub struct PostprocessOutcome {
     pub method: String,
 }
 
+/// Result of running a transcript through the `postProcessWithAgent` prompt template.
+#[derive(Debug, Clone)]
+pub struct AgentOutcome {
+    pub agent_name: String,
+    /// Agent output; `None` when the call failed and the raw transcript should be used.
+    pub processed: Option<String>,
+    pub error: Option<String>,
+}
+
+/// One step of the `dictationPipeline` setting: the prompt template (agent) to run and,
+/// optionally, the provider and model to run it with (default: Anthropic).
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineStep {
+    #[serde(alias = "prompt")]
+    pub agent: String,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+}
+
+/// Result of running a transcript through the `dictationPipeline` steps.
+#[derive(Debug, Clone)]
+pub struct PipelineOutcome {
+    /// Output of the last step that succeeded; `None` when none did.
+    pub processed: Option<String>,
+    /// Agents of the steps that succeeded, in order.
+    pub steps_run: Vec<String>,
+    /// One entry per failed (and skipped) step.
+    pub errors: Vec<String>,
+}
+
+impl PipelineOutcome {
+    /// `pipeline:cleanup>translate`, for the saved transcription's `processing_method`.
+    pub fn method(&self) -> String {
+        format!("pipeline:{}", self.steps_run.join(">"))
+    }
+}
+
 fn get_setting_string(app: &AppHandle, key: &str) -> Option<String> {
     super::settings::get_setting(app.clone(), key.to_string())
         .ok()
@@ -99,8 +140,9 @@ fn read_env_or_setting(app: &AppHandle, env_key: &str, setting_key: &str) -> Opt
         .filter(|value| !value.is_empty())
 }
 
-fn selected_mode(app: &AppHandle) -> String {
-    let mode = get_setting_string(app, "processingModeId")
+fn selected_mode(app: &AppHandle, mode_override: Option<String>) -> String {
+    let mode = mode_override
+        .or_else(|| get_setting_string(app, "processingModeId"))
         .unwrap_or_else(|| DEFAULT_PROCESSING_MODE_ID.to_string());
 
     match mode.as_str() {
@@ -524,8 +566,8 @@ async fn process_with_cloud_reasoning(
     system_prompt: &str,
     text: &str,
 ) -> Result<String, String> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(60))
+    let client = super::network::http_client_builder(app)?
+        .timeout(STEP_TIMEOUT)
         .build()
         .map_err(|e| e.to_string())?;
 
@@ -601,11 +643,17 @@ async fn process_with_cloud_reasoning(
     }
 }
 
-pub async fn postprocess_transcription(app: AppHandle, raw_text: String) -> PostprocessOutcome {
+/// Clean up a transcript with the selected processing mode; `mode_override` (e.g. from a
+/// dictation profile) takes precedence over `processingModeId`.
+pub async fn postprocess_transcription(
+    app: AppHandle,
+    raw_text: String,
+    mode_override: Option<String>,
+) -> PostprocessOutcome {
     let normalized_text = super::vocabulary::apply_snippet_replacements(&app, &raw_text)
         .trim()
         .to_string();
-    let mode = selected_mode(&app);
+    let mode = selected_mode(&app, mode_override);
 
     if normalized_text.is_empty() {
         return PostprocessOutcome {
@@ -666,3 +714,182 @@ pub async fn postprocess_transcription(app: AppHandle, raw_text: String) -> Post
         }
     }
 }
+
+/// Run the named prompt template over `text`. Without a provider the agent runs on
+/// Anthropic; without a model it reuses `reasoningModel` when that belongs to the provider.
+async fn run_agent(
+    app: &AppHandle,
+    agent_name: &str,
+    provider: Option<&str>,
+    model: Option<&str>,
+    text: &str,
+) -> Result<String, String> {
+    let template = super::database::get_prompt_template_by_name(app, agent_name)?;
+    let provider = provider
+        .map(str::trim)
+        .filter(|provider| !provider.is_empty())
+        .unwrap_or("anthropic");
+    let model = model
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+        .or_else(|| {
+            get_setting_string(app, "reasoningModel")
+                .map(|model| model.trim().to_string())
+                .filter(|model| !model.is_empty() && selected_provider(app, model) == provider)
+        });
+    let user_text = if template.user_template.trim().is_empty() {
+        text.to_string()
+    } else {
+        template.user_template.replace("{{text}}", text)
+    };
+
+    if provider != "anthropic" {
+        let model = model.ok_or_else(|| format!("No {provider} model configured"))?;
+        let text = process_with_cloud_reasoning(
+            app,
+            provider,
+            &model,
+            template.system_prompt.trim(),
+            &user_text,
+        )
+        .await?;
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Agent returned empty response".to_string());
+        }
+        return Ok(text.to_string());
+    }
+
+    let api_key = read_env_or_setting(app, "ANTHROPIC_API_KEY", "anthropicApiKey")
+        .ok_or_else(|| "Anthropic API key not configured".to_string())?;
+    let model = model.unwrap_or_else(|| DEFAULT_AGENT_MODEL.to_string());
+
+    let result = super::reasoning::process_anthropic_reasoning(
+        app.clone(),
+        super::reasoning::AnthropicReasoningRequest {
+            api_key,
+            model,
+            system_prompt: template.system_prompt,
+            text: user_text,
+            max_tokens: Some(4096),
+            temperature: Some(0.3),
+            template_id: None,
+        },
+    )
+    .await?;
+
+    match result.text.map(|text| text.trim().to_string()) {
+        Some(text) if result.success && !text.is_empty() => Ok(text),
+        _ => Err(result
+            .error
+            .unwrap_or_else(|| "Agent returned empty response".to_string())),
+    }
+}
+
+/// Agent named by the `postProcessWithAgent` setting, if any.
+pub fn configured_agent(app: &AppHandle) -> Option<String> {
+    get_setting_string(app, "postProcessWithAgent")
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Run the transcript through the named agent. Failures are reported in the outcome rather
+/// than returned, so dictation can still paste the raw transcript.
+pub async fn postprocess_with_agent(
+    app: &AppHandle,
+    agent_name: String,
+    raw_text: &str,
+) -> AgentOutcome {
+    eprintln!(
+        "[postprocessing] agent={} text_len={}",
+        agent_name,
+        raw_text.len()
+    );
+
+    match run_agent(app, &agent_name, None, None, raw_text).await {
+        Ok(text) => AgentOutcome {
+            agent_name,
+            processed: Some(text),
+            error: None,
+        },
+        Err(err) => {
+            eprintln!("[postprocessing] agent '{agent_name}' failed: {err}; using raw transcript");
+            AgentOutcome {
+                agent_name,
+                processed: None,
+                error: Some(err),
+            }
+        }
+    }
+}
+
+/// Steps from the `dictationPipeline` setting; empty when unset or invalid.
+pub fn configured_pipeline(app: &AppHandle) -> Vec<PipelineStep> {
+    let Ok(Some(value)) =
+        super::settings::get_setting(app.clone(), "dictationPipeline".to_string())
+    else {
+        return Vec::new();
+    };
+    match serde_json::from_value::<Vec<PipelineStep>>(value) {
+        Ok(steps) => steps
+            .into_iter()
+            .filter(|step| !step.agent.trim().is_empty())
+            .collect(),
+        Err(err) => {
+            eprintln!("[postprocessing] ignoring invalid dictationPipeline: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Run the transcript through each pipeline step in order, feeding each step's output to
+/// the next. A failed step is skipped (its input passes through) and recorded in `errors`.
+pub async fn postprocess_with_pipeline(
+    app: &AppHandle,
+    steps: &[PipelineStep],
+    raw_text: &str,
+) -> PipelineOutcome {
+    let mut outcome = PipelineOutcome {
+        processed: None,
+        steps_run: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut text = raw_text.to_string();
+
+    for (index, step) in steps.iter().enumerate() {
+        let agent = step.agent.trim();
+        eprintln!(
+            "[postprocessing] pipeline step {}/{} agent={} provider={} text_len={}",
+            index + 1,
+            steps.len(),
+            agent,
+            step.provider.as_deref().unwrap_or("anthropic"),
+            text.len()
+        );
+        match run_agent(
+            app,
+            agent,
+            step.provider.as_deref(),
+            step.model.as_deref(),
+            &text,
+        )
+        .await
+        {
+            Ok(output) => {
+                text = output;
+                outcome.steps_run.push(agent.to_string());
+            }
+            Err(err) => {
+                eprintln!("[postprocessing] pipeline step '{agent}' failed: {err}; skipping");
+                outcome
+                    .errors
+                    .push(format!("step {} ({}): {}", index + 1, agent, err));
+            }
+        }
+    }
+
+    if !outcome.steps_run.is_empty() {
+        outcome.processed = Some(text);
+    }
+    outcome
+}
@@ -1,5 +1,5 @@
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
 
 #[derive(Debug, Deserialize)]
 pub struct AnthropicReasoningRequest {
@@ -9,6 +9,52 @@ pub struct AnthropicReasoningRequest {
     pub text: String,
     pub max_tokens: Option<u32>,
     pub temperature: Option<f32>,
+    /// Stored prompt template to use instead of `system_prompt`; its `user_template` gets
+    /// `{{text}}` replaced with `text`.
+    pub template_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OpenAIReasoningRequest {
+    pub api_key: String,
+    pub model: String,
+    pub system_prompt: String,
+    pub text: String,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+}
+
+const OLLAMA_DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+fn default_ollama_base_url() -> String {
+    OLLAMA_DEFAULT_BASE_URL.to_string()
+}
+
+/// Local Ollama server; no API key, just the server's `base_url`.
+#[derive(Debug, Deserialize)]
+pub struct OllamaReasoningRequest {
+    #[serde(default = "default_ollama_base_url")]
+    pub base_url: String,
+    pub model: String,
+    pub system_prompt: String,
+    pub text: String,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+}
+
+/// Same fields as the per-provider requests plus `provider` (`anthropic`, `openai` or
+/// `ollama`). `api_key` may be omitted for Ollama, which uses `base_url` instead.
+#[derive(Debug, Deserialize)]
+pub struct UnifiedReasoningRequest {
+    pub provider: String,
+    #[serde(default)]
+    pub api_key: String,
+    pub base_url: Option<String>,
+    pub model: String,
+    pub system_prompt: String,
+    pub text: String,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -16,8 +62,39 @@ pub struct ReasoningResult {
     pub success: bool,
     pub text: Option<String>,
     pub error: Option<String>,
+    pub usage: Option<TokenUsage>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub struct TokenUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub estimated_cost_usd: f64,
+}
+
+/// USD per 1K (input, output) tokens, matched by model-name prefix, so more specific
+/// prefixes must come first. Unlisted models (and local Ollama models) are costed at zero.
+const MODEL_PRICES_PER_1K: &[(&str, &str, f64, f64)] = &[
+    ("anthropic", "claude-opus-4-5", 0.005, 0.025),
+    ("anthropic", "claude-opus-4", 0.015, 0.075),
+    ("anthropic", "claude-sonnet-4", 0.003, 0.015),
+    ("anthropic", "claude-3-7-sonnet", 0.003, 0.015),
+    ("anthropic", "claude-3-5-sonnet", 0.003, 0.015),
+    ("anthropic", "claude-haiku-4-5", 0.001, 0.005),
+    ("anthropic", "claude-3-5-haiku", 0.0008, 0.004),
+    ("anthropic", "claude-3-haiku", 0.00025, 0.00125),
+    ("openai", "gpt-5-nano", 0.00005, 0.0004),
+    ("openai", "gpt-5-mini", 0.00025, 0.002),
+    ("openai", "gpt-5", 0.00125, 0.01),
+    ("openai", "gpt-4.1-nano", 0.0001, 0.0004),
+    ("openai", "gpt-4.1-mini", 0.0004, 0.0016),
+    ("openai", "gpt-4.1", 0.002, 0.008),
+    ("openai", "gpt-4o-mini", 0.00015, 0.0006),
+    ("openai", "gpt-4o", 0.0025, 0.01),
+    ("openai", "o4-mini", 0.0011, 0.0044),
+    ("openai", "o3-mini", 0.0011, 0.0044),
+];
+
 #[derive(Debug, Deserialize)]
 struct AnthropicContentItem {
     #[serde(rename = "type")]
@@ -25,18 +102,237 @@ struct AnthropicContentItem {
     pub text: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
 #[derive(Debug, Deserialize)]
 struct AnthropicResponse {
     pub content: Vec<AnthropicContentItem>,
+    pub usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIMessage {
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIChoice {
+    pub message: OpenAIMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIChatResponse {
+    pub choices: Vec<OpenAIChoice>,
+    pub usage: Option<OpenAIUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    pub message: Option<OpenAIMessage>,
+    pub prompt_eval_count: Option<u32>,
+    pub eval_count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaModel {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    pub models: Vec<OllamaModel>,
 }
 
 #[tauri::command]
 pub async fn process_anthropic_reasoning(
+    app: AppHandle,
+    req: AnthropicReasoningRequest,
+) -> Result<ReasoningResult, String> {
+    let req = apply_prompt_template(&app, req)?;
+    anthropic_reasoning(&app, req).await
+}
+
+#[tauri::command]
+pub async fn process_openai_reasoning(
+    app: AppHandle,
+    req: OpenAIReasoningRequest,
+) -> Result<ReasoningResult, String> {
+    openai_reasoning(&app, req).await
+}
+
+#[tauri::command]
+pub async fn process_ollama_reasoning(
+    app: AppHandle,
+    req: OllamaReasoningRequest,
+) -> Result<ReasoningResult, String> {
+    ollama_reasoning(&app, req).await
+}
+
+/// Names of the models pulled on the Ollama server, for the model picker.
+#[tauri::command]
+pub async fn list_ollama_models(app: AppHandle, base_url: String) -> Result<Vec<String>, String> {
+    let client = super::network::build_http_client(&app)?;
+    let res = client
+        .get(format!("{}/api/tags", ollama_base_url(&base_url)))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Ollama: {}", e))?;
+
+    let status = res.status();
+    let body_text = res.text().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        return Err(format!(
+            "Ollama API error: {} {}",
+            status.as_u16(),
+            body_text
+        ));
+    }
+
+    let parsed: OllamaTagsResponse = serde_json::from_str(&body_text)
+        .map_err(|e| format!("Failed to parse Ollama model list: {}", e))?;
+    Ok(parsed.models.into_iter().map(|model| model.name).collect())
+}
+
+/// Stream a Claude response: emits `backend-reasoning-chunk` per text delta, then
+/// `backend-reasoning-done` (or `backend-reasoning-error`). Returns once the stream task is
+/// spawned; the renderer accumulates the chunks.
+#[tauri::command]
+pub async fn process_anthropic_reasoning_streaming(
+    app: AppHandle,
+    req: AnthropicReasoningRequest,
+) -> Result<(), String> {
+    let req = apply_prompt_template(&app, req)?;
+    let client = super::network::build_http_client(&app)?;
+    tauri::async_runtime::spawn(async move {
+        match stream_anthropic_reasoning(&app, &client, req).await {
+            Ok(()) => {
+                let _ = app.emit("backend-reasoning-done", ());
+            }
+            Err(err) => {
+                eprintln!("[reasoning] streaming failed: {}", err);
+                let _ = app.emit("backend-reasoning-error", err);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Single entry point for the renderer; dispatches on `req.provider`.
+#[tauri::command]
+pub async fn process_reasoning(
+    app: AppHandle,
+    req: UnifiedReasoningRequest,
+) -> Result<ReasoningResult, String> {
+    match req.provider.trim().to_ascii_lowercase().as_str() {
+        "anthropic" => {
+            anthropic_reasoning(
+                &app,
+                AnthropicReasoningRequest {
+                    api_key: req.api_key,
+                    model: req.model,
+                    system_prompt: req.system_prompt,
+                    text: req.text,
+                    max_tokens: req.max_tokens,
+                    temperature: req.temperature,
+                    template_id: None,
+                },
+            )
+            .await
+        }
+        "openai" => {
+            openai_reasoning(
+                &app,
+                OpenAIReasoningRequest {
+                    api_key: req.api_key,
+                    model: req.model,
+                    system_prompt: req.system_prompt,
+                    text: req.text,
+                    max_tokens: req.max_tokens,
+                    temperature: req.temperature,
+                },
+            )
+            .await
+        }
+        "ollama" => {
+            ollama_reasoning(
+                &app,
+                OllamaReasoningRequest {
+                    base_url: req.base_url.unwrap_or_else(default_ollama_base_url),
+                    model: req.model,
+                    system_prompt: req.system_prompt,
+                    text: req.text,
+                    max_tokens: req.max_tokens,
+                    temperature: req.temperature,
+                },
+            )
+            .await
+        }
+        other => Err(format!("Unsupported reasoning provider: {}", other)),
+    }
+}
+
+fn estimate_cost_usd(provider: &str, model: &str, input_tokens: u32, output_tokens: u32) -> f64 {
+    MODEL_PRICES_PER_1K
+        .iter()
+        .find(|(p, prefix, _, _)| *p == provider && model.starts_with(prefix))
+        .map_or(0.0, |(_, _, input_price, output_price)| {
+            (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1000.0
+        })
+}
+
+/// Cost out the reported token counts and add them to the `ai_usage` table.
+fn record_usage(
+    app: &AppHandle,
+    provider: &str,
+    model: &str,
+    tokens: Option<(u32, u32)>,
+) -> Option<TokenUsage> {
+    let (input_tokens, output_tokens) = tokens?;
+    let usage = TokenUsage {
+        input_tokens,
+        output_tokens,
+        estimated_cost_usd: estimate_cost_usd(provider, model, input_tokens, output_tokens),
+    };
+    if let Err(err) = super::database::record_ai_usage(app, provider, model, &usage) {
+        eprintln!("[reasoning] failed to record token usage: {}", err);
+    }
+    Some(usage)
+}
+
+/// Swap in the stored template's prompts when `req.template_id` is set.
+fn apply_prompt_template(
+    app: &AppHandle,
+    req: AnthropicReasoningRequest,
+) -> Result<AnthropicReasoningRequest, String> {
+    let Some(template_id) = req.template_id else {
+        return Ok(req);
+    };
+    let template = super::database::get_prompt_template(app, template_id)?;
+    Ok(AnthropicReasoningRequest {
+        system_prompt: template.system_prompt,
+        text: template.user_template.replace("{{text}}", &req.text),
+        ..req
+    })
+}
+
+async fn anthropic_reasoning(
+    app: &AppHandle,
     req: AnthropicReasoningRequest,
 ) -> Result<ReasoningResult, String> {
     let max_tokens = req.max_tokens.unwrap_or(1024);
 
-    let client = Client::new();
+    let client = super::network::build_http_client(app)?;
     let res = client
         .post("https://api.anthropic.com/v1/messages")
         .header("content-type", "application/json")
@@ -75,6 +371,7 @@ pub async fn process_anthropic_reasoning(
                 status.as_u16(),
                 body_text
             )),
+            usage: None,
         });
     }
 
@@ -86,6 +383,16 @@ pub async fn process_anthropic_reasoning(
         )
     })?;
 
+    let usage = record_usage(
+        app,
+        "anthropic",
+        &req.model,
+        parsed
+            .usage
+            .as_ref()
+            .map(|u| (u.input_tokens, u.output_tokens)),
+    );
+
     let text = parsed
         .content
         .iter()
@@ -100,6 +407,286 @@ pub async fn process_anthropic_reasoning(
             success: false,
             text: None,
             error: Some("Anthropic returned empty response".to_string()),
+            usage,
+        });
+    }
+
+    Ok(ReasoningResult {
+        success: true,
+        text: Some(text),
+        error: None,
+        usage,
+    })
+}
+
+async fn stream_anthropic_reasoning(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    req: AnthropicReasoningRequest,
+) -> Result<(), String> {
+    let max_tokens = req.max_tokens.unwrap_or(1024);
+
+    let mut res = client
+        .post("https://api.anthropic.com/v1/messages")
+        .header("content-type", "application/json")
+        .header("x-api-key", req.api_key)
+        .header("anthropic-version", "2023-06-01")
+        .json(&serde_json::json!({
+            "model": req.model,
+            "max_tokens": max_tokens,
+            "temperature": req.temperature,
+            "system": req.system_prompt,
+            "stream": true,
+            "messages": [
+                {
+                    "role": "user",
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": req.text
+                        }
+                    ]
+                }
+            ]
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = res.status();
+    if !status.is_success() {
+        let body_text = res.text().await.unwrap_or_default();
+        return Err(format!(
+            "Anthropic API error: {} {}",
+            status.as_u16(),
+            body_text
+        ));
+    }
+
+    // SSE events are separated by a blank line; buffer raw bytes so multi-byte characters
+    // split across network chunks decode correctly.
+    let mut pending: Vec<u8> = Vec::new();
+    while let Some(chunk) = res.chunk().await.map_err(|e| e.to_string())? {
+        pending.extend_from_slice(&chunk);
+        while let Some(end) = pending.windows(2).position(|w| w == b"\n\n") {
+            let event: Vec<u8> = pending.drain(..end + 2).collect();
+            handle_anthropic_sse_event(app, &String::from_utf8_lossy(&event))?;
+        }
+    }
+    if !pending.is_empty() {
+        handle_anthropic_sse_event(app, &String::from_utf8_lossy(&pending))?;
+    }
+
+    Ok(())
+}
+
+fn handle_anthropic_sse_event(app: &AppHandle, event: &str) -> Result<(), String> {
+    for line in event.lines() {
+        let Some(data) = line.strip_prefix("data:") else {
+            continue;
+        };
+        let Ok(payload) = serde_json::from_str::<serde_json::Value>(data.trim()) else {
+            continue;
+        };
+
+        match payload.get("type").and_then(|t| t.as_str()) {
+            Some("content_block_delta") => {
+                if let Some(text) = payload
+                    .get("delta")
+                    .and_then(|delta| delta.get("text"))
+                    .and_then(|text| text.as_str())
+                {
+                    let _ = app.emit("backend-reasoning-chunk", text);
+                }
+            }
+            Some("error") => {
+                let message = payload
+                    .get("error")
+                    .and_then(|error| error.get("message"))
+                    .and_then(|message| message.as_str())
+                    .unwrap_or("unknown error");
+                return Err(format!("Anthropic stream error: {}", message));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+async fn openai_reasoning(
+    app: &AppHandle,
+    req: OpenAIReasoningRequest,
+) -> Result<ReasoningResult, String> {
+    let max_tokens = req.max_tokens.unwrap_or(1024);
+
+    let client = super::network::build_http_client(app)?;
+    let res = client
+        .post("https://api.openai.com/v1/chat/completions")
+        .header("content-type", "application/json")
+        .header("Authorization", format!("Bearer {}", req.api_key))
+        .json(&serde_json::json!({
+            "model": req.model,
+            "max_tokens": max_tokens,
+            "temperature": req.temperature,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": req.system_prompt
+                },
+                {
+                    "role": "user",
+                    "content": req.text
+                }
+            ]
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = res.status();
+    let body_text = res.text().await.map_err(|e| e.to_string())?;
+
+    if !status.is_success() {
+        return Ok(ReasoningResult {
+            success: false,
+            text: None,
+            error: Some(format!(
+                "OpenAI API error: {} {}",
+                status.as_u16(),
+                body_text
+            )),
+            usage: None,
+        });
+    }
+
+    let parsed: OpenAIChatResponse = serde_json::from_str(&body_text).map_err(|e| {
+        format!(
+            "Failed to parse OpenAI response: {} (body: {})",
+            e,
+            body_text.chars().take(500).collect::<String>()
+        )
+    })?;
+
+    let usage = record_usage(
+        app,
+        "openai",
+        &req.model,
+        parsed
+            .usage
+            .as_ref()
+            .map(|u| (u.prompt_tokens, u.completion_tokens)),
+    );
+
+    let text = parsed
+        .choices
+        .first()
+        .and_then(|choice| choice.message.content.clone())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    if text.is_empty() {
+        return Ok(ReasoningResult {
+            success: false,
+            text: None,
+            error: Some("OpenAI returned empty response".to_string()),
+            usage,
+        });
+    }
+
+    Ok(ReasoningResult {
+        success: true,
+        text: Some(text),
+        error: None,
+        usage,
+    })
+}
+
+fn ollama_base_url(base_url: &str) -> String {
+    let trimmed = base_url.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        OLLAMA_DEFAULT_BASE_URL.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+async fn ollama_reasoning(
+    app: &AppHandle,
+    req: OllamaReasoningRequest,
+) -> Result<ReasoningResult, String> {
+    let mut options = serde_json::json!({ "temperature": req.temperature });
+    if let Some(max_tokens) = req.max_tokens {
+        options["num_predict"] = serde_json::json!(max_tokens);
+    }
+
+    let client = super::network::build_http_client(app)?;
+    let res = client
+        .post(format!("{}/api/chat", ollama_base_url(&req.base_url)))
+        .header("content-type", "application/json")
+        .json(&serde_json::json!({
+            "model": req.model,
+            "stream": false,
+            "options": options,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": req.system_prompt
+                },
+                {
+                    "role": "user",
+                    "content": req.text
+                }
+            ]
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Ollama: {}", e))?;
+
+    let status = res.status();
+    let body_text = res.text().await.map_err(|e| e.to_string())?;
+
+    if !status.is_success() {
+        return Ok(ReasoningResult {
+            success: false,
+            text: None,
+            error: Some(format!(
+                "Ollama API error: {} {}",
+                status.as_u16(),
+                body_text
+            )),
+            usage: None,
+        });
+    }
+
+    let parsed: OllamaChatResponse = serde_json::from_str(&body_text).map_err(|e| {
+        format!(
+            "Failed to parse Ollama response: {} (body: {})",
+            e,
+            body_text.chars().take(500).collect::<String>()
+        )
+    })?;
+
+    let usage = record_usage(
+        app,
+        "ollama",
+        &req.model,
+        parsed.prompt_eval_count.zip(parsed.eval_count),
+    );
+
+    let text = parsed
+        .message
+        .and_then(|message| message.content)
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    if text.is_empty() {
+        return Ok(ReasoningResult {
+            success: false,
+            text: None,
+            error: Some("Ollama returned empty response".to_string()),
+            usage,
         });
     }
 
@@ -107,5 +694,6 @@ pub async fn process_anthropic_reasoning(
         success: true,
         text: Some(text),
         error: None,
+        usage,
     })
 }
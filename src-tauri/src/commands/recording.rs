@@ -1,170 +1,1994 @@
 use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use std::time::Duration;
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const LEVEL_INTERVAL: Duration = Duration::from_millis(66); // ~15 Hz
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const LEVEL_FLOOR_DB: f32 = -60.0;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Bumped on every recording start so stale meter/tick loops stop instead of reporting
+/// on the next recording.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+static RECORDING_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Name of the input device the current (or most recent) recording captures from.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+static CURRENT_INPUT_DEVICE: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
 
 #[derive(Debug, Serialize, Clone)]
 pub struct NativeRecordingResult {
     pub audio_data: Vec<u8>,
     pub mime_type: String,
     pub duration_seconds: Option<f64>,
+    /// Where the WAV was kept when `keepRecordings` is enabled.
+    pub audio_path: Option<String>,
+    /// RMS level of the whole capture in dBFS, measured before any gain is applied.
+    pub level_db: Option<f32>,
+}
+
+/// Payload of `recording-level`: average and peak power mapped from dBFS to 0.0–1.0.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+#[derive(Debug, Serialize, Clone)]
+pub struct RecordingLevel {
+    pub average: f32,
+    pub peak: f32,
+}
+
+/// Returned by `get_recording_status` and emitted as `recording-tick` while recording.
+/// `elapsed_seconds` excludes time spent paused.
+#[derive(Debug, Serialize, Clone)]
+pub struct RecordingStatus {
+    pub active: bool,
+    pub paused: bool,
+    pub elapsed_seconds: f64,
+    pub started_at_ms: Option<u128>,
+}
+
+/// Ambient level measured by `calibrate_noise_floor`, in dBFS.
+#[derive(Debug, Serialize, Clone)]
+pub struct NoiseFloorCalibration {
+    pub rms_db: f32,
+    pub peak_db: f32,
+    pub duration_seconds: f64,
+}
+
+/// Payload of `recording-device-changed`. `substituted` means `requested` (the
+/// `preferredInputDevice` setting) was unavailable and `actual` was used instead.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+#[derive(Debug, Serialize, Clone)]
+pub struct RecordingDeviceChange {
+    pub requested: Option<String>,
+    pub actual: String,
+    pub previous: Option<String>,
+    pub substituted: bool,
+}
+
+/// `fmt ` and `data` chunk details of a finalized WAV.
+#[derive(Debug, Serialize, Clone)]
+pub struct WavInfo {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
+    pub data_len: usize,
+}
+
+/// Payload of `recording-diagnostics`, emitted when stopping does not yield a usable WAV.
+#[derive(Debug, Serialize, Clone)]
+pub struct RecordingDiagnostics {
+    pub error: String,
+    pub byte_len: Option<usize>,
+    /// First bytes of the output, hex encoded.
+    pub prefix: Option<String>,
+    pub active_at_stop: bool,
+    pub paused_at_stop: bool,
+    pub elapsed_seconds: f64,
+    pub microphone_permission: PermissionStatus,
+    /// Where the invalid output was saved, for "reveal in Finder".
+    pub audio_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AudioInputDevice {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+/// Whether this platform has a native recorder (and so can run dictation in the backend).
+pub fn native_recording_supported() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+/// List the available microphones. `id` is what `preferredInputDevice` should be set to.
+#[tauri::command]
+pub async fn list_audio_input_devices() -> Result<Vec<AudioInputDevice>, String> {
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        cpal_capture::list_input_devices()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Err("Audio device enumeration is only supported on macOS and Windows".to_string())
+    }
+}
+
+/// Device name currently being recorded from, or `None` when not recording.
+#[tauri::command]
+pub fn get_current_input_device() -> Option<String> {
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        if !is_native_recording_active() {
+            return None;
+        }
+        CURRENT_INPUT_DEVICE.lock().ok()?.clone()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn get_setting_string(app: &AppHandle, key: &str) -> Option<String> {
+    super::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().map(|s| s.trim().to_string()))
+        .filter(|s| !s.is_empty())
+}
+
+/// Remember the device a recording actually started on (`explicit`, or the system default)
+/// and emit `recording-device-changed` when it isn't the preferred device or differs from
+/// the one used last time.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn track_input_device(app: &AppHandle, explicit: Option<&str>) {
+    let Some(actual) = explicit
+        .map(str::to_string)
+        .or_else(cpal_capture::default_input_device_name)
+    else {
+        return;
+    };
+
+    if let Ok(mut current) = CURRENT_INPUT_DEVICE.lock() {
+        *current = Some(actual.clone());
+    }
+
+    let requested = get_setting_string(app, "preferredInputDevice");
+    let previous = get_setting_string(app, "lastInputDevice");
+    if previous.as_deref() != Some(actual.as_str()) {
+        let _ = super::settings::set_setting(
+            app.clone(),
+            "lastInputDevice".to_string(),
+            serde_json::json!(actual),
+        );
+    }
+
+    let substituted = requested.as_ref().is_some_and(|name| *name != actual);
+    let switched = previous.as_ref().is_some_and(|name| *name != actual);
+    if substituted || switched {
+        super::logging::component_log(
+            app,
+            "recording",
+            "info",
+            &format!(
+                "capturing from '{}' (preferred: {:?}, previous: {:?})",
+                actual, requested, previous
+            ),
+        );
+        let _ = app.emit(
+            "recording-device-changed",
+            RecordingDeviceChange {
+                requested,
+                actual,
+                previous,
+                substituted,
+            },
+        );
+    }
+}
+
+/// Resolve the `preferredInputDevice` setting to a device that is currently connected.
+/// Returns `None` for the system default, warning the UI if the configured device is gone.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn resolve_preferred_input_device(app: &AppHandle) -> Option<String> {
+    let preferred = get_setting_string(app, "preferredInputDevice")?;
+
+    let devices = match cpal_capture::list_input_devices() {
+        Ok(devices) => devices,
+        Err(err) => {
+            super::logging::component_log(
+                app,
+                "recording",
+                "error",
+                &format!("failed to enumerate input devices: {}", err),
+            );
+            return None;
+        }
+    };
+
+    match devices.iter().find(|device| device.id == preferred) {
+        // The system default already records from this device; no need to pick it explicitly.
+        Some(device) if device.is_default => None,
+        Some(device) => Some(device.id.clone()),
+        None => {
+            super::logging::component_log(
+                app,
+                "recording",
+                "warn",
+                &format!(
+                    "preferred input device '{}' not found; using system default",
+                    preferred
+                ),
+            );
+            let _ = app.emit(
+                "audio-input-device-fallback",
+                serde_json::json!({ "requested": preferred }),
+            );
+            None
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn recording_metering_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "recordingMetering".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// `recordingFormat`: `wav` (default) or `m4a` (AAC, much smaller uploads). Only the
+/// AVAudioEngine path honors it; device-specific capture always produces WAV.
+#[cfg(target_os = "macos")]
+fn recording_format(app: &AppHandle) -> macos::RecordingFormat {
+    let format = super::settings::get_setting(app.clone(), "recordingFormat".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().map(|s| s.trim().to_ascii_lowercase()));
+    match format.as_deref() {
+        Some("m4a") => macos::RecordingFormat::M4a,
+        _ => macos::RecordingFormat::Wav,
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn normalize_db(db: f32) -> f32 {
+    if !db.is_finite() {
+        return 0.0;
+    }
+    ((db - LEVEL_FLOOR_DB) / -LEVEL_FLOOR_DB).clamp(0.0, 1.0)
+}
+
+/// Current (average, peak) input power in dBFS, or `None` once recording has stopped.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn current_level() -> Option<(f32, f32)> {
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(level) = macos::sample_level() {
+            return Some(level);
+        }
+    }
+
+    cpal_capture::sample_level()
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn spawn_level_meter(app: AppHandle, generation: u64) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(LEVEL_INTERVAL).await;
+            if RECORDING_GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            let Some((average_db, peak_db)) = current_level() else {
+                break;
+            };
+            let _ = app.emit(
+                "recording-level",
+                RecordingLevel {
+                    average: normalize_db(average_db),
+                    peak: normalize_db(peak_db),
+                },
+            );
+        }
+    });
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn spawn_recording_ticker(app: AppHandle, generation: u64) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            if RECORDING_GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            let status = current_status();
+            if !status.active {
+                break;
+            }
+            let _ = app.emit("recording-tick", status);
+        }
+    });
+}
+
+/// Start the per-recording background loops (level meter, timer ticks).
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn spawn_recording_monitors(app: AppHandle, metering: bool) {
+    let generation = RECORDING_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if metering {
+        spawn_level_meter(app.clone(), generation);
+    }
+    spawn_recording_ticker(app, generation);
+}
+
+fn current_status() -> RecordingStatus {
+    #[cfg(target_os = "macos")]
+    let status = macos::status().or_else(cpal_capture::status);
+
+    #[cfg(target_os = "windows")]
+    let status = cpal_capture::status();
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let status: Option<(bool, f64, std::time::Duration)> = None;
+
+    match status {
+        Some((paused, elapsed_seconds, since_start)) => RecordingStatus {
+            active: true,
+            paused,
+            elapsed_seconds,
+            started_at_ms: std::time::SystemTime::now()
+                .checked_sub(since_start)
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis()),
+        },
+        None => RecordingStatus {
+            active: false,
+            paused: false,
+            elapsed_seconds: 0.0,
+            started_at_ms: None,
+        },
+    }
+}
+
+#[tauri::command]
+pub fn get_recording_status() -> RecordingStatus {
+    current_status()
+}
+
+/// Microphone access for this app. `Restricted` means a device policy (MDM profile, Screen
+/// Time) blocks it, so unlike `Denied` the user can't grant it in System Settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+// Only macOS reports anything but `Granted`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum PermissionStatus {
+    Granted,
+    Denied,
+    Restricted,
+    NotDetermined,
+}
+
+impl PermissionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Granted => "granted",
+            Self::Denied => "denied",
+            Self::Restricted => "restricted",
+            Self::NotDetermined => "not-determined",
+        }
+    }
+}
+
+/// Platforms without a queryable permission always report `Granted`.
+pub fn microphone_permission() -> PermissionStatus {
+    #[cfg(target_os = "macos")]
+    {
+        macos::microphone_permission()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        PermissionStatus::Granted
+    }
+}
+
+#[tauri::command]
+pub async fn check_microphone_permission() -> Result<PermissionStatus, String> {
+    Ok(microphone_permission())
+}
+
+/// Show the system microphone prompt if the user hasn't decided yet; resolves with
+/// whether access is granted.
+#[tauri::command]
+pub async fn request_microphone_permission() -> Result<bool, String> {
+    #[cfg(target_os = "macos")]
+    let status = macos::request_microphone_permission().await;
+
+    #[cfg(not(target_os = "macos"))]
+    let status = PermissionStatus::Granted;
+
+    Ok(status == PermissionStatus::Granted)
+}
+
+/// Whether system audio can be mixed into recordings: `granted`, `unavailable` (no
+/// loopback input device such as BlackHole is installed), or the microphone status when
+/// that access is missing, since macOS gates loopback inputs behind the same permission.
+#[tauri::command]
+pub fn check_system_audio_permission(app: AppHandle) -> String {
+    #[cfg(target_os = "macos")]
+    let status = match macos::microphone_permission() {
+        PermissionStatus::Granted => {
+            match cpal_capture::find_loopback_device(system_audio_device(&app).as_deref()) {
+                Some(_) => "granted",
+                None => "unavailable",
+            }
+        }
+        other => other.as_str(),
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    let status = {
+        let _ = app;
+        "unavailable"
+    };
+
+    status.to_string()
+}
+
+#[cfg(target_os = "macos")]
+fn capture_system_audio_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "captureSystemAudio".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// `systemAudioDevice`: loopback input to record system audio from; auto-detected if unset.
+#[cfg(target_os = "macos")]
+fn system_audio_device(app: &AppHandle) -> Option<String> {
+    super::settings::get_setting(app.clone(), "systemAudioDevice".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().map(|s| s.trim().to_string()))
+        .filter(|s| !s.is_empty())
+}
+
+/// Start the loopback stream for `captureSystemAudio`. Failures only drop the recording
+/// back to microphone-only; they never fail the dictation.
+#[cfg(target_os = "macos")]
+fn start_system_audio_capture(app: &AppHandle) {
+    if !capture_system_audio_enabled(app) {
+        return;
+    }
+
+    let result = match macos::microphone_permission() {
+        PermissionStatus::Granted => {
+            cpal_capture::find_loopback_device(system_audio_device(app).as_deref())
+                .ok_or_else(|| "no loopback input device found".to_string())
+                .and_then(cpal_capture::start_system_audio)
+        }
+        other => Err(format!("microphone permission {}", other.as_str())),
+    };
+
+    if let Err(err) = result {
+        super::logging::component_log(
+            app,
+            "recording",
+            "warn",
+            &format!(
+                "system audio capture unavailable ({}); recording microphone only",
+                err
+            ),
+        );
+        let _ = app.emit(
+            "system-audio-capture-fallback",
+            serde_json::json!({ "reason": err }),
+        );
+    }
+}
+
+#[tauri::command]
+pub async fn start_native_recording(app: AppHandle) -> Result<bool, String> {
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    let metering = recording_metering_enabled(&app);
+
+    #[cfg(target_os = "macos")]
+    {
+        // The AVAudioEngine input node follows the system default input, so an explicit
+        // device choice goes through the CoreAudio (cpal) capture path instead.
+        let device = resolve_preferred_input_device(&app);
+        match device.clone() {
+            Some(device) => {
+                if recording_format(&app) == macos::RecordingFormat::M4a {
+                    super::logging::component_log(
+                        &app,
+                        "recording",
+                        "info",
+                        "recordingFormat m4a only applies to the default input; \
+                         recording WAV from the selected device",
+                    );
+                }
+                cpal_capture::start(Some(device))?
+            }
+            None => macos::start(recording_format(&app))?,
+        }
+        track_input_device(&app, device.as_deref());
+        start_system_audio_capture(&app);
+        spawn_recording_monitors(app, metering);
+        return Ok(true);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let device = resolve_preferred_input_device(&app);
+        cpal_capture::start(device.clone())?;
+        track_input_device(&app, device.as_deref());
+        spawn_recording_monitors(app, metering);
+        Ok(true)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = app;
+        Err("Native recording is only supported on macOS and Windows".to_string())
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const MAX_CALIBRATION_SECONDS: u32 = 30;
+
+/// Start the microphone the way `start_native_recording` would, minus the meters and
+/// system audio, always producing WAV so the samples can be analyzed.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn start_calibration_capture(app: &AppHandle) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        match resolve_preferred_input_device(app) {
+            Some(device) => cpal_capture::start(Some(device)),
+            None => macos::start(macos::RecordingFormat::Wav),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::start(resolve_preferred_input_device(app))
+    }
+}
+
+/// Record `seconds` of room noise (keep quiet!) and store its RMS level as
+/// `calibratedNoiseFloorDb`, which silence detection then prefers over its default.
+#[tauri::command]
+pub async fn calibrate_noise_floor(
+    app: AppHandle,
+    seconds: u32,
+) -> Result<NoiseFloorCalibration, String> {
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        if is_native_recording_active() {
+            return Err("Cannot calibrate while a recording is in progress".to_string());
+        }
+
+        start_calibration_capture(&app)?;
+        let seconds = seconds.clamp(1, MAX_CALIBRATION_SECONDS);
+        tokio::time::sleep(Duration::from_secs(u64::from(seconds))).await;
+        let result = tauri::async_runtime::spawn_blocking(stop_platform_recording)
+            .await
+            .map_err(|e| format!("Failed to finalize calibration recording: {e}"))??;
+
+        let range = pcm16_data_range(&result.audio_data)
+            .ok_or_else(|| "Calibration recording is not 16-bit PCM WAV".to_string())?;
+        let samples: Vec<f32> = result.audio_data[range]
+            .chunks_exact(2)
+            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]) as f32 / 32_768.0)
+            .collect();
+        let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+
+        let calibration = NoiseFloorCalibration {
+            rms_db: rms_db(&samples),
+            peak_db: to_dbfs(peak),
+            duration_seconds: result.duration_seconds.unwrap_or(f64::from(seconds)),
+        };
+        super::logging::component_log(
+            &app,
+            "recording",
+            "info",
+            &format!(
+                "noise floor calibrated: rms {:.1} dBFS, peak {:.1} dBFS",
+                calibration.rms_db, calibration.peak_db
+            ),
+        );
+        super::settings::set_setting(
+            app,
+            "calibratedNoiseFloorDb".to_string(),
+            serde_json::json!(calibration.rms_db),
+        )?;
+        Ok(calibration)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (app, seconds);
+        Err("Native recording is only supported on macOS and Windows".to_string())
+    }
+}
+
+const DEFAULT_MAX_STORED_RECORDINGS_MB: u64 = 500;
+const AUTO_GAIN_TARGET_DBFS: f64 = -20.0;
+// Don't let auto gain turn near-silence into loud noise.
+const AUTO_GAIN_MAX_DB: f64 = 30.0;
+
+fn db_to_linear(db: f64) -> f64 {
+    10f64.powf(db / 20.0)
+}
+
+/// Byte range of the sample data in a 16-bit PCM WAV, or `None` for anything else.
+fn pcm16_data_range(wav: &[u8]) -> Option<std::ops::Range<usize>> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut offset = 12;
+    let mut is_pcm16 = false;
+    while offset + 8 <= wav.len() {
+        let id = &wav[offset..offset + 4];
+        let size = u32::from_le_bytes(wav[offset + 4..offset + 8].try_into().ok()?) as usize;
+        let body = offset + 8;
+        match id {
+            b"fmt " if body + 16 <= wav.len() => {
+                let format = u16::from_le_bytes([wav[body], wav[body + 1]]);
+                let bits = u16::from_le_bytes([wav[body + 14], wav[body + 15]]);
+                is_pcm16 = format == 1 && bits == 16;
+            }
+            b"data" => {
+                let end = (body + size).min(wav.len());
+                return is_pcm16.then_some(body..end);
+            }
+            _ => {}
+        }
+        // Chunks are word-aligned.
+        offset = body + size + (size & 1);
+    }
+    None
+}
+
+/// Apply `recordingGainDb`, or normalize to ~-20 dBFS RMS when `recordingAutoGain` is on,
+/// rewriting the WAV samples in place. Samples are clamped so loud input can't wrap around.
+fn apply_recording_gain(app: &AppHandle, wav: &mut [u8]) {
+    let auto_gain = super::settings::get_setting(app.clone(), "recordingAutoGain".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let gain_db = super::settings::get_setting(app.clone(), "recordingGainDb".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+
+    match apply_gain(wav, auto_gain, gain_db) {
+        Ok(Some(gain)) => super::logging::component_log(
+            app,
+            "recording",
+            "info",
+            &format!(
+                "applied gain {:.1} dB (auto={})",
+                20.0 * gain.log10(),
+                auto_gain
+            ),
+        ),
+        Ok(None) => {}
+        Err(err) => {
+            super::logging::component_log(app, "recording", "warn", &format!("gain skipped: {err}"))
+        }
+    }
+}
+
+/// Scale the samples of a 16-bit PCM WAV by `gain_db`, or by the auto-gain factor when
+/// `auto_gain` is set. Returns the linear gain applied, or `None` if nothing was changed.
+fn apply_gain(wav: &mut [u8], auto_gain: bool, gain_db: f64) -> Result<Option<f64>, String> {
+    if !auto_gain && gain_db == 0.0 {
+        return Ok(None);
+    }
+    let range =
+        pcm16_data_range(wav).ok_or_else(|| "recording is not 16-bit PCM WAV".to_string())?;
+    let data = &mut wav[range];
+
+    let gain = if auto_gain {
+        match auto_gain_factor(data) {
+            Some(gain) => gain,
+            None => return Ok(None),
+        }
+    } else {
+        db_to_linear(gain_db)
+    };
+    scale_pcm16(data, gain);
+    Ok(Some(gain))
 }
 
-#[tauri::command]
-pub async fn start_native_recording() -> Result<bool, String> {
-    #[cfg(target_os = "macos")]
-    {
-        return macos::start().map(|_| true);
+/// Gain that brings the RMS to the auto-gain target without pushing the peak past full scale.
+fn auto_gain_factor(data: &[u8]) -> Option<f64> {
+    let mut sum_squares = 0f64;
+    let mut peak = 0f64;
+    let mut count = 0usize;
+    for chunk in data.chunks_exact(2) {
+        let sample = i16::from_le_bytes([chunk[0], chunk[1]]) as f64 / 32_768.0;
+        sum_squares += sample * sample;
+        peak = peak.max(sample.abs());
+        count += 1;
+    }
+    if count == 0 || peak == 0.0 {
+        return None;
+    }
+
+    let rms = (sum_squares / count as f64).sqrt();
+    let gain = (db_to_linear(AUTO_GAIN_TARGET_DBFS) / rms)
+        .min(db_to_linear(AUTO_GAIN_MAX_DB))
+        .min(1.0 / peak);
+    Some(gain)
+}
+
+fn scale_pcm16(data: &mut [u8], gain: f64) {
+    for chunk in data.chunks_exact_mut(2) {
+        let sample = i16::from_le_bytes([chunk[0], chunk[1]]) as f64;
+        let scaled = (sample * gain)
+            .round()
+            .clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+        chunk.copy_from_slice(&scaled.to_le_bytes());
+    }
+}
+
+fn keep_recordings_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "keepRecordings".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+fn max_stored_recordings_bytes(app: &AppHandle) -> u64 {
+    super::settings::get_setting(app.clone(), "maxStoredRecordingsMb".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_MAX_STORED_RECORDINGS_MB)
+        .saturating_mul(1024 * 1024)
+}
+
+/// Delete the oldest recordings until the directory fits within `max_bytes`, never `keep`.
+/// Only files directly in `dir` count, so subdirectories such as `invalid/` are left alone.
+fn prune_recordings(dir: &Path, max_bytes: u64, keep: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(std::time::SystemTime, u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            let modified = meta.modified().unwrap_or(std::time::UNIX_EPOCH);
+            Some((modified, meta.len(), entry.path()))
+        })
+        .collect();
+    files.sort_by_key(|(modified, _, _)| *modified);
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| *len).sum();
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if path == keep {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                super::logging::log(
+                    "recording",
+                    "info",
+                    &format!("pruned old recording {}", path.to_string_lossy()),
+                );
+                total = total.saturating_sub(len);
+            }
+            Err(err) => super::logging::log(
+                "recording",
+                "error",
+                &format!("failed to prune {}: {}", path.to_string_lossy(), err),
+            ),
+        }
+    }
+}
+
+/// Save a finished recording under `app_data_dir/recordings/` and enforce the size cap,
+/// pruning older recordings to make room. A recording larger than the cap on its own is
+/// not kept, so `None`.
+fn store_recording(
+    app: &AppHandle,
+    audio_data: &[u8],
+    mime_type: &str,
+) -> Result<Option<PathBuf>, String> {
+    let max_bytes = max_stored_recordings_bytes(app);
+    if audio_data.len() as u64 > max_bytes {
+        super::logging::component_log(
+            app,
+            "recording",
+            "info",
+            &format!(
+                "not keeping recording: {} bytes exceeds maxStoredRecordingsMb",
+                audio_data.len()
+            ),
+        );
+        return Ok(None);
+    }
+
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("recordings");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create recordings dir: {e}"))?;
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let extension = if mime_type == "audio/mp4" {
+        "m4a"
+    } else {
+        "wav"
+    };
+    let path = dir.join(format!("recording-{now_ms}.{extension}"));
+    std::fs::write(&path, audio_data).map_err(|e| format!("Failed to save recording: {e}"))?;
+
+    prune_recordings(&dir, max_bytes, &path);
+    Ok(Some(path).filter(|path| path.exists()))
+}
+
+#[tauri::command]
+pub async fn stop_native_recording(app: AppHandle) -> Result<NativeRecordingResult, String> {
+    // Stopping joins the capture thread, may shell out to `afconvert` and writes the kept
+    // recording to disk; keep all of that off the async runtime's worker threads.
+    tauri::async_runtime::spawn_blocking(move || finish_recording(&app))
+        .await
+        .map_err(|e| format!("Failed to finalize recording: {e}"))?
+}
+
+/// Parse the RIFF structure of a PCM WAV, describing the first problem found.
+fn parse_wav_info(wav: &[u8]) -> Result<WavInfo, String> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return Err("missing RIFF/WAVE header".to_string());
+    }
+
+    let mut offset = 12;
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    while offset + 8 <= wav.len() {
+        let id = &wav[offset..offset + 4];
+        let size = u32::from_le_bytes([
+            wav[offset + 4],
+            wav[offset + 5],
+            wav[offset + 6],
+            wav[offset + 7],
+        ]) as usize;
+        let body = offset + 8;
+        match id {
+            b"fmt " => {
+                if size < 16 || body + 16 > wav.len() {
+                    return Err(format!("fmt chunk too short ({size} bytes)"));
+                }
+                let u16_at = |at: usize| u16::from_le_bytes([wav[body + at], wav[body + at + 1]]);
+                let sample_rate = u32::from_le_bytes([
+                    wav[body + 4],
+                    wav[body + 5],
+                    wav[body + 6],
+                    wav[body + 7],
+                ]);
+                format = Some((u16_at(0), u16_at(2), sample_rate, u16_at(14)));
+            }
+            b"data" => {
+                let (audio_format, channels, sample_rate, bits_per_sample) =
+                    format.ok_or_else(|| "data chunk before fmt chunk".to_string())?;
+                if audio_format != 1 {
+                    return Err(format!("unsupported WAV format tag {audio_format}"));
+                }
+                if channels == 0 || sample_rate == 0 || bits_per_sample == 0 {
+                    return Err(format!(
+                        "invalid fmt chunk (channels={channels}, sample_rate={sample_rate}, bits={bits_per_sample})"
+                    ));
+                }
+                let present = wav.len() - body;
+                if size > present {
+                    return Err(format!(
+                        "data chunk truncated (declared {size} bytes, {present} present)"
+                    ));
+                }
+                if size == 0 {
+                    return Err("data chunk is empty".to_string());
+                }
+                return Ok(WavInfo {
+                    sample_rate,
+                    channels,
+                    bits_per_sample,
+                    data_len: size,
+                });
+            }
+            _ => {}
+        }
+        offset = body + size + (size & 1);
+    }
+
+    Err(match format {
+        Some(_) => "data chunk missing".to_string(),
+        None => "fmt chunk missing".to_string(),
+    })
+}
+
+/// Log and emit `recording-diagnostics` for a failed stop, keeping any output on disk, and
+/// return the error text enriched with the same details.
+fn report_recording_failure(
+    app: &AppHandle,
+    error: String,
+    output: Option<&[u8]>,
+    status_at_stop: &RecordingStatus,
+) -> String {
+    let audio_path = output.and_then(|data| {
+        // Kept apart from `recordings/` itself so pruning never counts or deletes them.
+        let dir = app
+            .path()
+            .app_data_dir()
+            .ok()?
+            .join("recordings")
+            .join("invalid");
+        std::fs::create_dir_all(&dir).ok()?;
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = dir.join(format!("invalid-{now_ms}.wav"));
+        std::fs::write(&path, data).ok()?;
+        Some(path.to_string_lossy().to_string())
+    });
+
+    let diagnostics = RecordingDiagnostics {
+        error,
+        byte_len: output.map(<[u8]>::len),
+        prefix: output.map(|data| {
+            data.iter()
+                .take(16)
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        }),
+        active_at_stop: status_at_stop.active,
+        paused_at_stop: status_at_stop.paused,
+        elapsed_seconds: status_at_stop.elapsed_seconds,
+        microphone_permission: microphone_permission(),
+        audio_path,
+    };
+    super::logging::component_log(
+        app,
+        "recording",
+        "error",
+        &format!("stop failed: {:?}", diagnostics),
+    );
+
+    let message = format!(
+        "{} (len={}, prefix={}, active={}, paused={}, elapsed={:.1}s, mic permission={}{})",
+        diagnostics.error,
+        diagnostics
+            .byte_len
+            .map_or_else(|| "n/a".to_string(), |len| len.to_string()),
+        diagnostics.prefix.as_deref().unwrap_or("n/a"),
+        diagnostics.active_at_stop,
+        diagnostics.paused_at_stop,
+        diagnostics.elapsed_seconds,
+        diagnostics.microphone_permission.as_str(),
+        diagnostics
+            .audio_path
+            .as_deref()
+            .map(|path| format!(", kept at {path}"))
+            .unwrap_or_default(),
+    );
+    let _ = app.emit("recording-diagnostics", diagnostics);
+    message
+}
+
+fn finish_recording(app: &AppHandle) -> Result<NativeRecordingResult, String> {
+    let status_at_stop = current_status();
+    let mut result = stop_platform_recording()
+        .map_err(|err| report_recording_failure(app, err, None, &status_at_stop))?;
+    if result.mime_type == "audio/wav" {
+        if let Err(err) = parse_wav_info(&result.audio_data) {
+            return Err(report_recording_failure(
+                app,
+                format!("Native recording did not produce a valid WAV: {err}"),
+                Some(&result.audio_data),
+                &status_at_stop,
+            ));
+        }
+    }
+    apply_recording_gain(app, &mut result.audio_data);
+    if keep_recordings_enabled(app) {
+        match store_recording(app, &result.audio_data, &result.mime_type) {
+            Ok(path) => result.audio_path = path.map(|path| path.to_string_lossy().to_string()),
+            Err(err) => super::logging::component_log(
+                app,
+                "recording",
+                "error",
+                &format!("failed to keep recording: {}", err),
+            ),
+        }
+    }
+    Ok(result)
+}
+
+fn stop_platform_recording() -> Result<NativeRecordingResult, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let result = if cpal_capture::is_active() {
+            cpal_capture::stop()
+        } else {
+            macos::stop()
+        };
+        // Normally consumed by the mix in `stop`; this catches the early-error paths.
+        cpal_capture::cancel_system_audio();
+        return result;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::stop()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Err("Native recording is only supported on macOS and Windows".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_native_recording() -> Result<bool, String> {
+    #[cfg(target_os = "macos")]
+    {
+        cpal_capture::cancel_system_audio();
+        cpal_capture::cancel()?;
+        return macos::cancel().map(|_| true);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::cancel().map(|_| true)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Err("Native recording is only supported on macOS and Windows".to_string())
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn notify_paused(app: &AppHandle, paused: bool) {
+    super::dictation_session::emit(
+        app,
+        "backend-dictation-paused",
+        super::dictation_session::Active { active: paused },
+    );
+    let overlay_state = if paused {
+        crate::overlay::OverlayState::Paused
+    } else {
+        crate::overlay::OverlayState::Recording
+    };
+    crate::overlay::update_recording_overlay(app, overlay_state);
+}
+
+#[tauri::command]
+pub async fn pause_native_recording(app: AppHandle) -> Result<bool, String> {
+    #[cfg(target_os = "macos")]
+    {
+        if cpal_capture::is_active() {
+            cpal_capture::set_paused(true)?;
+        } else {
+            macos::pause()?;
+        }
+        cpal_capture::set_system_audio_paused(true);
+        notify_paused(&app, true);
+        return Ok(true);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::set_paused(true)?;
+        notify_paused(&app, true);
+        Ok(true)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = app;
+        Err("Native recording is only supported on macOS and Windows".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn resume_native_recording(app: AppHandle) -> Result<bool, String> {
+    #[cfg(target_os = "macos")]
+    {
+        if cpal_capture::is_active() {
+            cpal_capture::set_paused(false)?;
+        } else {
+            macos::resume()?;
+        }
+        cpal_capture::set_system_audio_paused(false);
+        notify_paused(&app, false);
+        return Ok(true);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::set_paused(false)?;
+        notify_paused(&app, false);
+        Ok(true)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = app;
+        Err("Native recording is only supported on macOS and Windows".to_string())
+    }
+}
+
+/// Check if the native recorder is paused (it still counts as active).
+pub fn is_native_recording_paused() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        return macos::is_paused() || cpal_capture::is_paused();
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::is_paused()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        false
+    }
+}
+
+/// Check if the native recorder (macOS or Windows) is currently active, including paused.
+pub fn is_native_recording_active() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        return macos::is_active() || cpal_capture::is_active();
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        cpal_capture::is_active()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        false
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn resample_linear(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || input.is_empty() {
+        return input.to_vec();
+    }
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (input.len() as f64 / ratio).floor() as usize;
+    let last = input.len() - 1;
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos.floor() as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = input[idx.min(last)];
+            let b = input[(idx + 1).min(last)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn encode_wav_pcm16(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    wav
+}
+
+/// Playback length of a PCM WAV from its header: `data` chunk length over the `fmt ` byte rate.
+///
+/// Handles extended `fmt ` chunks and skips unknown chunks (e.g. `LIST`) before `data`.
+#[cfg(any(target_os = "macos", target_os = "windows", test))]
+fn wav_duration_seconds(wav: &[u8]) -> Option<f64> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut offset = 12usize;
+    let mut byte_rate: Option<u32> = None;
+    while offset + 8 <= wav.len() {
+        let chunk_id = &wav[offset..offset + 4];
+        let chunk_size = u32::from_le_bytes(wav[offset + 4..offset + 8].try_into().ok()?) as usize;
+        let chunk_start = offset + 8;
+
+        match chunk_id {
+            b"fmt " if chunk_size >= 16 && chunk_start + 16 <= wav.len() => {
+                byte_rate = Some(u32::from_le_bytes(
+                    wav[chunk_start + 8..chunk_start + 12].try_into().ok()?,
+                ));
+            }
+            b"data" => {
+                // Writers that stream to disk may leave the size unpatched; trust the bytes present.
+                let data_len = chunk_size.min(wav.len() - chunk_start);
+                let byte_rate = byte_rate.filter(|rate| *rate > 0)?;
+                return Some(data_len as f64 / byte_rate as f64);
+            }
+            _ => {}
+        }
+
+        offset = chunk_start
+            .checked_add(chunk_size)?
+            .checked_add(chunk_size % 2)?;
+    }
+
+    None
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn to_dbfs(amplitude: f32) -> f32 {
+    20.0 * amplitude.max(1e-8).log10()
+}
+
+/// RMS power of `samples` in dBFS.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn rms_db(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return f32::NEG_INFINITY;
+    }
+    let mean_square = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+    to_dbfs(mean_square.sqrt())
+}
+
+/// (average, peak) power in dBFS over roughly the last meter interval of `samples`.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn recent_level(samples: &[f32], sample_rate: u32) -> (f32, f32) {
+    let window = (sample_rate / 15).max(1) as usize;
+    let recent = &samples[samples.len().saturating_sub(window)..];
+    if recent.is_empty() {
+        return (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    }
+
+    let mean_square = recent.iter().map(|s| s * s).sum::<f32>() / recent.len() as f32;
+    let peak = recent.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+    (to_dbfs(mean_square.sqrt()), to_dbfs(peak))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{NativeRecordingResult, PermissionStatus};
+    use block2::RcBlock;
+    use objc2::exception;
+    use objc2::runtime::Bool;
+    use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAudio};
+    use objc2_avf_audio::{AVAudioEngine, AVAudioPCMBuffer, AVAudioTime};
+    use objc2_foundation::NSError;
+    use std::panic::AssertUnwindSafe;
+    use std::path::PathBuf;
+    use std::ptr::NonNull;
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex, OnceLock};
+    use std::thread::JoinHandle;
+    use std::time::{Duration, Instant};
+
+    const TARGET_SAMPLE_RATE: u32 = 16_000;
+    // ~100ms at 44.1/48kHz; the engine may deliver a different size.
+    const TAP_BUFFER_FRAMES: u32 = 4096;
+    // Plenty for 16kHz mono speech; roughly 8x smaller than 16-bit PCM.
+    const AAC_BIT_RATE: u32 = 32_000;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum RecordingFormat {
+        Wav,
+        M4a,
+    }
+
+    impl RecordingFormat {
+        fn mime_type(self) -> &'static str {
+            match self {
+                Self::Wav => "audio/wav",
+                Self::M4a => "audio/mp4",
+            }
+        }
+    }
+
+    /// Mono PCM as captured, at the input device's native sample rate.
+    #[derive(Clone)]
+    pub struct PcmFrames {
+        pub sample_rate: u32,
+        pub samples: Vec<f32>,
+    }
+
+    enum Control {
+        Pause,
+        Resume,
+        Stop,
+    }
+
+    struct RecorderState {
+        control_tx: mpsc::Sender<Control>,
+        worker: JoinHandle<()>,
+        // Mono samples at the device's native rate; resampled on stop.
+        samples: Arc<Mutex<Vec<f32>>>,
+        sample_rate: u32,
+        format: RecordingFormat,
+        started_at: Instant,
+        // Recorded time before the current segment; `resumed_at` is `None` while paused.
+        active_elapsed: Duration,
+        resumed_at: Option<Instant>,
+    }
+
+    impl RecorderState {
+        fn active_duration(&self) -> Duration {
+            self.active_elapsed + self.resumed_at.map_or(Duration::ZERO, |t| t.elapsed())
+        }
+    }
+
+    static RECORDER_STATE: OnceLock<Mutex<Option<RecorderState>>> = OnceLock::new();
+    static FRAME_CONSUMERS: OnceLock<Mutex<Vec<mpsc::Sender<PcmFrames>>>> = OnceLock::new();
+
+    fn state() -> &'static Mutex<Option<RecorderState>> {
+        RECORDER_STATE.get_or_init(|| Mutex::new(None))
+    }
+
+    fn frame_consumers() -> &'static Mutex<Vec<mpsc::Sender<PcmFrames>>> {
+        FRAME_CONSUMERS.get_or_init(|| Mutex::new(Vec::new()))
+    }
+
+    /// Receive captured audio while recording. Consumers are dropped once their receiver is.
+    pub fn subscribe_frames() -> mpsc::Receiver<PcmFrames> {
+        let (tx, rx) = mpsc::channel();
+        if let Ok(mut consumers) = frame_consumers().lock() {
+            consumers.push(tx);
+        }
+        rx
+    }
+
+    fn ns_error_to_string(error: &NSError) -> String {
+        // `NSString` implements Display in objc2-foundation.
+        let desc = error.localizedDescription();
+        desc.to_string()
+    }
+
+    fn unique_temp_path(ext: &str) -> PathBuf {
+        let pid = std::process::id();
+        let now_ns = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir().join(format!("typefree-native-recording-{pid}-{now_ns}.{ext}"))
+    }
+
+    /// Downmix one tap buffer to mono, append it to the recording and fan it out to consumers.
+    fn push_buffer(
+        samples: &Mutex<Vec<f32>>,
+        buffer: &AVAudioPCMBuffer,
+        channels: usize,
+        sample_rate: u32,
+    ) {
+        let frames = unsafe { buffer.frameLength() } as usize;
+        let data = unsafe { buffer.floatChannelData() };
+        if data.is_null() || frames == 0 || channels == 0 {
+            return;
+        }
+
+        // The input node's format is deinterleaved float32: one contiguous slice per channel.
+        let planes: Vec<&[f32]> = (0..channels)
+            .map(|c| unsafe { std::slice::from_raw_parts((*data.add(c)).as_ptr(), frames) })
+            .collect();
+        let mono: Vec<f32> = (0..frames)
+            .map(|i| planes.iter().map(|plane| plane[i]).sum::<f32>() / channels as f32)
+            .collect();
+
+        if let Ok(mut consumers) = frame_consumers().lock() {
+            consumers.retain(|tx| {
+                tx.send(PcmFrames {
+                    sample_rate,
+                    samples: mono.clone(),
+                })
+                .is_ok()
+            });
+        }
+        if let Ok(mut buffer) = samples.lock() {
+            buffer.extend_from_slice(&mono);
+        }
+    }
+
+    /// Owns the engine for the lifetime of a recording; `AVAudioEngine` is not `Send`, so
+    /// the recorder talks to it only through channels (same model as the cpal capture).
+    fn run_engine(
+        samples: Arc<Mutex<Vec<f32>>>,
+        ready_tx: mpsc::Sender<Result<u32, String>>,
+        control_rx: mpsc::Receiver<Control>,
+    ) {
+        let engine = unsafe { AVAudioEngine::new() };
+        let input = unsafe { engine.inputNode() };
+        let format = unsafe { input.outputFormatForBus(0) };
+        let sample_rate = unsafe { format.sampleRate() } as u32;
+        let channels = unsafe { format.channelCount() } as usize;
+        if sample_rate == 0 || channels == 0 {
+            let _ = ready_tx.send(Err(
+                "No microphone input available (check microphone permission)".to_string(),
+            ));
+            return;
+        }
+
+        let tap: RcBlock<dyn Fn(NonNull<AVAudioPCMBuffer>, NonNull<AVAudioTime>)> = RcBlock::new(
+            move |buffer: NonNull<AVAudioPCMBuffer>, _when: NonNull<AVAudioTime>| {
+                push_buffer(&samples, unsafe { buffer.as_ref() }, channels, sample_rate);
+            },
+        );
+
+        let installed = exception::catch(AssertUnwindSafe(|| unsafe {
+            input.installTapOnBus_bufferSize_format_block(
+                0,
+                TAP_BUFFER_FRAMES,
+                Some(&*format),
+                RcBlock::as_ptr(&tap),
+            )
+        }));
+        if let Err(exc) = installed {
+            let _ = ready_tx.send(Err(format!(
+                "Objective-C exception while installing input tap: {:?}",
+                exc
+            )));
+            return;
+        }
+
+        let started = exception::catch(AssertUnwindSafe(|| unsafe {
+            engine.prepare();
+            engine.startAndReturnError()
+        }));
+        let start_error = match started {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => Some(ns_error_to_string(&err)),
+            Err(exc) => Some(format!("Objective-C exception during start: {:?}", exc)),
+        };
+        if let Some(err) = start_error {
+            let _ = exception::catch(AssertUnwindSafe(|| unsafe { input.removeTapOnBus(0) }));
+            let _ = ready_tx.send(Err(format!("Failed to start recording: {err}")));
+            return;
+        }
+        let _ = ready_tx.send(Ok(sample_rate));
+
+        while let Ok(control) = control_rx.recv() {
+            let result = match control {
+                Control::Pause => exception::catch(AssertUnwindSafe(|| unsafe { engine.pause() }))
+                    .map_err(|exc| format!("{:?}", exc)),
+                Control::Resume => {
+                    match exception::catch(AssertUnwindSafe(|| unsafe {
+                        engine.startAndReturnError()
+                    })) {
+                        Ok(Ok(())) => Ok(()),
+                        Ok(Err(err)) => Err(ns_error_to_string(&err)),
+                        Err(exc) => Err(format!("{:?}", exc)),
+                    }
+                }
+                Control::Stop => break,
+            };
+            if let Err(err) = result {
+                crate::commands::logging::log(
+                    "recording",
+                    "error",
+                    &format!("failed to pause/resume engine: {}", err),
+                );
+            }
+        }
+
+        if let Err(exc) = exception::catch(AssertUnwindSafe(|| unsafe {
+            engine.stop();
+            input.removeTapOnBus(0);
+        })) {
+            crate::commands::logging::log(
+                "recording",
+                "error",
+                &format!("objc exception during engine stop: {:?}", exc),
+            );
+        }
+        drop(tap);
+    }
+
+    /// Signal the engine thread to stop and wait for it to exit.
+    fn shutdown(state: RecorderState) -> (Arc<Mutex<Vec<f32>>>, u32, RecordingFormat) {
+        let _ = state.control_tx.send(Control::Stop);
+        if state.worker.join().is_err() {
+            crate::commands::logging::log("recording", "error", "engine thread panicked");
+        }
+        (state.samples, state.sample_rate, state.format)
+    }
+
+    /// Encode a WAV to AAC with the system `afconvert`.
+    fn encode_m4a(wav: &[u8]) -> Result<Vec<u8>, String> {
+        let input_path = unique_temp_path("wav");
+        let output_path = unique_temp_path("m4a");
+        std::fs::write(&input_path, wav).map_err(|e| format!("Failed to write temp WAV: {e}"))?;
+
+        let output = std::process::Command::new("/usr/bin/afconvert")
+            .args(["-f", "m4af", "-d", "aac", "-b", &AAC_BIT_RATE.to_string()])
+            .arg(&input_path)
+            .arg(&output_path)
+            .output();
+        let _ = std::fs::remove_file(&input_path);
+        let output = output.map_err(|e| format!("Failed to run afconvert: {e}"))?;
+
+        let encoded = if output.status.success() {
+            std::fs::read(&output_path).map_err(|e| format!("Failed to read M4A: {e}"))
+        } else {
+            Err(format!(
+                "afconvert failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        };
+        let _ = std::fs::remove_file(&output_path);
+
+        let encoded = encoded?;
+        if encoded.len() < 12 || &encoded[4..8] != b"ftyp" {
+            return Err("afconvert did not produce a valid M4A".to_string());
+        }
+        Ok(encoded)
+    }
+
+    pub fn microphone_permission() -> PermissionStatus {
+        let Some(media_type) = (unsafe { AVMediaTypeAudio }) else {
+            return PermissionStatus::NotDetermined;
+        };
+        let status = unsafe { AVCaptureDevice::authorizationStatusForMediaType(media_type) };
+        match status {
+            AVAuthorizationStatus::Authorized => PermissionStatus::Granted,
+            AVAuthorizationStatus::Denied => PermissionStatus::Denied,
+            AVAuthorizationStatus::Restricted => PermissionStatus::Restricted,
+            _ => PermissionStatus::NotDetermined,
+        }
+    }
+
+    pub async fn request_microphone_permission() -> PermissionStatus {
+        let current = microphone_permission();
+        if current != PermissionStatus::NotDetermined {
+            return current;
+        }
+        let Some(media_type) = (unsafe { AVMediaTypeAudio }) else {
+            return current;
+        };
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<bool>();
+        {
+            // The completion handler runs on an arbitrary dispatch queue; the block itself is
+            // not `Send`, so it must be dropped before awaiting.
+            let tx = Mutex::new(Some(tx));
+            let handler: RcBlock<dyn Fn(Bool)> = RcBlock::new(move |granted: Bool| {
+                if let Some(tx) = tx.lock().ok().and_then(|mut tx| tx.take()) {
+                    let _ = tx.send(granted.as_bool());
+                }
+            });
+            unsafe {
+                AVCaptureDevice::requestAccessForMediaType_completionHandler(media_type, &handler)
+            };
+        }
+
+        match rx.await {
+            Ok(true) => PermissionStatus::Granted,
+            Ok(false) => PermissionStatus::Denied,
+            Err(_) => microphone_permission(),
+        }
+    }
+
+    pub fn is_active() -> bool {
+        match state().lock() {
+            Ok(guard) => guard.as_ref().is_some(),
+            Err(_) => false,
+        }
+    }
+
+    /// (average, peak) power in dBFS over roughly the last meter interval of audio.
+    pub fn sample_level() -> Option<(f32, f32)> {
+        let guard = state().lock().ok()?;
+        let state = guard.as_ref()?;
+        if state.resumed_at.is_none() {
+            return Some((f32::NEG_INFINITY, f32::NEG_INFINITY));
+        }
+        let samples = state.samples.lock().ok()?;
+        Some(super::recent_level(&samples, state.sample_rate))
+    }
+
+    pub fn start(format: RecordingFormat) -> Result<(), String> {
+        let mut guard = state()
+            .lock()
+            .map_err(|_| "Native recorder state poisoned".to_string())?;
+
+        if guard.as_ref().is_some() {
+            return Err("Recording already in progress".to_string());
+        }
+
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<u32, String>>();
+        let (control_tx, control_rx) = mpsc::channel::<Control>();
+
+        let engine_samples = samples.clone();
+        let worker = std::thread::Builder::new()
+            .name("typefree-audio-engine".to_string())
+            .spawn(move || run_engine(engine_samples, ready_tx, control_rx))
+            .map_err(|e| format!("Failed to spawn audio engine thread: {e}"))?;
+
+        let sample_rate = match ready_rx.recv() {
+            Ok(Ok(sample_rate)) => sample_rate,
+            Ok(Err(err)) => {
+                let _ = worker.join();
+                return Err(err);
+            }
+            Err(_) => {
+                let _ = worker.join();
+                return Err("Audio engine thread exited before the microphone opened".to_string());
+            }
+        };
+
+        *guard = Some(RecorderState {
+            control_tx,
+            worker,
+            samples,
+            sample_rate,
+            format,
+            started_at: Instant::now(),
+            active_elapsed: Duration::ZERO,
+            resumed_at: Some(Instant::now()),
+        });
+
+        Ok(())
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Native recording is only supported on macOS".to_string())
+    /// (paused, recorded seconds, time since start) for the current recording.
+    pub fn status() -> Option<(bool, f64, Duration)> {
+        let guard = state().lock().ok()?;
+        let state = guard.as_ref()?;
+        Some((
+            state.resumed_at.is_none(),
+            state.active_duration().as_secs_f64(),
+            state.started_at.elapsed(),
+        ))
     }
-}
 
-#[tauri::command]
-pub async fn stop_native_recording() -> Result<NativeRecordingResult, String> {
-    #[cfg(target_os = "macos")]
-    {
-        return macos::stop();
+    pub fn is_paused() -> bool {
+        match state().lock() {
+            Ok(guard) => guard
+                .as_ref()
+                .is_some_and(|state| state.resumed_at.is_none()),
+            Err(_) => false,
+        }
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Native recording is only supported on macOS".to_string())
-    }
-}
+    pub fn pause() -> Result<(), String> {
+        let mut guard = state()
+            .lock()
+            .map_err(|_| "Native recorder state poisoned".to_string())?;
+        let state = guard
+            .as_mut()
+            .ok_or_else(|| "Not currently recording".to_string())?;
+        let Some(resumed_at) = state.resumed_at else {
+            return Ok(());
+        };
 
-#[tauri::command]
-pub async fn cancel_native_recording() -> Result<bool, String> {
-    #[cfg(target_os = "macos")]
-    {
-        return macos::cancel().map(|_| true);
+        state
+            .control_tx
+            .send(Control::Pause)
+            .map_err(|_| "Audio engine is no longer running".to_string())?;
+        state.active_elapsed += resumed_at.elapsed();
+        state.resumed_at = None;
+        Ok(())
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Native recording is only supported on macOS".to_string())
+    pub fn resume() -> Result<(), String> {
+        let mut guard = state()
+            .lock()
+            .map_err(|_| "Native recorder state poisoned".to_string())?;
+        let state = guard
+            .as_mut()
+            .ok_or_else(|| "Not currently recording".to_string())?;
+        if state.resumed_at.is_some() {
+            return Ok(());
+        }
+
+        state
+            .control_tx
+            .send(Control::Resume)
+            .map_err(|_| "Audio engine is no longer running".to_string())?;
+        state.resumed_at = Some(Instant::now());
+        Ok(())
     }
-}
 
-/// Check if the macOS native recorder is currently active.
-pub fn is_native_recording_active() -> bool {
-    #[cfg(target_os = "macos")]
-    {
-        return macos::is_active();
+    pub fn stop() -> Result<NativeRecordingResult, String> {
+        let state = {
+            let mut guard = state()
+                .lock()
+                .map_err(|_| "Native recorder state poisoned".to_string())?;
+            guard
+                .take()
+                .ok_or_else(|| "Not currently recording".to_string())?
+        };
+
+        let wall_clock = state.active_duration();
+        let (samples, sample_rate, format) = shutdown(state);
+        let captured = std::mem::take(
+            &mut *samples
+                .lock()
+                .map_err(|_| "Native recorder buffer poisoned".to_string())?,
+        );
+        if captured.is_empty() {
+            return Err(
+                "Native recording captured no audio (check microphone privacy settings)"
+                    .to_string(),
+            );
+        }
+
+        let mut resampled = super::resample_linear(&captured, sample_rate, TARGET_SAMPLE_RATE);
+        super::cpal_capture::mix_system_audio(&mut resampled);
+        let wav = super::encode_wav_pcm16(&resampled, TARGET_SAMPLE_RATE);
+        let duration_seconds =
+            Some(super::wav_duration_seconds(&wav).unwrap_or_else(|| wall_clock.as_secs_f64()));
+
+        let (audio_data, format) = match format {
+            RecordingFormat::Wav => (wav, RecordingFormat::Wav),
+            RecordingFormat::M4a => match encode_m4a(&wav) {
+                Ok(m4a) => (m4a, RecordingFormat::M4a),
+                Err(err) => {
+                    crate::commands::logging::log(
+                        "recording",
+                        "error",
+                        &format!("M4A encoding failed, keeping WAV: {}", err),
+                    );
+                    (wav, RecordingFormat::Wav)
+                }
+            },
+        };
+
+        Ok(NativeRecordingResult {
+            audio_data,
+            mime_type: format.mime_type().to_string(),
+            duration_seconds,
+            audio_path: None,
+            level_db: Some(super::rms_db(&resampled)),
+        })
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        false
+    pub fn cancel() -> Result<(), String> {
+        let state = {
+            let mut guard = state()
+                .lock()
+                .map_err(|_| "Native recorder state poisoned".to_string())?;
+            guard.take()
+        };
+
+        if let Some(state) = state {
+            shutdown(state);
+        }
+
+        Ok(())
     }
 }
 
-#[cfg(target_os = "macos")]
-mod macos {
-    use super::NativeRecordingResult;
-    use objc2::exception;
-    use objc2::rc::Retained;
-    use objc2::runtime::{AnyObject, ProtocolObject};
-    use objc2::{AnyThread, ClassType};
-    use objc2_avf_audio::{
-        AVAudioRecorder, AVFormatIDKey, AVLinearPCMBitDepthKey, AVLinearPCMIsBigEndianKey,
-        AVLinearPCMIsFloatKey, AVNumberOfChannelsKey, AVSampleRateKey,
-    };
-    use objc2_foundation::{NSDictionary, NSError, NSMutableDictionary, NSNumber, NSString, NSURL};
-    use std::ffi::CString;
-    use std::panic::AssertUnwindSafe;
-    use std::path::PathBuf;
-    use std::ptr::NonNull;
-    use std::sync::{Mutex, OnceLock};
-    use std::time::Duration;
-    use std::time::Instant;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod cpal_capture {
+    use super::{AudioInputDevice, NativeRecordingResult};
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{BuildStreamError, Device, SampleFormat, Stream, StreamConfig};
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex, OnceLock};
+    use std::thread::JoinHandle;
+    use std::time::{Duration, Instant};
 
-    const K_AUDIO_FORMAT_LINEAR_PCM: u32 = 0x6C70_636D; // 'lpcm'
+    const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+    enum Control {
+        Pause,
+        Resume,
+        Stop,
+    }
 
     struct RecorderState {
-        recorder: Retained<AVAudioRecorder>,
-        path: PathBuf,
+        control_tx: mpsc::Sender<Control>,
+        worker: JoinHandle<()>,
+        paused: bool,
+        // Mono samples at the device's native rate; resampled on stop.
+        samples: Arc<Mutex<Vec<f32>>>,
+        sample_rate: u32,
         started_at: Instant,
     }
 
     static RECORDER_STATE: OnceLock<Mutex<Option<RecorderState>>> = OnceLock::new();
+    // Loopback capture that runs alongside the microphone when `captureSystemAudio` is on.
+    static SYSTEM_AUDIO_STATE: OnceLock<Mutex<Option<RecorderState>>> = OnceLock::new();
 
     fn state() -> &'static Mutex<Option<RecorderState>> {
         RECORDER_STATE.get_or_init(|| Mutex::new(None))
     }
 
-    fn nsstring_from_str(s: &str) -> Result<Retained<NSString>, String> {
-        let cstr = CString::new(s)
-            .map_err(|_| "Failed to create NSString (string contains null byte)".to_string())?;
-        let ptr = NonNull::new(cstr.as_ptr() as *mut i8)
-            .ok_or_else(|| "Failed to create NSString (null pointer)".to_string())?;
-        unsafe { NSString::stringWithUTF8String(ptr.cast()) }
-            .ok_or_else(|| "Failed to create NSString from UTF-8".to_string())
+    fn system_audio_state() -> &'static Mutex<Option<RecorderState>> {
+        SYSTEM_AUDIO_STATE.get_or_init(|| Mutex::new(None))
     }
 
-    fn ns_error_to_string(error: &NSError) -> String {
-        // `NSString` implements Display in objc2-foundation.
-        let desc = error.localizedDescription();
-        desc.to_string()
+    fn describe_build_error(err: BuildStreamError) -> String {
+        match err {
+            BuildStreamError::DeviceNotAvailable => {
+                "Microphone is no longer available (was it disconnected?)".to_string()
+            }
+            BuildStreamError::StreamConfigNotSupported => {
+                "Microphone does not support its reported default format".to_string()
+            }
+            other => format!(
+                "Failed to open microphone (it may be in use by another application): {other}"
+            ),
+        }
+    }
+
+    fn push_frames<T: Copy>(
+        samples: &Mutex<Vec<f32>>,
+        data: &[T],
+        channels: usize,
+        to_f32: impl Fn(T) -> f32,
+    ) {
+        let Ok(mut buffer) = samples.lock() else {
+            return;
+        };
+        // Downmix interleaved frames to mono by averaging channels.
+        for frame in data.chunks(channels) {
+            let sum: f32 = frame.iter().map(|s| to_f32(*s)).sum();
+            buffer.push(sum / frame.len() as f32);
+        }
     }
 
-    fn any_object(value: &NSNumber) -> &AnyObject {
-        // NSNumber -> NSValue -> NSObject -> AnyObject
-        value.as_super().as_super().as_super()
+    fn find_input_device(host: &cpal::Host, name: &str) -> Option<Device> {
+        host.input_devices()
+            .ok()?
+            .find(|device| device.name().map(|n| n == name).unwrap_or(false))
     }
 
-    fn unique_recording_path() -> PathBuf {
-        let pid = std::process::id();
-        let now_ns = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-        std::env::temp_dir().join(format!("typefree-native-recording-{pid}-{now_ns}.wav"))
+    pub fn default_input_device_name() -> Option<String> {
+        cpal::default_host()
+            .default_input_device()
+            .and_then(|device| device.name().ok())
     }
 
-    fn is_wav_header(bytes: &[u8]) -> bool {
-        bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE"
+    pub fn list_input_devices() -> Result<Vec<AudioInputDevice>, String> {
+        let host = cpal::default_host();
+        let default_name = host
+            .default_input_device()
+            .and_then(|device| device.name().ok());
+        let devices = host
+            .input_devices()
+            .map_err(|e| format!("Failed to enumerate input devices: {e}"))?;
+
+        // cpal has no stable device identifier, so the name doubles as the id.
+        Ok(devices
+            .filter_map(|device| device.name().ok())
+            .map(|name| AudioInputDevice {
+                id: name.clone(),
+                is_default: default_name.as_deref() == Some(name.as_str()),
+                name,
+            })
+            .collect())
     }
 
-    fn bytes_prefix_hex(bytes: &[u8], max_len: usize) -> String {
-        let prefix = &bytes[..bytes.len().min(max_len)];
-        let mut out = String::new();
-        for (idx, b) in prefix.iter().enumerate() {
-            if idx > 0 {
-                out.push(' ');
-            }
-            out.push_str(&format!("{:02x}", b));
+    fn open_input_stream(
+        preferred: Option<&str>,
+        samples: Arc<Mutex<Vec<f32>>>,
+    ) -> Result<(Stream, u32), String> {
+        let host = cpal::default_host();
+        let device = match preferred {
+            Some(name) => find_input_device(&host, name)
+                .ok_or_else(|| format!("Microphone '{name}' is no longer connected"))?,
+            None => host.default_input_device().ok_or_else(|| {
+                "No microphone found. Connect an input device and try again.".to_string()
+            })?,
+        };
+        let supported = device
+            .default_input_config()
+            .map_err(|e| format!("Failed to query microphone format: {e}"))?;
+
+        let sample_format = supported.sample_format();
+        let config: StreamConfig = supported.into();
+        let channels = usize::from(config.channels.max(1));
+        let sample_rate = config.sample_rate.0;
+        let err_fn = |err: cpal::StreamError| {
+            crate::commands::logging::log(
+                "recording",
+                "error",
+                &format!("input stream error: {err}"),
+            )
+        };
+
+        let stream = match sample_format {
+            SampleFormat::F32 => device.build_input_stream(
+                &config,
+                move |data: &[f32], _: &_| push_frames(&samples, data, channels, |s| s),
+                err_fn,
+                None,
+            ),
+            SampleFormat::I16 => device.build_input_stream(
+                &config,
+                move |data: &[i16], _: &_| {
+                    push_frames(&samples, data, channels, |s| s as f32 / 32_768.0)
+                },
+                err_fn,
+                None,
+            ),
+            SampleFormat::U16 => device.build_input_stream(
+                &config,
+                move |data: &[u16], _: &_| {
+                    push_frames(&samples, data, channels, |s| {
+                        (s as f32 - 32_768.0) / 32_768.0
+                    })
+                },
+                err_fn,
+                None,
+            ),
+            other => return Err(format!("Unsupported microphone sample format: {other}")),
         }
-        out
-    }
+        .map_err(describe_build_error)?;
 
-    fn read_wav_with_retry(path: &PathBuf) -> Result<Vec<u8>, String> {
-        let mut last_len = 0usize;
-        let mut last_prefix = String::new();
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start microphone capture: {e}"))?;
 
-        // `AVAudioRecorder.stop()` should finalize synchronously, but in practice the file can
-        // briefly appear empty/partial. Retrying avoids uploading truncated WAVs.
-        for _ in 0..25 {
-            match std::fs::read(path) {
-                Ok(bytes) => {
-                    last_len = bytes.len();
-                    last_prefix = bytes_prefix_hex(&bytes, 16);
-                    if is_wav_header(&bytes) && bytes.len() >= 44 {
-                        return Ok(bytes);
-                    }
-                }
-                Err(_) => {
-                    // Still being created; keep waiting.
-                }
-            }
+        Ok((stream, sample_rate))
+    }
 
-            std::thread::sleep(Duration::from_millis(20));
+    /// Signal the capture thread to drop its stream and wait for it to exit.
+    fn shutdown(state: RecorderState) -> (Arc<Mutex<Vec<f32>>>, u32, Instant) {
+        let _ = state.control_tx.send(Control::Stop);
+        if state.worker.join().is_err() {
+            crate::commands::logging::log("recording", "error", "capture thread panicked");
         }
+        (state.samples, state.sample_rate, state.started_at)
+    }
 
-        Err(format!(
-            "Native recording did not produce a valid WAV (len={last_len}, prefix={last_prefix}). File kept at: {}",
-            path.to_string_lossy()
-        ))
+    /// (average, peak) power in dBFS over roughly the last meter interval of audio.
+    pub fn sample_level() -> Option<(f32, f32)> {
+        let guard = state().lock().ok()?;
+        let state = guard.as_ref()?;
+        if state.paused {
+            return Some((f32::NEG_INFINITY, f32::NEG_INFINITY));
+        }
+        let samples = state.samples.lock().ok()?;
+        Some(super::recent_level(&samples, state.sample_rate))
     }
 
     pub fn is_active() -> bool {
@@ -174,7 +1998,8 @@ mod macos {
         }
     }
 
-    pub fn start() -> Result<(), String> {
+    /// Start capturing from `preferred` (a device name) or the system default input.
+    pub fn start(preferred: Option<String>) -> Result<(), String> {
         let mut guard = state()
             .lock()
             .map_err(|_| "Native recorder state poisoned".to_string())?;
@@ -183,104 +2008,211 @@ mod macos {
             return Err("Recording already in progress".to_string());
         }
 
-        let path = unique_recording_path();
-        let path_str = path.to_string_lossy();
-        let ns_path = nsstring_from_str(&path_str)?;
-        let url = NSURL::fileURLWithPath(&ns_path);
-
-        let settings = NSMutableDictionary::<NSString, AnyObject>::initWithCapacity(
-            NSMutableDictionary::alloc(),
-            8,
-        );
+        *guard = Some(spawn_capture(preferred, "typefree-audio-capture")?);
+        Ok(())
+    }
 
-        let format_key =
-            unsafe { AVFormatIDKey }.ok_or_else(|| "AVFormatIDKey unavailable".to_string())?;
-        let sample_rate_key =
-            unsafe { AVSampleRateKey }.ok_or_else(|| "AVSampleRateKey unavailable".to_string())?;
-        let channels_key = unsafe { AVNumberOfChannelsKey }
-            .ok_or_else(|| "AVNumberOfChannelsKey unavailable".to_string())?;
-        let bit_depth_key = unsafe { AVLinearPCMBitDepthKey }
-            .ok_or_else(|| "AVLinearPCMBitDepthKey unavailable".to_string())?;
-        let big_endian_key = unsafe { AVLinearPCMIsBigEndianKey }
-            .ok_or_else(|| "AVLinearPCMIsBigEndianKey unavailable".to_string())?;
-        let float_key = unsafe { AVLinearPCMIsFloatKey }
-            .ok_or_else(|| "AVLinearPCMIsFloatKey unavailable".to_string())?;
-
-        let format_id = NSNumber::initWithUnsignedInt(NSNumber::alloc(), K_AUDIO_FORMAT_LINEAR_PCM);
-        let sample_rate = NSNumber::initWithDouble(NSNumber::alloc(), 16_000.0);
-        let channels = NSNumber::initWithUnsignedInt(NSNumber::alloc(), 1);
-        let bit_depth = NSNumber::initWithUnsignedInt(NSNumber::alloc(), 16);
-        let is_big_endian = NSNumber::initWithBool(NSNumber::alloc(), false);
-        let is_float = NSNumber::initWithBool(NSNumber::alloc(), false);
-
-        unsafe {
-            settings.setObject_forKey(any_object(&format_id), ProtocolObject::from_ref(format_key));
-            settings.setObject_forKey(
-                any_object(&sample_rate),
-                ProtocolObject::from_ref(sample_rate_key),
-            );
-            settings.setObject_forKey(
-                any_object(&channels),
-                ProtocolObject::from_ref(channels_key),
-            );
-            settings.setObject_forKey(
-                any_object(&bit_depth),
-                ProtocolObject::from_ref(bit_depth_key),
-            );
-            settings.setObject_forKey(
-                any_object(&is_big_endian),
-                ProtocolObject::from_ref(big_endian_key),
-            );
-            settings.setObject_forKey(any_object(&is_float), ProtocolObject::from_ref(float_key));
-        }
+    /// Open `preferred` on a dedicated thread and wait until the stream is running.
+    fn spawn_capture(
+        preferred: Option<String>,
+        thread_name: &str,
+    ) -> Result<RecorderState, String> {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<u32, String>>();
+        let (control_tx, control_rx) = mpsc::channel::<Control>();
 
-        let settings_dict: &NSDictionary<NSString, AnyObject> = settings.as_super();
+        // cpal streams are not `Send` on every backend, so the stream lives and dies on its
+        // own thread; we only talk to it through channels.
+        let stream_samples = samples.clone();
+        let worker = std::thread::Builder::new()
+            .name(thread_name.to_string())
+            .spawn(move || {
+                let stream = match open_input_stream(preferred.as_deref(), stream_samples) {
+                    Ok((stream, sample_rate)) => {
+                        let _ = ready_tx.send(Ok(sample_rate));
+                        stream
+                    }
+                    Err(err) => {
+                        let _ = ready_tx.send(Err(err));
+                        return;
+                    }
+                };
+                while let Ok(control) = control_rx.recv() {
+                    let result = match control {
+                        Control::Pause => stream.pause().map_err(|e| e.to_string()),
+                        Control::Resume => stream.play().map_err(|e| e.to_string()),
+                        Control::Stop => break,
+                    };
+                    if let Err(err) = result {
+                        crate::commands::logging::log(
+                            "recording",
+                            "error",
+                            &format!("failed to pause/resume capture: {}", err),
+                        );
+                    }
+                }
+                drop(stream);
+            })
+            .map_err(|e| format!("Failed to spawn capture thread: {e}"))?;
 
-        let recorder = match exception::catch(AssertUnwindSafe(|| unsafe {
-            AVAudioRecorder::initWithURL_settings_error(
-                AVAudioRecorder::alloc(),
-                &url,
-                settings_dict,
-            )
-        })) {
-            Ok(Ok(recorder)) => recorder,
-            Ok(Err(err)) => return Err(ns_error_to_string(&err)),
-            Err(exc) => {
-                return Err(format!(
-                    "Objective-C exception while creating recorder: {:?}",
-                    exc
-                ));
+        let sample_rate = match ready_rx.recv() {
+            Ok(Ok(sample_rate)) => sample_rate,
+            Ok(Err(err)) => {
+                let _ = worker.join();
+                return Err(err);
+            }
+            Err(_) => {
+                let _ = worker.join();
+                return Err("Capture thread exited before the microphone opened".to_string());
             }
         };
 
-        let prepared =
-            match exception::catch(AssertUnwindSafe(|| unsafe { recorder.prepareToRecord() })) {
-                Ok(prepared) => prepared,
-                Err(exc) => {
-                    return Err(format!(
-                        "Objective-C exception during prepareToRecord: {:?}",
-                        exc
-                    ));
-                }
+        Ok(RecorderState {
+            control_tx,
+            worker,
+            paused: false,
+            samples,
+            sample_rate,
+            started_at: Instant::now(),
+        })
+    }
+
+    /// Input devices that expose system output as an input (virtual loopback drivers).
+    #[cfg(target_os = "macos")]
+    const LOOPBACK_DEVICE_HINTS: &[&str] = &["blackhole", "loopback", "soundflower"];
+
+    /// The loopback device to record system audio from: `configured` if it is connected,
+    /// otherwise the first input whose name looks like a known loopback driver.
+    #[cfg(target_os = "macos")]
+    pub fn find_loopback_device(configured: Option<&str>) -> Option<String> {
+        let devices = list_input_devices().ok()?;
+        if let Some(configured) = configured {
+            return devices
+                .into_iter()
+                .find(|device| device.id == configured)
+                .map(|device| device.id);
+        }
+        devices
+            .into_iter()
+            .find(|device| {
+                let name = device.name.to_ascii_lowercase();
+                LOOPBACK_DEVICE_HINTS.iter().any(|hint| name.contains(hint))
+            })
+            .map(|device| device.id)
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn start_system_audio(device: String) -> Result<(), String> {
+        let mut guard = system_audio_state()
+            .lock()
+            .map_err(|_| "System audio state poisoned".to_string())?;
+        if let Some(previous) = guard.take() {
+            shutdown(previous);
+        }
+        *guard = Some(spawn_capture(Some(device), "typefree-system-audio")?);
+        Ok(())
+    }
+
+    /// Keep the system audio stream in step with the microphone's pause state.
+    #[cfg(target_os = "macos")]
+    pub fn set_system_audio_paused(paused: bool) {
+        let Ok(mut guard) = system_audio_state().lock() else {
+            return;
+        };
+        let Some(state) = guard.as_mut() else {
+            return;
+        };
+        if state.paused != paused {
+            let control = if paused {
+                Control::Pause
+            } else {
+                Control::Resume
             };
-        if !prepared {
-            return Err("Failed to prepare audio recorder".to_string());
+            let _ = state.control_tx.send(control);
+            state.paused = paused;
+        }
+    }
+
+    /// Stop the system audio stream (if any) and add it into `samples` (16 kHz mono).
+    pub fn mix_system_audio(samples: &mut Vec<f32>) {
+        let state = match system_audio_state().lock() {
+            Ok(mut guard) => guard.take(),
+            Err(_) => None,
+        };
+        let Some(state) = state else {
+            return;
+        };
+
+        let (system_samples, sample_rate, _) = shutdown(state);
+        let captured = match system_samples.lock() {
+            Ok(mut buffer) => std::mem::take(&mut *buffer),
+            Err(_) => return,
+        };
+        let system = super::resample_linear(&captured, sample_rate, TARGET_SAMPLE_RATE);
+
+        // Both streams start within a few milliseconds of each other, so align them at zero.
+        if system.len() > samples.len() {
+            samples.resize(system.len(), 0.0);
+        }
+        for (sample, other) in samples.iter_mut().zip(system) {
+            *sample = (*sample + other).clamp(-1.0, 1.0);
         }
+    }
 
-        let started = match exception::catch(AssertUnwindSafe(|| unsafe { recorder.record() })) {
-            Ok(started) => started,
-            Err(exc) => return Err(format!("Objective-C exception during record: {:?}", exc)),
+    #[cfg(target_os = "macos")]
+    pub fn cancel_system_audio() {
+        let state = match system_audio_state().lock() {
+            Ok(mut guard) => guard.take(),
+            Err(_) => None,
         };
-        if !started {
-            return Err("Failed to start recording (microphone permission?)".to_string());
+        if let Some(state) = state {
+            shutdown(state);
         }
+    }
 
-        *guard = Some(RecorderState {
-            recorder,
-            path,
-            started_at: Instant::now(),
-        });
+    /// (paused, recorded seconds, time since start). Recorded time comes from the captured
+    /// sample count, so paused stretches are excluded.
+    pub fn status() -> Option<(bool, f64, Duration)> {
+        let guard = state().lock().ok()?;
+        let state = guard.as_ref()?;
+        let captured = state.samples.lock().map(|s| s.len()).unwrap_or(0);
+        Some((
+            state.paused,
+            captured as f64 / state.sample_rate as f64,
+            state.started_at.elapsed(),
+        ))
+    }
+
+    pub fn is_paused() -> bool {
+        match state().lock() {
+            Ok(guard) => guard.as_ref().is_some_and(|state| state.paused),
+            Err(_) => false,
+        }
+    }
+
+    /// Pause or resume the stream. Samples stop arriving while paused, so the WAV (and its
+    /// duration) only covers active time.
+    pub fn set_paused(paused: bool) -> Result<(), String> {
+        let mut guard = state()
+            .lock()
+            .map_err(|_| "Native recorder state poisoned".to_string())?;
+        let state = guard
+            .as_mut()
+            .ok_or_else(|| "Not currently recording".to_string())?;
+        if state.paused == paused {
+            return Ok(());
+        }
 
+        let control = if paused {
+            Control::Pause
+        } else {
+            Control::Resume
+        };
+        state
+            .control_tx
+            .send(control)
+            .map_err(|_| "Capture thread is no longer running".to_string())?;
+        state.paused = paused;
         Ok(())
     }
 
@@ -294,19 +2226,33 @@ mod macos {
                 .ok_or_else(|| "Not currently recording".to_string())?
         };
 
-        if let Err(exc) = exception::catch(AssertUnwindSafe(|| unsafe { state.recorder.stop() })) {
-            return Err(format!("Objective-C exception during stop: {:?}", exc));
-        }
+        let (samples, sample_rate, started_at) = shutdown(state);
+        let captured = std::mem::take(
+            &mut *samples
+                .lock()
+                .map_err(|_| "Native recorder buffer poisoned".to_string())?,
+        );
 
-        let duration_seconds = Some(state.started_at.elapsed().as_secs_f64());
+        if captured.is_empty() {
+            return Err(
+                "Native recording captured no audio (check microphone privacy settings)"
+                    .to_string(),
+            );
+        }
 
-        let audio_data = read_wav_with_retry(&state.path)?;
-        let _ = std::fs::remove_file(&state.path);
+        let mut resampled = super::resample_linear(&captured, sample_rate, TARGET_SAMPLE_RATE);
+        mix_system_audio(&mut resampled);
+        let wav = super::encode_wav_pcm16(&resampled, TARGET_SAMPLE_RATE);
+        let duration_seconds = Some(
+            super::wav_duration_seconds(&wav).unwrap_or_else(|| started_at.elapsed().as_secs_f64()),
+        );
 
         Ok(NativeRecordingResult {
-            audio_data,
+            audio_data: wav,
             mime_type: "audio/wav".to_string(),
             duration_seconds,
+            audio_path: None,
+            level_db: Some(super::rms_db(&resampled)),
         })
     }
 
@@ -319,14 +2265,286 @@ mod macos {
         };
 
         if let Some(state) = state {
-            if let Err(exc) =
-                exception::catch(AssertUnwindSafe(|| unsafe { state.recorder.stop() }))
-            {
-                eprintln!("[recording] objc exception during cancel stop: {:?}", exc);
-            }
-            let _ = std::fs::remove_file(&state.path);
+            shutdown(state);
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pcm16_wav(samples: &[i16]) -> Vec<u8> {
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&16_000u32.to_le_bytes());
+        wav.extend_from_slice(&32_000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&data);
+        wav
+    }
+
+    fn samples(data: &[u8]) -> Vec<i16> {
+        data.chunks_exact(2)
+            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect()
+    }
+
+    fn rms_dbfs(samples: &[i16]) -> f64 {
+        let mean_square = samples
+            .iter()
+            .map(|&s| (s as f64 / 32_768.0).powi(2))
+            .sum::<f64>()
+            / samples.len() as f64;
+        10.0 * mean_square.log10()
+    }
+
+    #[test]
+    fn unity_gain_leaves_samples_unchanged() {
+        let original = pcm16_wav(&[0, 1, -1, 12_345, i16::MAX, i16::MIN]);
+        let mut wav = original.clone();
+        assert_eq!(apply_gain(&mut wav, false, 0.0), Ok(None));
+        assert_eq!(wav, original);
+
+        let mut data = original[44..].to_vec();
+        scale_pcm16(&mut data, 1.0);
+        assert_eq!(data, original[44..]);
+    }
+
+    #[test]
+    fn fixed_gain_scales_samples() {
+        let mut wav = pcm16_wav(&[1_000, -1_000]);
+        let gain = apply_gain(&mut wav, false, 6.0).unwrap().unwrap();
+        assert!((gain - 1.995).abs() < 0.001, "gain {gain}");
+        assert_eq!(samples(&wav[44..]), [1_995, -1_995]);
+    }
+
+    #[test]
+    fn auto_gain_brings_a_quiet_signal_to_the_target() {
+        // About -40 dBFS RMS.
+        let quiet: Vec<i16> = (0..1_600)
+            .map(|i| if i % 2 == 0 { 328 } else { -328 })
+            .collect();
+        let mut wav = pcm16_wav(&quiet);
+        let gain = apply_gain(&mut wav, true, 0.0).unwrap().unwrap();
+        assert!((20.0 * gain.log10() - 20.0).abs() < 0.1, "gain {gain}");
+        let level = rms_dbfs(&samples(&wav[44..]));
+        assert!((level - AUTO_GAIN_TARGET_DBFS).abs() < 0.1, "level {level}");
+    }
+
+    #[test]
+    fn auto_gain_is_capped_by_the_peak_and_the_maximum() {
+        let mut spiky = vec![10i16; 1_000];
+        spiky[500] = 16_384;
+        let gain = auto_gain_factor(&pcm16_wav(&spiky)[44..]).unwrap();
+        assert!((gain - 2.0).abs() < 1e-9, "gain {gain}");
+
+        let gain = auto_gain_factor(&pcm16_wav(&[1, -1, 1, -1])[44..]).unwrap();
+        assert!(
+            (gain - db_to_linear(AUTO_GAIN_MAX_DB)).abs() < 1e-9,
+            "gain {gain}"
+        );
+
+        assert_eq!(auto_gain_factor(&pcm16_wav(&[0; 100])[44..]), None);
+        assert_eq!(auto_gain_factor(&[]), None);
+    }
+
+    #[test]
+    fn scaling_saturates_instead_of_wrapping() {
+        let mut data = pcm16_wav(&[i16::MAX, i16::MIN, 20_000, -20_000, 100])[44..].to_vec();
+        scale_pcm16(&mut data, 2.0);
+        assert_eq!(
+            samples(&data),
+            [i16::MAX, i16::MIN, i16::MAX, i16::MIN, 200]
+        );
+
+        let mut wav = pcm16_wav(&[30_000, -30_000]);
+        apply_gain(&mut wav, false, 12.0).unwrap();
+        assert_eq!(samples(&wav[44..]), [i16::MAX, i16::MIN]);
+    }
+
+    #[test]
+    fn odd_length_data_keeps_the_trailing_byte() {
+        let mut data = vec![0x10, 0x00, 0x20, 0x00, 0x7f];
+        scale_pcm16(&mut data, 2.0);
+        assert_eq!(data, [0x20, 0x00, 0x40, 0x00, 0x7f]);
+        assert!(auto_gain_factor(&[0x7f]).is_none());
+
+        // A data chunk cut off mid-sample.
+        let mut wav = pcm16_wav(&[0x10, 0x20]);
+        wav.pop();
+        apply_gain(&mut wav, false, 6.0).unwrap();
+        assert_eq!(samples(&wav[44..]), [0x20]);
+        assert_eq!(wav.len(), 47);
+    }
+
+    #[test]
+    fn buffers_shorter_than_the_header_are_left_alone() {
+        let full = pcm16_wav(&[1_000, -1_000]);
+        for len in [0, 4, 11, 12, 20, 36, 40] {
+            let mut wav = full[..len].to_vec();
+            assert!(apply_gain(&mut wav, false, 6.0).is_err(), "len {len}");
+            assert!(apply_gain(&mut wav, true, 0.0).is_err(), "len {len}");
+            assert_eq!(wav, full[..len]);
+        }
+
+        // Header only: a data chunk with no samples.
+        let mut wav = full[..44].to_vec();
+        assert_eq!(apply_gain(&mut wav, true, 0.0), Ok(None));
+        assert_eq!(
+            apply_gain(&mut wav, false, 6.0),
+            Ok(Some(db_to_linear(6.0)))
+        );
+    }
+
+    /// A WAV with the given `fmt ` body, extra chunks before `data`, and `data_len` bytes
+    /// of silence.
+    fn wav_with_chunks(fmt: &[u8], before_data: &[(&[u8; 4], &[u8])], data_len: usize) -> Vec<u8> {
+        let mut body = Vec::new();
+        let chunks = std::iter::once((b"fmt ", fmt)).chain(before_data.iter().copied());
+        for (id, chunk) in chunks {
+            body.extend_from_slice(id);
+            body.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+            body.extend_from_slice(chunk);
+            if chunk.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        body.extend_from_slice(b"data");
+        body.extend_from_slice(&(data_len as u32).to_le_bytes());
+        body.resize(body.len() + data_len, 0);
+
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(4 + body.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(&body);
+        wav
+    }
+
+    /// `fmt ` body for 16-bit mono at 16 kHz: 32 000 bytes per second.
+    fn pcm_fmt(format: u16) -> Vec<u8> {
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&format.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&16_000u32.to_le_bytes());
+        fmt.extend_from_slice(&32_000u32.to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+        fmt
+    }
+
+    #[test]
+    fn duration_from_a_plain_pcm_header() {
+        let wav = wav_with_chunks(&pcm_fmt(1), &[], 48_000);
+        assert_eq!(wav_duration_seconds(&wav), Some(1.5));
+        assert_eq!(wav_duration_seconds(&pcm16_wav(&[0; 8_000])), Some(0.5));
+    }
+
+    #[test]
+    fn duration_from_extended_fmt_chunks() {
+        // WAVEFORMATEX with an empty extension.
+        let mut fmt = pcm_fmt(1);
+        fmt.extend_from_slice(&0u16.to_le_bytes());
+        assert_eq!(fmt.len(), 18);
+        let wav = wav_with_chunks(&fmt, &[], 16_000);
+        assert_eq!(wav_duration_seconds(&wav), Some(0.5));
+
+        // WAVE_FORMAT_EXTENSIBLE: valid bits, channel mask and the PCM sub-format GUID.
+        let mut fmt = pcm_fmt(0xFFFE);
+        fmt.extend_from_slice(&22u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+        fmt.extend_from_slice(&4u32.to_le_bytes());
+        fmt.extend_from_slice(&[
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38,
+            0x9B, 0x71,
+        ]);
+        assert_eq!(fmt.len(), 40);
+        let wav = wav_with_chunks(&fmt, &[], 64_000);
+        assert_eq!(wav_duration_seconds(&wav), Some(2.0));
+    }
+
+    #[test]
+    fn duration_skips_chunks_before_data() {
+        let list: &[u8] = b"INFOISFT\x05\x00\x00\x00Lavf\x00";
+        let wav = wav_with_chunks(&pcm_fmt(1), &[(b"LIST", list), (b"junk", b"abc")], 32_000);
+        assert_eq!(wav_duration_seconds(&wav), Some(1.0));
+    }
+
+    #[test]
+    fn duration_of_truncated_or_malformed_headers_is_none() {
+        let wav = wav_with_chunks(&pcm_fmt(1), &[(b"LIST", b"INFO")], 32_000);
+        let data_start = wav.len() - 32_000;
+        for len in 0..data_start {
+            assert_eq!(wav_duration_seconds(&wav[..len]), None, "len {len}");
+        }
+        // Cut off inside the samples: only the bytes present count.
+        assert_eq!(wav_duration_seconds(&wav[..data_start + 16_000]), Some(0.5));
+
+        let mut short_fmt = wav_with_chunks(&pcm_fmt(1)[..14], &[], 32_000);
+        assert_eq!(wav_duration_seconds(&short_fmt), None);
+        short_fmt[0] = b'X';
+        assert_eq!(wav_duration_seconds(&short_fmt), None);
+
+        let mut zero_rate = pcm_fmt(1);
+        zero_rate[8..12].copy_from_slice(&0u32.to_le_bytes());
+        let wav = wav_with_chunks(&zero_rate, &[], 32_000);
+        assert_eq!(wav_duration_seconds(&wav), None);
+
+        // A chunk size that runs past the end of the buffer.
+        let mut huge = wav_with_chunks(&pcm_fmt(1), &[(b"LIST", b"INFO")], 32_000);
+        huge[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(wav_duration_seconds(&huge), None);
+    }
+
+    #[test]
+    fn pruning_keeps_the_new_recording_and_skips_subdirectories() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("typefree-prune-{nanos}"));
+        std::fs::create_dir_all(dir.join("invalid")).unwrap();
+        let write = |name: &str, len: usize, age_secs: u64| {
+            let path = dir.join(name);
+            std::fs::write(&path, vec![0u8; len]).unwrap();
+            let modified = std::time::SystemTime::now() - std::time::Duration::from_secs(age_secs);
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+            path
+        };
+        let invalid = write("invalid/invalid-1.wav", 500, 400);
+        let oldest = write("recording-1.wav", 100, 300);
+        let older = write("recording-2.wav", 100, 200);
+        let newest = write("recording-3.wav", 100, 0);
+
+        prune_recordings(&dir, 250, &newest);
+        assert!(!oldest.exists());
+        assert!(older.exists() && newest.exists() && invalid.exists());
+
+        // Even with no room at all, the recording just saved stays.
+        prune_recordings(&dir, 0, &oldest);
+        assert!(!older.exists() && !newest.exists());
+        let kept = write("recording-4.wav", 100, 500);
+        prune_recordings(&dir, 0, &kept);
+        assert!(kept.exists() && invalid.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
 
 fn get_env_file_path(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
@@ -37,21 +38,22 @@ fn save_env_file(path: &PathBuf, env_vars: &HashMap<String, String>) -> Result<(
     fs::write(path, content).map_err(|e| e.to_string())
 }
 
+const ENV_KEYS: &[&str] = &[
+    "ASSEMBLYAI_API_KEY",
+    "OPENAI_API_KEY",
+    "GROQ_API_KEY",
+    "DEEPSEEK_API_KEY",
+    "ZAI_API_KEY",
+    "ANTHROPIC_API_KEY",
+    "GEMINI_API_KEY",
+    "CUSTOM_REASONING_API_KEY",
+    "VOLCENGINE_APP_ID",
+    "VOLCENGINE_ACCESS_TOKEN",
+    "VOLCENGINE_RESOURCE_ID",
+];
+
 fn is_allowed_env_key(key: &str) -> bool {
-    matches!(
-        key,
-        "ASSEMBLYAI_API_KEY"
-            | "OPENAI_API_KEY"
-            | "GROQ_API_KEY"
-            | "DEEPSEEK_API_KEY"
-            | "ZAI_API_KEY"
-            | "ANTHROPIC_API_KEY"
-            | "GEMINI_API_KEY"
-            | "CUSTOM_REASONING_API_KEY"
-            | "VOLCENGINE_APP_ID"
-            | "VOLCENGINE_ACCESS_TOKEN"
-            | "VOLCENGINE_RESOURCE_ID"
-    )
+    ENV_KEYS.contains(&key)
 }
 
 fn validate_env_key(key: &str) -> Result<(), String> {
@@ -62,44 +64,145 @@ fn validate_env_key(key: &str) -> Result<(), String> {
     }
 }
 
-/// Get an environment variable from .env file
+/// Payload of `setting-changed`.
+#[derive(Debug, Clone, Serialize)]
+struct SettingChangedEvent {
+    key: String,
+    value: serde_json::Value,
+}
+
+/// Payload of `env-var-changed`; the value itself is never broadcast.
+#[derive(Debug, Clone, Serialize)]
+struct EnvVarChangedEvent {
+    key: String,
+}
+
+/// Service name API keys are stored under in the OS credential store.
+const KEYRING_SERVICE: &str = "com.typefree.app";
+
+fn keyring_entry(key: &str) -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, key)
+}
+
+fn remove_env_key(app: &AppHandle, key: &str) -> Result<(), String> {
+    let env_path = get_env_file_path(app)?;
+    let mut env_vars = load_env_file(&env_path);
+    if env_vars.remove(key).is_none() {
+        return Ok(());
+    }
+    if env_vars.is_empty() {
+        fs::remove_file(&env_path).map_err(|e| e.to_string())
+    } else {
+        save_env_file(&env_path, &env_vars)
+    }
+}
+
+/// Get an API key from the OS credential store (macOS Keychain, Windows Credential Manager,
+/// Secret Service), falling back to the .env file for keys that aren't there.
 #[tauri::command]
-pub fn get_env_var(app: AppHandle, key: String) -> Result<Option<String>, String> {
+pub fn get_api_key(app: AppHandle, key: String) -> Result<Option<String>, String> {
     validate_env_key(&key)?;
+    match keyring_entry(&key).and_then(|entry| entry.get_password()) {
+        Ok(value) => return Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => {}
+        Err(err) => eprintln!("[settings] keychain read failed for {}: {}", key, err),
+    }
     let env_path = get_env_file_path(&app)?;
-    let env_vars = load_env_file(&env_path);
-    Ok(env_vars.get(&key).cloned())
+    Ok(load_env_file(&env_path).get(&key).cloned())
 }
 
-/// Set an environment variable in .env file
+/// Store an API key in the OS credential store; an empty value deletes it. Any copy left
+/// in the .env file is removed once the keychain has it.
 #[tauri::command]
-pub fn set_env_var(app: AppHandle, key: String, value: String) -> Result<(), String> {
+pub fn set_api_key(app: AppHandle, key: String, value: String) -> Result<(), String> {
     validate_env_key(&key)?;
-    let env_path = get_env_file_path(&app)?;
-    let mut env_vars = load_env_file(&env_path);
+    let entry = keyring_entry(&key).map_err(|e| e.to_string())?;
     if value.trim().is_empty() {
-        env_vars.remove(&key);
+        match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(err) => return Err(err.to_string()),
+        }
     } else {
-        env_vars.insert(key, value);
+        entry.set_password(&value).map_err(|e| e.to_string())?;
     }
-    save_env_file(&env_path, &env_vars)
+    remove_env_key(&app, &key)?;
+
+    let _ = app.emit("env-var-changed", EnvVarChangedEvent { key });
+    Ok(())
 }
 
-/// Get a setting from localStorage-like storage
+/// Move API keys from the plaintext .env file into the OS credential store. Each key is
+/// read back before it's deleted from disk; keys the keychain won't take stay in .env.
+pub fn migrate_env_keys_to_keychain(app: &AppHandle) {
+    let Ok(env_path) = get_env_file_path(app) else {
+        return;
+    };
+    let env_vars = load_env_file(&env_path);
+    for (key, value) in env_vars.iter().filter(|(key, _)| is_allowed_env_key(key)) {
+        let stored = keyring_entry(key).and_then(|entry| {
+            entry.set_password(value)?;
+            entry.get_password()
+        });
+        match stored {
+            Ok(stored) if stored == *value => {
+                if let Err(err) = remove_env_key(app, key) {
+                    eprintln!("[settings] failed to remove {} from .env: {}", key, err);
+                } else {
+                    eprintln!("[settings] moved {} to the keychain", key);
+                }
+            }
+            Ok(_) => eprintln!(
+                "[settings] keychain returned a different {}; kept .env",
+                key
+            ),
+            Err(err) => eprintln!("[settings] keeping {} in .env: {}", key, err),
+        }
+    }
+}
+
+/// Get an API key (kept for existing callers; see `get_api_key`).
+#[tauri::command]
+pub fn get_env_var(app: AppHandle, key: String) -> Result<Option<String>, String> {
+    get_api_key(app, key)
+}
+
+/// Set an API key (kept for existing callers; see `set_api_key`).
+#[tauri::command]
+pub fn set_env_var(app: AppHandle, key: String, value: String) -> Result<(), String> {
+    set_api_key(app, key, value)
+}
+
+/// Get a setting from localStorage-like storage, or its schema default when unset
 #[tauri::command]
 pub fn get_setting(app: AppHandle, key: String) -> Result<Option<serde_json::Value>, String> {
     let settings_path = get_settings_path(&app)?;
     let settings = load_settings(&settings_path);
-    Ok(settings.get(&key).cloned())
+    Ok(settings
+        .get(&key)
+        .filter(|value| !value.is_null())
+        .cloned()
+        .or_else(|| super::settings_schema::default_value(&key)))
 }
 
-/// Set a setting in localStorage-like storage
+/// Set a setting in localStorage-like storage, rejecting values the schema doesn't allow
 #[tauri::command]
 pub fn set_setting(app: AppHandle, key: String, value: serde_json::Value) -> Result<(), String> {
+    super::settings_schema::validate_setting(&key, &value)?;
     let settings_path = get_settings_path(&app)?;
     let mut settings = load_settings(&settings_path);
-    settings.insert(key, value);
-    save_settings(&settings_path, &settings)
+    let moves_overlay = matches!(
+        key.as_str(),
+        "overlayPosition" | "overlayOffsetX" | "overlayOffsetY"
+    );
+    settings.insert(key.clone(), value.clone());
+    save_settings(&settings_path, &settings)?;
+
+    if moves_overlay {
+        crate::overlay::overlay_position_changed(&app);
+    }
+    // Every window keeps its own copy of the settings it shows.
+    let _ = app.emit("setting-changed", SettingChangedEvent { key, value });
+    Ok(())
 }
 
 /// Get all settings
@@ -109,6 +212,62 @@ pub fn get_all_settings(app: AppHandle) -> Result<HashMap<String, serde_json::Va
     Ok(load_settings(&settings_path))
 }
 
+/// Exported settings. `env_keys` names the API keys that are set; their values are never
+/// exported.
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsExport {
+    settings: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    env_keys: Vec<String>,
+}
+
+/// Write all settings, plus the names of configured API keys, to `dest_path` as JSON.
+#[tauri::command]
+pub async fn export_settings(app: AppHandle, dest_path: String) -> Result<(), String> {
+    let settings = load_settings(&get_settings_path(&app)?);
+    let env_keys = ENV_KEYS
+        .iter()
+        .filter(|key| {
+            get_api_key(app.clone(), key.to_string())
+                .ok()
+                .flatten()
+                .is_some_and(|value| !value.trim().is_empty())
+        })
+        .map(|key| key.to_string())
+        .collect();
+    let export = SettingsExport { settings, env_keys };
+    let content = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    fs::write(&dest_path, content).map_err(|e| format!("Failed to write {}: {}", dest_path, e))
+}
+
+/// Apply the settings from a file written by `export_settings` and return the keys that
+/// were updated. Values the schema rejects are skipped; API keys have to be re-entered.
+#[tauri::command]
+pub async fn import_settings(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let export: SettingsExport =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid settings file: {}", e))?;
+
+    let mut updated = Vec::new();
+    for (key, value) in export.settings {
+        match set_setting(app.clone(), key.clone(), value) {
+            Ok(()) => updated.push(key),
+            Err(err) => eprintln!("[settings] skipping imported {}: {}", key, err),
+        }
+    }
+    updated.sort();
+    if !export.env_keys.is_empty() {
+        eprintln!(
+            "[settings] imported settings reference API keys that must be re-entered: {}",
+            export.env_keys.join(", ")
+        );
+    }
+
+    let _ = app.emit("settings-imported", updated.clone());
+    Ok(updated)
+}
+
 fn get_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
     Ok(app_data_dir.join("settings.json"))
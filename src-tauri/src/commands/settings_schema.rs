@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Type, default and meaning of a known setting. Keys missing from the schema are stored
+/// as-is, so the renderer can keep its own settings without registering them here.
+pub struct SettingMeta {
+    pub default_value: Value,
+    pub validator: fn(&Value) -> bool,
+    /// What a valid value looks like, for error messages ("an integer between 0 and 1000").
+    pub expected: &'static str,
+    pub description: &'static str,
+}
+
+pub type SettingsSchema = HashMap<&'static str, SettingMeta>;
+
+/// A schema entry as sent to the renderer.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingDescription {
+    pub key: &'static str,
+    pub default_value: Value,
+    pub expected: &'static str,
+    pub description: &'static str,
+}
+
+fn is_bool(value: &Value) -> bool {
+    value.is_boolean()
+}
+
+fn int_between<const MIN: i64, const MAX: i64>(value: &Value) -> bool {
+    value.as_i64().is_some_and(|n| (MIN..=MAX).contains(&n))
+}
+
+fn number_between<const MIN: i64, const MAX: i64>(value: &Value) -> bool {
+    value
+        .as_f64()
+        .is_some_and(|n| n >= MIN as f64 && n <= MAX as f64)
+}
+
+fn is_one_of(value: &Value, options: &[&str]) -> bool {
+    value.as_str().is_some_and(|s| options.contains(&s))
+}
+
+fn is_activation_mode(value: &Value) -> bool {
+    is_one_of(value, &["tap", "push"])
+}
+
+fn is_paste_mode(value: &Value) -> bool {
+    is_one_of(value, &["paste", "copy_only", "copy", "type"])
+}
+
+fn is_insert_mode(value: &Value) -> bool {
+    is_one_of(value, &["paste", "accessibility"])
+}
+
+fn is_press_during_processing(value: &Value) -> bool {
+    is_one_of(value, &["ignore", "queue", "cancel"])
+}
+
+fn is_recording_format(value: &Value) -> bool {
+    is_one_of(value, &["wav", "m4a"])
+}
+
+fn is_number_format(value: &Value) -> bool {
+    is_one_of(value, &["words", "digits", "auto"])
+}
+
+fn is_auto_capitalize(value: &Value) -> bool {
+    value.is_boolean() || is_one_of(value, &["none", "first-word", "sentences"])
+}
+
+fn is_overlay_position(value: &Value) -> bool {
+    is_one_of(
+        value,
+        &[
+            "bottom-center",
+            "bottom-left",
+            "bottom-right",
+            "top-center",
+            "top-left",
+            "top-right",
+            "custom",
+        ],
+    )
+}
+
+fn meta(
+    default_value: Value,
+    validator: fn(&Value) -> bool,
+    expected: &'static str,
+    description: &'static str,
+) -> SettingMeta {
+    SettingMeta {
+        default_value,
+        validator,
+        expected,
+        description,
+    }
+}
+
+/// Defaults here must match the fallbacks where each setting is read, since `get_setting`
+/// returns them for unset keys.
+fn build_schema() -> SettingsSchema {
+    const BOOL: &str = "true or false";
+    HashMap::from([
+        (
+            "activationMode",
+            meta(
+                json!("tap"),
+                is_activation_mode,
+                "\"tap\" or \"push\"",
+                "Tap the hotkey to start and stop, or hold it to record (push-to-talk).",
+            ),
+        ),
+        (
+            "autoCapitalize",
+            meta(
+                json!(true),
+                is_auto_capitalize,
+                "a boolean or \"none\", \"first-word\" or \"sentences\"",
+                "Capitalize the start of the transcription and of each sentence.",
+            ),
+        ),
+        (
+            "autoHideAfterPasteMs",
+            meta(
+                json!(0),
+                int_between::<0, 60_000>,
+                "an integer between 0 and 60000",
+                "Hide the main window this long after pasting; 0 keeps it visible.",
+            ),
+        ),
+        (
+            "autoPaste",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Paste each transcription into the focused app.",
+            ),
+        ),
+        (
+            "autoPunctuation",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "End transcriptions with a period when they lack punctuation.",
+            ),
+        ),
+        (
+            "autoTrailingSpace",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Add a space after each transcription.",
+            ),
+        ),
+        (
+            "builtInTextCommands",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Turn spoken \"comma\", \"period\", \"new line\" and \"new paragraph\" into text.",
+            ),
+        ),
+        (
+            "captureSystemAudio",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Mix system audio into recordings (macOS).",
+            ),
+        ),
+        (
+            "clipboardFileDetection",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Classify copied file paths as files in clipboard history.",
+            ),
+        ),
+        (
+            "clipboardHistorySize",
+            meta(
+                json!(50),
+                int_between::<0, 1000>,
+                "an integer between 0 and 1000",
+                "Number of clipboard items kept in history.",
+            ),
+        ),
+        (
+            "continuousDictationMode",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Start recording again after each transcription until the hotkey is pressed.",
+            ),
+        ),
+        (
+            "doubleTapMs",
+            meta(
+                json!(350),
+                int_between::<0, 2000>,
+                "an integer between 0 and 2000",
+                "Push-to-talk: a second tap within this window locks recording on.",
+            ),
+        ),
+        (
+            "doubleTapWindowMs",
+            meta(
+                json!(0),
+                int_between::<0, 2000>,
+                "an integer between 0 and 2000",
+                "Two taps within this window trigger the double-tap action; 0 turns it off.",
+            ),
+        ),
+        (
+            "duckSystemAudioWhileRecording",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Lower system volume instead of muting it while recording.",
+            ),
+        ),
+        (
+            "duckVolumePercent",
+            meta(
+                json!(20),
+                number_between::<0, 100>,
+                "a number between 0 and 100",
+                "Ducked volume as a percentage of the current volume.",
+            ),
+        ),
+        (
+            "hotkeyDebounceMs",
+            meta(
+                json!(30),
+                int_between::<0, 1000>,
+                "an integer between 0 and 1000",
+                "Hotkey presses this soon after the previous one are ignored as key chatter.",
+            ),
+        ),
+        (
+            "insertMode",
+            meta(
+                json!("paste"),
+                is_insert_mode,
+                "\"paste\" or \"accessibility\"",
+                "Insert text by pasting, or through the macOS Accessibility API.",
+            ),
+        ),
+        (
+            "keepRecordings",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Keep audio files of past recordings.",
+            ),
+        ),
+        (
+            "longPressThresholdMs",
+            meta(
+                json!(500),
+                int_between::<0, 5000>,
+                "an integer between 0 and 5000",
+                "Holding the hotkey longer than this records until release.",
+            ),
+        ),
+        (
+            "maxStoredRecordingsMb",
+            meta(
+                json!(500),
+                int_between::<0, 100_000>,
+                "an integer between 0 and 100000",
+                "Oldest kept recordings are deleted beyond this size.",
+            ),
+        ),
+        (
+            "minRecordingSeconds",
+            meta(
+                json!(0.5),
+                number_between::<0, 10>,
+                "a number between 0 and 10",
+                "Shorter recordings are discarded without transcribing.",
+            ),
+        ),
+        (
+            "muteSystemAudioWhileRecording",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Mute system audio while recording.",
+            ),
+        ),
+        (
+            "numberFormat",
+            meta(
+                json!("words"),
+                is_number_format,
+                "\"words\", \"digits\" or \"auto\"",
+                "How spoken numbers are written.",
+            ),
+        ),
+        (
+            "overlayPosition",
+            meta(
+                json!("bottom-center"),
+                is_overlay_position,
+                "a top/bottom-left/center/right position or \"custom\"",
+                "Where the recording overlay appears.",
+            ),
+        ),
+        (
+            "pasteMode",
+            meta(
+                json!("paste"),
+                is_paste_mode,
+                "\"paste\", \"copy_only\" or \"type\"",
+                "Paste transcriptions, or only copy them to the clipboard.",
+            ),
+        ),
+        (
+            "persistClipboardHistory",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Save clipboard history to disk.",
+            ),
+        ),
+        (
+            "pressDuringProcessing",
+            meta(
+                json!("ignore"),
+                is_press_during_processing,
+                "\"ignore\", \"queue\" or \"cancel\"",
+                "What a hotkey press does while a transcription is processing.",
+            ),
+        ),
+        (
+            "processingTimeoutSeconds",
+            meta(
+                Value::Null,
+                int_between::<0, 3600>,
+                "an integer between 0 and 3600",
+                "Give up on a transcription that takes longer than this; 0 waits forever. \
+                 Unset, it allows for every retry, fallback provider and pipeline step.",
+            ),
+        ),
+        (
+            "pushToTalkReleaseCheckMs",
+            meta(
+                json!(2000),
+                int_between::<0, 60_000>,
+                "an integer between 0 and 60000",
+                "How often push-to-talk confirms the hotkey is still held; 0 disables it.",
+            ),
+        ),
+        (
+            "recordingAutoGain",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Normalize recording loudness before transcribing.",
+            ),
+        ),
+        (
+            "recordingFormat",
+            meta(
+                json!("wav"),
+                is_recording_format,
+                "\"wav\" or \"m4a\"",
+                "Audio format for recordings (macOS).",
+            ),
+        ),
+        (
+            "recordingGainDb",
+            meta(
+                json!(0),
+                number_between::<-30, 30>,
+                "a number between -30 and 30",
+                "Fixed gain applied to recordings, in dB.",
+            ),
+        ),
+        (
+            "recordingMetering",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Show the input level while recording.",
+            ),
+        ),
+        (
+            "restoreClipboard",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Put the previous clipboard contents back after pasting.",
+            ),
+        ),
+        (
+            "skipForCodeLike",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Skip smart formatting for text that looks like code.",
+            ),
+        ),
+        (
+            "soundFeedback",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Play sounds when recording starts, stops and finishes.",
+            ),
+        ),
+        (
+            "soundFeedbackVolume",
+            meta(
+                json!(0.5),
+                number_between::<0, 1>,
+                "a number between 0 and 1",
+                "Volume of feedback sounds.",
+            ),
+        ),
+        (
+            "streamTranscription",
+            meta(
+                json!(false),
+                is_bool,
+                BOOL,
+                "Show partial transcripts while they arrive.",
+            ),
+        ),
+        (
+            "transcriptionMaxRetries",
+            meta(
+                json!(3),
+                int_between::<0, 10>,
+                "an integer between 0 and 10",
+                "Retries for failed transcription requests.",
+            ),
+        ),
+        (
+            "transcriptionTimeoutSeconds",
+            meta(
+                json!(60),
+                int_between::<1, 600>,
+                "an integer between 1 and 600",
+                "Timeout for a single transcription request.",
+            ),
+        ),
+        (
+            "useReasoningModel",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Clean up transcriptions with the reasoning model.",
+            ),
+        ),
+        (
+            "windowSnapping",
+            meta(
+                json!(true),
+                is_bool,
+                BOOL,
+                "Snap the dictation panel to screen edges when dragged.",
+            ),
+        ),
+    ])
+}
+
+pub fn settings_schema() -> &'static SettingsSchema {
+    static SCHEMA: OnceLock<SettingsSchema> = OnceLock::new();
+    SCHEMA.get_or_init(build_schema)
+}
+
+/// Schema default for `key`, if it has one.
+pub fn default_value(key: &str) -> Option<Value> {
+    settings_schema()
+        .get(key)
+        .map(|meta| meta.default_value.clone())
+}
+
+/// Check `value` against the schema. `null` (clearing the setting) and unknown keys are
+/// always accepted.
+pub fn validate_setting(key: &str, value: &Value) -> Result<(), String> {
+    match settings_schema().get(key) {
+        Some(meta) if !value.is_null() && !(meta.validator)(value) => {
+            Err(format!("{} must be {}", key, meta.expected))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Known settings with their defaults, for rendering a typed settings UI.
+#[tauri::command]
+pub fn get_settings_schema() -> Vec<SettingDescription> {
+    let mut descriptions: Vec<_> = settings_schema()
+        .iter()
+        .map(|(key, meta)| SettingDescription {
+            key,
+            default_value: meta.default_value.clone(),
+            expected: meta.expected,
+            description: meta.description,
+        })
+        .collect();
+    descriptions.sort_by_key(|description| description.key);
+    descriptions
+}
@@ -0,0 +1,199 @@
+use tauri::AppHandle;
+
+const DEFAULT_SOUND_FEEDBACK_VOLUME: f32 = 0.5;
+
+/// Cues played around a dictation when `soundFeedback` is on.
+#[derive(Clone, Copy, Debug)]
+pub enum FeedbackSound {
+    Start,
+    Stop,
+    Success,
+    Error,
+}
+
+impl FeedbackSound {
+    #[cfg(target_os = "macos")]
+    fn system_sound_name(self) -> &'static str {
+        match self {
+            Self::Start => "Tink",
+            Self::Stop => "Pop",
+            Self::Success => "Glass",
+            Self::Error => "Basso",
+        }
+    }
+
+    /// (frequency Hz, duration ms) steps of the synthesized cue.
+    #[cfg(target_os = "windows")]
+    fn tones(self) -> &'static [(f32, u64)] {
+        match self {
+            Self::Start => &[(660.0, 60), (880.0, 80)],
+            Self::Stop => &[(880.0, 60), (660.0, 80)],
+            Self::Success => &[(1046.5, 90)],
+            Self::Error => &[(330.0, 120), (247.0, 160)],
+        }
+    }
+}
+
+fn sound_feedback_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "soundFeedback".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// `soundFeedbackVolume`: 0.0 (silent) to 1.0.
+fn sound_feedback_volume(app: &AppHandle) -> f32 {
+    super::settings::get_setting(app.clone(), "soundFeedbackVolume".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_f64())
+        .map_or(DEFAULT_SOUND_FEEDBACK_VOLUME, |volume| {
+            volume.clamp(0.0, 1.0) as f32
+        })
+}
+
+/// Play `sound` without blocking the caller. Playback failures are only logged.
+pub fn play_feedback(app: &AppHandle, sound: FeedbackSound) {
+    if !sound_feedback_enabled(app) {
+        return;
+    }
+    let volume = sound_feedback_volume(app);
+
+    #[cfg(target_os = "macos")]
+    {
+        // NSSound plays asynchronously; AppKit objects belong on the main thread.
+        let result = app.run_on_main_thread(move || {
+            if let Err(err) = play_system_sound(sound.system_sound_name(), volume) {
+                eprintln!("[sound] failed to play {:?}: {}", sound, err);
+            }
+        });
+        if let Err(err) = result {
+            eprintln!("[sound] run_on_main_thread failed: {}", err);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let spawned = std::thread::Builder::new()
+            .name("typefree-sound-feedback".to_string())
+            .spawn(move || {
+                if let Err(err) = play_tones(sound.tones(), volume) {
+                    eprintln!("[sound] failed to play {:?}: {}", sound, err);
+                }
+            });
+        if let Err(err) = spawned {
+            eprintln!("[sound] failed to spawn playback thread: {}", err);
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (sound, volume);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn play_system_sound(name: &str, volume: f32) -> Result<(), String> {
+    use objc2::exception;
+    use objc2_app_kit::NSSound;
+    use objc2_foundation::NSString;
+    use std::panic::AssertUnwindSafe;
+
+    let played = exception::catch(AssertUnwindSafe(|| {
+        let Some(sound) = NSSound::soundNamed(&NSString::from_str(name)) else {
+            return false;
+        };
+        sound.setVolume(volume);
+        sound.play()
+    }))
+    .map_err(|exc| format!("Objective-C exception: {:?}", exc))?;
+
+    if played {
+        Ok(())
+    } else {
+        Err(format!("system sound '{}' unavailable", name))
+    }
+}
+
+/// Synthesize `tones` on the default output device; blocks until they have played.
+#[cfg(target_os = "windows")]
+fn play_tones(tones: &[(f32, u64)], volume: f32) -> Result<(), String> {
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{SampleFormat, StreamConfig};
+
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "No audio output device".to_string())?;
+    let supported = device
+        .default_output_config()
+        .map_err(|e| format!("Failed to query output format: {e}"))?;
+    let sample_format = supported.sample_format();
+    let config: StreamConfig = supported.into();
+    let channels = usize::from(config.channels.max(1));
+    let samples = synthesize(tones, config.sample_rate.0 as f32, volume);
+    let duration_ms: u64 = tones.iter().map(|(_, ms)| ms).sum();
+
+    let mut position = 0usize;
+    let err_fn = |err: cpal::StreamError| eprintln!("[sound] output stream error: {err}");
+    let stream = match sample_format {
+        SampleFormat::F32 => device.build_output_stream(
+            &config,
+            move |data: &mut [f32], _: &_| {
+                fill_frames(data, channels, &samples, &mut position, |s| s)
+            },
+            err_fn,
+            None,
+        ),
+        SampleFormat::I16 => device.build_output_stream(
+            &config,
+            move |data: &mut [i16], _: &_| {
+                fill_frames(data, channels, &samples, &mut position, |s| {
+                    (s * i16::MAX as f32) as i16
+                })
+            },
+            err_fn,
+            None,
+        ),
+        other => return Err(format!("Unsupported output sample format: {other}")),
+    }
+    .map_err(|e| format!("Failed to open output stream: {e}"))?;
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start output stream: {e}"))?;
+    // Let the device drain its buffer before the stream is dropped.
+    std::thread::sleep(std::time::Duration::from_millis(duration_ms + 80));
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn synthesize(tones: &[(f32, u64)], sample_rate: f32, volume: f32) -> Vec<f32> {
+    // Short linear fades keep the tone edges from clicking.
+    let fade = (sample_rate * 0.008) as usize;
+    let mut samples = Vec::new();
+    for &(frequency, ms) in tones {
+        let len = (sample_rate * ms as f32 / 1000.0) as usize;
+        for i in 0..len {
+            let envelope = (i.min(len - i) as f32 / fade.max(1) as f32).min(1.0);
+            let phase = 2.0 * std::f32::consts::PI * frequency * i as f32 / sample_rate;
+            samples.push(phase.sin() * volume * envelope * 0.6);
+        }
+    }
+    samples
+}
+
+#[cfg(target_os = "windows")]
+fn fill_frames<T: Copy>(
+    data: &mut [T],
+    channels: usize,
+    samples: &[f32],
+    position: &mut usize,
+    convert: impl Fn(f32) -> T,
+) {
+    for frame in data.chunks_mut(channels) {
+        let sample = samples.get(*position).copied().unwrap_or(0.0);
+        *position += 1;
+        frame.fill(convert(sample));
+    }
+}
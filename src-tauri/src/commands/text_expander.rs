@@ -0,0 +1,1183 @@
+use regex::{Captures, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// The last abbreviation alternation and the sorted word list it was built from.
+static ABBREVIATION_REGEX: Mutex<Option<(Vec<String>, Regex)>> = Mutex::new(None);
+
+/// A spoken command and the text it expands to. `pattern` is a regular expression
+/// matched against the transcription; `replacement` may reference its capture groups.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextCommand {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+impl TextCommand {
+    fn new(pattern: &str, replacement: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+}
+
+/// Built-in commands, used when `builtInTextCommands` is on. Each also swallows the
+/// whitespace and stray punctuation the transcriber tends to put around the spoken phrase
+/// ("Hello. New paragraph. Next"). They match the words anywhere, so they are off by
+/// default: "the comma splice" would lose its "comma".
+fn default_text_commands() -> Vec<TextCommand> {
+    vec![
+        TextCommand::new(r"(?i)[ \t]*\bnew paragraph\b[,.!?]?[ \t]*", "\n\n"),
+        TextCommand::new(r"(?i)[ \t]*\bnew line\b[,.!?]?[ \t]*", "\n"),
+        TextCommand::new(r"(?i)[ \t]*\b(?:period|full stop)\b[.]?", "."),
+        TextCommand::new(r"(?i)[ \t]*\bcomma\b[,]?", ","),
+    ]
+}
+
+#[tauri::command]
+pub fn get_default_text_commands() -> Vec<TextCommand> {
+    default_text_commands()
+}
+
+/// User commands from `dictationTextCommands`, followed by the built-in defaults when
+/// `builtInTextCommands` is on, so a user pattern can claim a phrase before a default sees it.
+fn load_text_commands(app: &AppHandle) -> Vec<(Regex, String)> {
+    let user_commands =
+        match super::settings::get_setting(app.clone(), "dictationTextCommands".to_string()) {
+            Ok(Some(value)) => {
+                serde_json::from_value::<Vec<TextCommand>>(value).unwrap_or_else(|err| {
+                    eprintln!("[text-commands] ignoring invalid dictationTextCommands: {err}");
+                    Vec::new()
+                })
+            }
+            _ => Vec::new(),
+        };
+    let built_in = if bool_setting(app, "builtInTextCommands", false) {
+        default_text_commands()
+    } else {
+        Vec::new()
+    };
+
+    user_commands
+        .into_iter()
+        .filter(|command| !command.pattern.trim().is_empty())
+        .chain(built_in)
+        .filter_map(|command| match Regex::new(&command.pattern) {
+            Ok(regex) => Some((regex, command.replacement)),
+            Err(err) => {
+                eprintln!(
+                    "[text-commands] skipping invalid pattern '{}': {}",
+                    command.pattern, err
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+/// Apply each command in order to `text`.
+pub fn expand_commands(text: &str, commands: &[(Regex, &str)]) -> String {
+    let mut result = text.to_string();
+    for (regex, replacement) in commands {
+        result = regex.replace_all(&result, *replacement).into_owned();
+    }
+    result
+}
+
+/// An editing command spoken at the end of a dictation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceCommand {
+    /// Press Return once after inserting the text.
+    NewLine,
+    /// Press Return twice after inserting the text.
+    NewParagraph,
+    /// Delete the previous dictation before inserting this one.
+    ScratchThat,
+}
+
+impl VoiceCommand {
+    fn parse(action: &str) -> Option<Self> {
+        match action.trim().to_ascii_lowercase().as_str() {
+            "new-line" | "newline" => Some(Self::NewLine),
+            "new-paragraph" | "paragraph" => Some(Self::NewParagraph),
+            "scratch-that" | "delete-last" => Some(Self::ScratchThat),
+            _ => None,
+        }
+    }
+}
+
+fn default_voice_commands() -> Vec<(String, VoiceCommand)> {
+    vec![
+        ("new paragraph".to_string(), VoiceCommand::NewParagraph),
+        ("new line".to_string(), VoiceCommand::NewLine),
+        ("scratch that".to_string(), VoiceCommand::ScratchThat),
+    ]
+}
+
+/// `voiceCommands` maps a phrase to `new-line`, `new-paragraph` or `scratch-that`. When set
+/// it replaces the defaults, so `{}` turns voice commands off.
+fn load_voice_commands(app: &AppHandle) -> Vec<(String, VoiceCommand)> {
+    let Ok(Some(value)) = super::settings::get_setting(app.clone(), "voiceCommands".to_string())
+    else {
+        return default_voice_commands();
+    };
+    let Some(map) = value.as_object() else {
+        eprintln!("[voice-commands] ignoring invalid voiceCommands: expected an object");
+        return default_voice_commands();
+    };
+
+    map.iter()
+        .filter(|(phrase, _)| !phrase.trim().is_empty())
+        .filter_map(
+            |(phrase, action)| match action.as_str().and_then(VoiceCommand::parse) {
+                Some(command) => Some((phrase.trim().to_string(), command)),
+                None => {
+                    eprintln!(
+                        "[voice-commands] skipping '{}': unknown action {}",
+                        phrase, action
+                    );
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+fn is_trailing_punctuation(ch: char) -> bool {
+    matches!(
+        ch,
+        '.' | ',' | '!' | '?' | ';' | ':' | '…' | '。' | '，' | '！' | '？' | '；' | '：'
+    )
+}
+
+/// Split a voice command off the end of `text`. Case is ignored, as is the punctuation the
+/// transcriber puts around the phrase ("Hello, new line."). The longest matching phrase wins.
+/// Any text ending in the phrase as whole words counts, so phrases should be ones that rarely
+/// end an ordinary sentence.
+pub fn split_voice_command(
+    text: &str,
+    commands: &[(String, VoiceCommand)],
+) -> (String, Option<VoiceCommand>) {
+    let trimmed =
+        text.trim_end_matches(|ch: char| ch.is_whitespace() || is_trailing_punctuation(ch));
+    let mut commands: Vec<_> = commands.iter().collect();
+    commands.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.chars().count()));
+
+    for (phrase, command) in commands {
+        let length = phrase.chars().count();
+        let Some((start, _)) = trimmed.char_indices().rev().nth(length - 1) else {
+            continue;
+        };
+        if trimmed[start..].to_lowercase() != phrase.to_lowercase() {
+            continue;
+        }
+        let before = &trimmed[..start];
+        if before.chars().last().is_some_and(char::is_alphanumeric) {
+            continue;
+        }
+        let remaining = before.trim_end_matches(|ch: char| {
+            ch.is_whitespace() || matches!(ch, ',' | ';' | ':' | '，' | '；' | '：')
+        });
+        return (remaining.to_string(), Some(*command));
+    }
+    (text.to_string(), None)
+}
+
+/// Split the configured voice command, if any, off the end of a finished transcription.
+pub fn take_voice_command(app: &AppHandle, text: &str) -> (String, Option<VoiceCommand>) {
+    split_voice_command(text, &load_voice_commands(app))
+}
+
+/// A find-and-replace rule from `textSubstitutionRules`. `flags` holds single-letter regex
+/// flags: `i` (ignore case), `m` (multi-line), `s` (`.` matches newlines), `x` (verbose) and
+/// `U` (swap greediness).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextSubstitutionRule {
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub flags: String,
+}
+
+fn compile_substitution_rule(rule: &TextSubstitutionRule) -> Result<Regex, String> {
+    let mut builder = RegexBuilder::new(&rule.pattern);
+    for flag in rule.flags.chars().filter(|ch| !ch.is_whitespace()) {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            'U' => builder.swap_greed(true),
+            other => return Err(format!("Unsupported regex flag '{}'", other)),
+        };
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn compile_substitution_rules(
+    rules: &[TextSubstitutionRule],
+) -> Result<Vec<(Regex, String)>, String> {
+    rules
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| !rule.pattern.is_empty())
+        .map(|(index, rule)| {
+            compile_substitution_rule(rule)
+                .map(|regex| (regex, rule.replacement.clone()))
+                .map_err(|err| format!("Rule {} ('{}'): {}", index + 1, rule.pattern, err))
+        })
+        .collect()
+}
+
+/// Rules from `textSubstitutionRules`, compiled once per load. Invalid rules are skipped
+/// so one bad pattern doesn't disable the rest.
+fn load_substitution_rules(app: &AppHandle) -> Vec<(Regex, String)> {
+    let rules = match super::settings::get_setting(app.clone(), "textSubstitutionRules".to_string())
+    {
+        Ok(Some(value)) => serde_json::from_value::<Vec<TextSubstitutionRule>>(value)
+            .unwrap_or_else(|err| {
+                eprintln!("[substitutions] ignoring invalid textSubstitutionRules: {err}");
+                Vec::new()
+            }),
+        _ => Vec::new(),
+    };
+
+    rules
+        .iter()
+        .filter(|rule| !rule.pattern.is_empty())
+        .filter_map(|rule| match compile_substitution_rule(rule) {
+            Ok(regex) => Some((regex, rule.replacement.clone())),
+            Err(err) => {
+                eprintln!(
+                    "[substitutions] skipping invalid rule '{}': {}",
+                    rule.pattern, err
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+fn apply_substitution_rules(text: &str, rules: &[(Regex, String)]) -> String {
+    let mut result = text.to_string();
+    for (regex, replacement) in rules {
+        result = regex
+            .replace_all(&result, replacement.as_str())
+            .into_owned();
+    }
+    result
+}
+
+/// Run unsaved rules against sample text, for the settings preview.
+#[tauri::command]
+pub fn test_text_substitution(
+    text: String,
+    rules: Vec<TextSubstitutionRule>,
+) -> Result<String, String> {
+    let compiled = compile_substitution_rules(&rules)?;
+    Ok(apply_substitution_rules(&text, &compiled))
+}
+
+/// Apply the saved `textSubstitutionRules`, in order, to a finished transcription.
+pub fn apply_text_substitutions(app: &AppHandle, text: &str) -> String {
+    let rules = load_substitution_rules(app);
+    if rules.is_empty() {
+        return text.to_string();
+    }
+    apply_substitution_rules(text, &rules)
+}
+
+/// Expand each abbreviation that appears as a whole word, ignoring case
+/// (`asap` and `ASAP`, but not the `asap` inside `kaasap`).
+pub fn expand_abbreviations(text: &str, abbrevs: &HashMap<String, String>) -> String {
+    let expansions = abbrevs
+        .iter()
+        .map(|(abbrev, expansion)| (abbrev.trim().to_lowercase(), expansion.as_str()))
+        .filter(|(abbrev, _)| !abbrev.is_empty())
+        .collect::<HashMap<_, _>>();
+    if text.is_empty() || expansions.is_empty() {
+        return text.to_string();
+    }
+
+    // Longest first so `ty` can't shadow `tyvm`.
+    let mut words = expansions.keys().cloned().collect::<Vec<_>>();
+    words.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    let Some(regex) = abbreviation_regex(words) else {
+        return text.to_string();
+    };
+
+    regex
+        .replace_all(text, |caps: &Captures| {
+            let matched = &caps[0];
+            expansions
+                .get(&matched.to_lowercase())
+                .map_or_else(|| matched.to_string(), |expansion| expansion.to_string())
+        })
+        .into_owned()
+}
+
+/// Compile (or reuse) one alternation of `words`. They must be sorted longest first so
+/// `ty` can't shadow `tyvm`: the regex takes the first alternative that matches.
+fn abbreviation_regex(words: Vec<String>) -> Option<Regex> {
+    let mut cached = ABBREVIATION_REGEX
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((cached_words, regex)) = cached.as_ref() {
+        if *cached_words == words {
+            return Some(regex.clone());
+        }
+    }
+
+    let pattern = words
+        .iter()
+        .map(|word| format!(r"\b{}\b", regex::escape(word)))
+        .collect::<Vec<_>>()
+        .join("|");
+    match Regex::new(&format!("(?i){pattern}")) {
+        Ok(regex) => {
+            *cached = Some((words, regex.clone()));
+            Some(regex)
+        }
+        Err(err) => {
+            eprintln!("[abbreviations] failed to compile abbreviations: {err}");
+            None
+        }
+    }
+}
+
+fn load_abbreviations(app: &AppHandle) -> HashMap<String, String> {
+    super::settings::get_setting(app.clone(), "abbreviations".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(abbrev, expansion)| Some((abbrev, expansion.as_str()?.to_string())))
+        .collect()
+}
+
+fn save_abbreviations(app: &AppHandle, abbrevs: HashMap<String, String>) -> Result<(), String> {
+    let value = serde_json::to_value(abbrevs).map_err(|e| e.to_string())?;
+    super::settings::set_setting(app.clone(), "abbreviations".to_string(), value)
+}
+
+#[tauri::command]
+pub fn add_abbreviation(app: AppHandle, abbrev: String, expansion: String) -> Result<(), String> {
+    let abbrev = abbrev.trim();
+    if abbrev.is_empty() {
+        return Err("Abbreviation cannot be empty".to_string());
+    }
+    if expansion.trim().is_empty() {
+        return Err("Expansion cannot be empty".to_string());
+    }
+    let mut abbrevs = load_abbreviations(&app);
+    abbrevs.retain(|existing, _| !existing.trim().eq_ignore_ascii_case(abbrev));
+    abbrevs.insert(abbrev.to_string(), expansion);
+    save_abbreviations(&app, abbrevs)
+}
+
+#[tauri::command]
+pub fn remove_abbreviation(app: AppHandle, abbrev: String) -> Result<(), String> {
+    let mut abbrevs = load_abbreviations(&app);
+    let before = abbrevs.len();
+    abbrevs.retain(|existing, _| !existing.trim().eq_ignore_ascii_case(abbrev.trim()));
+    if abbrevs.len() == before {
+        return Ok(());
+    }
+    save_abbreviations(&app, abbrevs)
+}
+
+/// Expand the user's `abbreviations` in a finished transcription.
+pub fn apply_abbreviations(app: &AppHandle, text: &str) -> String {
+    expand_abbreviations(text, &load_abbreviations(app))
+}
+
+/// `numberFormat` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// Convert every spoken number, including ordinals ("twenty-third" -> "23rd").
+    Digits,
+    /// Leave spoken numbers as words.
+    Words,
+    /// Convert cardinals of ten and above; keep zero to nine and ordinals as words,
+    /// the usual style for prose.
+    Auto,
+}
+
+fn number_format(app: &AppHandle) -> NumberFormat {
+    match get_text_setting(app, "numberFormat").as_deref() {
+        Some("digits") => NumberFormat::Digits,
+        Some("auto") => NumberFormat::Auto,
+        _ => NumberFormat::Words,
+    }
+}
+
+fn get_text_setting(app: &AppHandle, key: &str) -> Option<String> {
+    super::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().map(|s| s.trim().to_ascii_lowercase()))
+}
+
+const MAX_SPOKEN_NUMBER: u64 = 999_999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberWord {
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Thousand,
+}
+
+fn cardinal_word(word: &str) -> Option<NumberWord> {
+    let word = match word {
+        "zero" => NumberWord::Unit(0),
+        "one" => NumberWord::Unit(1),
+        "two" => NumberWord::Unit(2),
+        "three" => NumberWord::Unit(3),
+        "four" => NumberWord::Unit(4),
+        "five" => NumberWord::Unit(5),
+        "six" => NumberWord::Unit(6),
+        "seven" => NumberWord::Unit(7),
+        "eight" => NumberWord::Unit(8),
+        "nine" => NumberWord::Unit(9),
+        "ten" => NumberWord::Teen(10),
+        "eleven" => NumberWord::Teen(11),
+        "twelve" => NumberWord::Teen(12),
+        "thirteen" => NumberWord::Teen(13),
+        "fourteen" => NumberWord::Teen(14),
+        "fifteen" => NumberWord::Teen(15),
+        "sixteen" => NumberWord::Teen(16),
+        "seventeen" => NumberWord::Teen(17),
+        "eighteen" => NumberWord::Teen(18),
+        "nineteen" => NumberWord::Teen(19),
+        "twenty" => NumberWord::Tens(20),
+        "thirty" => NumberWord::Tens(30),
+        "forty" => NumberWord::Tens(40),
+        "fifty" => NumberWord::Tens(50),
+        "sixty" => NumberWord::Tens(60),
+        "seventy" => NumberWord::Tens(70),
+        "eighty" => NumberWord::Tens(80),
+        "ninety" => NumberWord::Tens(90),
+        "hundred" => NumberWord::Hundred,
+        "thousand" => NumberWord::Thousand,
+        _ => return None,
+    };
+    Some(word)
+}
+
+fn ordinal_word(word: &str) -> Option<NumberWord> {
+    let word = match word {
+        "first" => NumberWord::Unit(1),
+        "second" => NumberWord::Unit(2),
+        "third" => NumberWord::Unit(3),
+        "fourth" => NumberWord::Unit(4),
+        "fifth" => NumberWord::Unit(5),
+        "sixth" => NumberWord::Unit(6),
+        "seventh" => NumberWord::Unit(7),
+        "eighth" => NumberWord::Unit(8),
+        "ninth" => NumberWord::Unit(9),
+        "tenth" => NumberWord::Teen(10),
+        "eleventh" => NumberWord::Teen(11),
+        "twelfth" => NumberWord::Teen(12),
+        "thirteenth" => NumberWord::Teen(13),
+        "fourteenth" => NumberWord::Teen(14),
+        "fifteenth" => NumberWord::Teen(15),
+        "sixteenth" => NumberWord::Teen(16),
+        "seventeenth" => NumberWord::Teen(17),
+        "eighteenth" => NumberWord::Teen(18),
+        "nineteenth" => NumberWord::Teen(19),
+        "twentieth" => NumberWord::Tens(20),
+        "thirtieth" => NumberWord::Tens(30),
+        "fortieth" => NumberWord::Tens(40),
+        "fiftieth" => NumberWord::Tens(50),
+        "sixtieth" => NumberWord::Tens(60),
+        "seventieth" => NumberWord::Tens(70),
+        "eightieth" => NumberWord::Tens(80),
+        "ninetieth" => NumberWord::Tens(90),
+        "hundredth" => NumberWord::Hundred,
+        "thousandth" => NumberWord::Thousand,
+        _ => return None,
+    };
+    Some(word)
+}
+
+/// Running value of a spoken number while its words are read left to right.
+#[derive(Debug, Default)]
+struct SpokenNumber {
+    /// Completed thousands, already multiplied out.
+    thousands: u64,
+    /// Value below the thousands.
+    group: u64,
+    last: Option<NumberWord>,
+    group_has_hundred: bool,
+}
+
+impl SpokenNumber {
+    /// Add the next word, or return false when it can't continue this number
+    /// ("twenty thirty", "one two").
+    fn push(&mut self, word: NumberWord) -> bool {
+        let after_multiplier = matches!(
+            self.last,
+            None | Some(NumberWord::Hundred | NumberWord::Thousand)
+        );
+        match word {
+            NumberWord::Unit(value) => {
+                if !after_multiplier && !matches!(self.last, Some(NumberWord::Tens(_))) {
+                    return false;
+                }
+                // "zero" only stands on its own.
+                if value == 0 && self.last.is_some() {
+                    return false;
+                }
+                self.group += value;
+            }
+            NumberWord::Teen(value) | NumberWord::Tens(value) => {
+                if !after_multiplier {
+                    return false;
+                }
+                self.group += value;
+            }
+            NumberWord::Hundred => {
+                // "three hundred", "nineteen hundred", but not "two thousand twelve hundred".
+                let limit = if self.thousands > 0 { 10 } else { 100 };
+                if !matches!(self.last, Some(NumberWord::Unit(_) | NumberWord::Teen(_)))
+                    || self.group_has_hundred
+                    || self.group == 0
+                    || self.group >= limit
+                {
+                    return false;
+                }
+                self.group *= 100;
+                self.group_has_hundred = true;
+            }
+            NumberWord::Thousand => {
+                if self.last.is_none()
+                    || self.thousands > 0
+                    || self.group == 0
+                    || self.group * 1000 > MAX_SPOKEN_NUMBER
+                {
+                    return false;
+                }
+                self.thousands = self.group * 1000;
+                self.group = 0;
+                self.group_has_hundred = false;
+            }
+        }
+        self.last = Some(word);
+        true
+    }
+
+    fn value(&self) -> u64 {
+        self.thousands + self.group
+    }
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Rewrite spoken English numbers up to 999,999 as digits, ignoring case:
+/// "forty two" -> "42", "three hundred and five" -> "305", "twenty-third" -> "23rd",
+/// "a thousand" -> "1000".
+/// Text that is already numeric is left untouched. A lone "second" is kept as a word
+/// since it's far more often the unit of time.
+pub fn convert_numbers(text: &str) -> String {
+    convert_spoken_numbers(text, NumberFormat::Digits)
+}
+
+fn convert_spoken_numbers(text: &str, format: NumberFormat) -> String {
+    if format == NumberFormat::Words {
+        return text.to_string();
+    }
+    let Ok(word_regex) = Regex::new(r"[A-Za-z]+") else {
+        return text.to_string();
+    };
+    let words = word_regex
+        .find_iter(text)
+        .map(|m| (m.start(), m.end(), m.as_str().to_ascii_lowercase()))
+        .collect::<Vec<_>>();
+    // Words of one number are separated by whitespace or a single hyphen.
+    let joined = |left: usize, right: usize| {
+        let gap = &text[words[left].1..words[right].0];
+        gap == "-" || (!gap.is_empty() && gap.chars().all(char::is_whitespace))
+    };
+
+    let mut result = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    let mut index = 0;
+    while index < words.len() {
+        let mut number = SpokenNumber::default();
+        let mut end = index;
+        let mut ordinal = false;
+        while end < words.len() && (end == index || joined(end - 1, end)) {
+            let word = words[end].2.as_str();
+            if let Some(ordinal_value) = ordinal_word(word) {
+                let standalone_second = word == "second" && end == index;
+                if !standalone_second && number.push(ordinal_value) {
+                    ordinal = true;
+                    end += 1;
+                }
+                break;
+            }
+            if word == "a" && end == index {
+                // "a hundred", "a thousand".
+                let counts = end + 1 < words.len()
+                    && joined(end, end + 1)
+                    && matches!(
+                        cardinal_word(&words[end + 1].2),
+                        Some(NumberWord::Hundred | NumberWord::Thousand)
+                    );
+                if !counts || !number.push(NumberWord::Unit(1)) {
+                    break;
+                }
+                end += 1;
+                continue;
+            }
+            if word == "and" {
+                // Only "hundred and five" / "thousand and twelve".
+                let continues = matches!(
+                    number.last,
+                    Some(NumberWord::Hundred | NumberWord::Thousand)
+                ) && end + 1 < words.len()
+                    && joined(end, end + 1)
+                    && matches!(
+                        cardinal_word(&words[end + 1].2)
+                            .or_else(|| ordinal_word(&words[end + 1].2)),
+                        Some(NumberWord::Unit(1..) | NumberWord::Teen(_) | NumberWord::Tens(_))
+                    );
+                if !continues {
+                    break;
+                }
+                end += 1;
+                continue;
+            }
+            match cardinal_word(word) {
+                Some(value) if number.push(value) => end += 1,
+                _ => break,
+            }
+        }
+
+        let value = number.value();
+        let convert = end > index
+            && match format {
+                NumberFormat::Digits => true,
+                NumberFormat::Auto => !ordinal && value >= 10,
+                NumberFormat::Words => false,
+            };
+        if !convert {
+            index = end.max(index + 1);
+            continue;
+        }
+
+        result.push_str(&text[copied_to..words[index].0]);
+        result.push_str(&value.to_string());
+        if ordinal {
+            result.push_str(ordinal_suffix(value));
+        }
+        copied_to = words[end - 1].1;
+        index = end;
+    }
+    result.push_str(&text[copied_to..]);
+    result
+}
+
+/// Apply the `numberFormat` setting (default `words`) to a finished transcription.
+pub fn apply_number_format(app: &AppHandle, text: &str) -> String {
+    match number_format(app) {
+        NumberFormat::Digits => convert_numbers(text),
+        format => convert_spoken_numbers(text, format),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoCapitalizeMode {
+    None,
+    /// Capitalize only the first letter of the whole text.
+    FirstWord,
+    /// Capitalize the first letter of the text and of every sentence after it.
+    Sentences,
+}
+
+/// `autoCapitalize`: `true` (default) means `sentences`; `false` means `none`.
+fn auto_capitalize_mode(app: &AppHandle) -> AutoCapitalizeMode {
+    let value = super::settings::get_setting(app.clone(), "autoCapitalize".to_string())
+        .ok()
+        .flatten();
+    match value {
+        Some(serde_json::Value::Bool(false)) => AutoCapitalizeMode::None,
+        Some(serde_json::Value::String(mode)) => match mode.trim() {
+            "none" => AutoCapitalizeMode::None,
+            "first-word" => AutoCapitalizeMode::FirstWord,
+            _ => AutoCapitalizeMode::Sentences,
+        },
+        _ => AutoCapitalizeMode::Sentences,
+    }
+}
+
+/// Short words that are written with a trailing `.` without ending the sentence.
+const DOTTED_ABBREVIATIONS: &[&str] = &["approx", "cf", "dr", "mr", "mrs", "ms", "vs"];
+
+/// Whether `token`, the word right before a `.`, is an abbreviation rather than the end
+/// of a sentence: a listed word, or short dotted letters like `e.g`, `i.e` or `U.S`.
+fn is_abbreviation(token: &str) -> bool {
+    let token = token.trim_start_matches(|ch: char| !ch.is_alphanumeric());
+    if DOTTED_ABBREVIATIONS.contains(&token.to_lowercase().as_str()) {
+        return true;
+    }
+    token.contains('.')
+        && token
+            .split('.')
+            .all(|part| (1..=2).contains(&part.len()) && part.chars().all(char::is_alphabetic))
+}
+
+/// Uppercase the first letter of the text, and in `Sentences` mode every letter that
+/// starts a sentence: after `.`, `!` or `?` followed by whitespace (so `3.5` and
+/// `example.com` are left alone) unless the `.` ends an abbreviation like `e.g.` or
+/// `vs.`, or after a newline.
+pub fn auto_capitalize(text: &str, mode: AutoCapitalizeMode) -> String {
+    if mode == AutoCapitalizeMode::None {
+        return text.to_string();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut capitalize_next = true;
+    let mut after_terminator = false;
+    // Byte index where the current whitespace-separated token starts.
+    let mut token_start = 0;
+    for (index, ch) in text.char_indices() {
+        if after_terminator {
+            after_terminator = false;
+            capitalize_next = ch.is_whitespace();
+        }
+        if ch.is_whitespace() {
+            token_start = index + ch.len_utf8();
+        }
+
+        if capitalize_next && ch.is_alphanumeric() {
+            capitalize_next = false;
+            result.extend(ch.to_uppercase());
+            if mode == AutoCapitalizeMode::FirstWord {
+                result.push_str(&text[index + ch.len_utf8()..]);
+                return result;
+            }
+            continue;
+        }
+
+        if mode == AutoCapitalizeMode::Sentences {
+            match ch {
+                '.' => after_terminator = !is_abbreviation(&text[token_start..index]),
+                '!' | '?' => after_terminator = true,
+                '\n' => capitalize_next = true,
+                _ => {}
+            }
+        }
+        result.push(ch);
+    }
+    result
+}
+
+fn bool_setting(app: &AppHandle, key: &str, default: bool) -> bool {
+    super::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(default)
+}
+
+/// Braces, semicolons and backticks rarely show up in prose but are everywhere in code.
+fn looks_like_code(text: &str) -> bool {
+    text.contains(['{', ';', '`'])
+}
+
+fn is_cjk(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+    )
+}
+
+/// Words in `text`, for dictation stats. Each CJK character counts as a word since those
+/// scripts don't separate words; elsewhere a word is a run starting with a letter or digit.
+pub fn count_words(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+    for ch in text.chars() {
+        if is_cjk(ch) {
+            count += 1;
+            in_word = false;
+        } else if ch.is_whitespace() {
+            in_word = false;
+        } else if !in_word && ch.is_alphanumeric() {
+            count += 1;
+            in_word = true;
+        }
+    }
+    count
+}
+
+/// Final cleanup before the text is inserted: trim the provider's whitespace, apply
+/// `autoCapitalize`, add a terminal period when `autoPunctuation` is on and the text ends
+/// mid-sentence, and append one space when `autoTrailingSpace` is on so the next dictation
+/// doesn't run into this one. With `skipForCodeLike` (default on), code-looking text is
+/// only trimmed.
+pub fn apply_smart_formatting(app: &AppHandle, text: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return String::new();
+    }
+    if bool_setting(app, "skipForCodeLike", true) && looks_like_code(text) {
+        return text.to_string();
+    }
+
+    let mut result = auto_capitalize(text, auto_capitalize_mode(app));
+    if bool_setting(app, "autoPunctuation", false) {
+        if let Some(last) = result.chars().last().filter(|ch| ch.is_alphanumeric()) {
+            result.push(if is_cjk(last) { '。' } else { '.' });
+        }
+    }
+    if bool_setting(app, "autoTrailingSpace", false) {
+        result.push(' ');
+    }
+    result
+}
+
+/// Expand the configured text commands in a finished transcription.
+pub fn apply_text_commands(app: &AppHandle, text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let commands = load_text_commands(app);
+    let borrowed = commands
+        .iter()
+        .map(|(regex, replacement)| (regex.clone(), replacement.as_str()))
+        .collect::<Vec<_>>();
+    expand_commands(text, &borrowed).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_defaults(text: &str) -> String {
+        let commands = default_text_commands()
+            .into_iter()
+            .map(|command| (Regex::new(&command.pattern).unwrap(), command.replacement))
+            .collect::<Vec<_>>();
+        let borrowed = commands
+            .iter()
+            .map(|(regex, replacement)| (regex.clone(), replacement.as_str()))
+            .collect::<Vec<_>>();
+        expand_commands(text, &borrowed)
+    }
+
+    #[test]
+    fn text_commands_merge_with_surrounding_punctuation() {
+        assert_eq!(expand_defaults("Hello comma world"), "Hello, world");
+        assert_eq!(expand_defaults("Hello, comma, world"), "Hello,, world");
+        assert_eq!(expand_defaults("Done period"), "Done.");
+        assert_eq!(expand_defaults("Done full stop."), "Done.");
+        assert_eq!(
+            expand_defaults("Hello. New paragraph. Next"),
+            "Hello.\n\nNext"
+        );
+        assert_eq!(expand_defaults("one new line two"), "one\ntwo");
+    }
+
+    #[test]
+    fn text_commands_ignore_case() {
+        assert_eq!(expand_defaults("Yes COMMA please"), "Yes, please");
+        assert_eq!(expand_defaults("End Of It Period"), "End Of It.");
+        assert_eq!(expand_defaults("a NEW LINE b"), "a\nb");
+    }
+
+    #[test]
+    fn text_commands_leave_other_words_alone() {
+        for text in [
+            "two commas here",
+            "a periodic table",
+            "the newline character",
+            "new lines of code",
+            "renew line items",
+        ] {
+            assert_eq!(expand_defaults(text), text);
+        }
+    }
+
+    #[test]
+    fn user_commands_run_in_order() {
+        let commands = [
+            (Regex::new(r"(?i)\bsmiley\b").unwrap(), ":)"),
+            (Regex::new(r"\)").unwrap(), ")!"),
+        ];
+        assert_eq!(expand_commands("hi Smiley", &commands), "hi :)!");
+        assert_eq!(expand_commands("", &commands), "");
+    }
+
+    #[test]
+    fn spoken_numbers_become_digits() {
+        assert_eq!(convert_numbers("twenty one"), "21");
+        assert_eq!(convert_numbers("twenty-one apples"), "21 apples");
+        assert_eq!(convert_numbers("one hundred and five"), "105");
+        assert_eq!(convert_numbers("a thousand people"), "1000 people");
+        assert_eq!(convert_numbers("a hundred and twelve"), "112");
+        assert_eq!(
+            convert_numbers("two thousand three hundred forty five"),
+            "2345"
+        );
+        assert_eq!(
+            convert_numbers("the twenty-third of May"),
+            "the 23rd of May"
+        );
+        assert_eq!(convert_numbers("Forty Two"), "42");
+    }
+
+    #[test]
+    fn spoken_numbers_split_where_they_cannot_continue() {
+        assert_eq!(convert_numbers("one two three"), "1 2 3");
+        assert_eq!(convert_numbers("twenty thirty"), "20 30");
+        assert_eq!(convert_numbers("wait a second"), "wait a second");
+        assert_eq!(
+            convert_numbers("a few and then some"),
+            "a few and then some"
+        );
+        assert_eq!(convert_numbers("five and six"), "5 and 6");
+        assert_eq!(convert_numbers("already 42"), "already 42");
+    }
+
+    #[test]
+    fn auto_number_format_keeps_small_numbers_and_ordinals() {
+        let auto = |text| convert_spoken_numbers(text, NumberFormat::Auto);
+        assert_eq!(auto("one of them"), "one of them");
+        assert_eq!(auto("the first three"), "the first three");
+        assert_eq!(auto("twenty one of them"), "21 of them");
+        assert_eq!(auto("the twenty-third"), "the twenty-third");
+        assert_eq!(
+            convert_spoken_numbers("twenty one", NumberFormat::Words),
+            "twenty one"
+        );
+    }
+
+    #[test]
+    fn spoken_number_rejects_invalid_sequences() {
+        let mut number = SpokenNumber::default();
+        assert!(number.push(NumberWord::Unit(3)));
+        assert!(number.push(NumberWord::Hundred));
+        assert!(!number.push(NumberWord::Hundred));
+        assert!(number.push(NumberWord::Tens(40)));
+        assert!(!number.push(NumberWord::Teen(12)));
+        assert_eq!(number.value(), 340);
+
+        let mut number = SpokenNumber::default();
+        assert!(!number.push(NumberWord::Thousand));
+        assert!(number.push(NumberWord::Unit(0)));
+        assert!(!number.push(NumberWord::Unit(0)));
+    }
+
+    fn split(text: &str) -> (String, Option<VoiceCommand>) {
+        let mut commands = default_voice_commands();
+        commands.push(("press enter".to_string(), VoiceCommand::NewLine));
+        commands.push(("enter".to_string(), VoiceCommand::NewParagraph));
+        split_voice_command(text, &commands)
+    }
+
+    #[test]
+    fn voice_command_is_split_off_the_end() {
+        assert_eq!(
+            split("Hello world new line"),
+            ("Hello world".to_string(), Some(VoiceCommand::NewLine))
+        );
+        assert_eq!(
+            split("Hello world. New Paragraph."),
+            ("Hello world.".to_string(), Some(VoiceCommand::NewParagraph))
+        );
+        assert_eq!(
+            split("Hello, scratch that!"),
+            ("Hello".to_string(), Some(VoiceCommand::ScratchThat))
+        );
+        assert_eq!(
+            split("你好，new line。"),
+            ("你好".to_string(), Some(VoiceCommand::NewLine))
+        );
+        assert_eq!(
+            split("New line"),
+            (String::new(), Some(VoiceCommand::NewLine))
+        );
+    }
+
+    #[test]
+    fn longest_voice_command_wins() {
+        assert_eq!(
+            split("Send it, press enter."),
+            ("Send it".to_string(), Some(VoiceCommand::NewLine))
+        );
+        assert_eq!(
+            split("Send it. Enter"),
+            ("Send it.".to_string(), Some(VoiceCommand::NewParagraph))
+        );
+    }
+
+    #[test]
+    fn voice_command_needs_whole_words_at_the_end() {
+        for text in [
+            "Then press enter twice",
+            "The data was entered",
+            "Draw a hairline",
+            "Renew line items",
+            "Start a new lines section",
+            "",
+        ] {
+            assert_eq!(split(text), (text.to_string(), None));
+        }
+    }
+
+    #[test]
+    fn sentence_ending_in_a_command_phrase_is_a_command() {
+        // Matching is purely positional, which is why the defaults avoid everyday endings.
+        assert_eq!(
+            split("To submit the form press enter"),
+            (
+                "To submit the form".to_string(),
+                Some(VoiceCommand::NewLine)
+            )
+        );
+        assert_eq!(
+            split_voice_command("To submit the form press enter", &default_voice_commands()),
+            ("To submit the form press enter".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn voice_commands_can_be_turned_off() {
+        assert_eq!(
+            split_voice_command("Hello new line", &[]),
+            ("Hello new line".to_string(), None)
+        );
+    }
+
+    fn abbreviations(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(abbrev, expansion)| (abbrev.to_string(), expansion.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn abbreviations_expand_whole_words_only() {
+        let abbrevs = abbreviations(&[("asap", "as soon as possible")]);
+        assert_eq!(
+            expand_abbreviations("Reply asap.", &abbrevs),
+            "Reply as soon as possible."
+        );
+        assert_eq!(
+            expand_abbreviations("(asap) asap-ish", &abbrevs),
+            "(as soon as possible) as soon as possible-ish"
+        );
+        assert_eq!(
+            expand_abbreviations("kaasap asaps", &abbrevs),
+            "kaasap asaps"
+        );
+    }
+
+    #[test]
+    fn abbreviations_ignore_case() {
+        let abbrevs = abbreviations(&[(" ASAP ", "as soon as possible")]);
+        assert_eq!(
+            expand_abbreviations("ASAP, Asap or asap", &abbrevs),
+            "as soon as possible, as soon as possible or as soon as possible"
+        );
+    }
+
+    #[test]
+    fn longer_abbreviations_win_over_their_prefixes() {
+        let abbrevs = abbreviations(&[("btw", "by the way"), ("btwn", "between")]);
+        assert_eq!(
+            expand_abbreviations("btw, btwn us", &abbrevs),
+            "by the way, between us"
+        );
+        let abbrevs = abbreviations(&[("ty", "thank you"), ("ty vm", "thank you very much")]);
+        assert_eq!(
+            expand_abbreviations("ty vm and ty", &abbrevs),
+            "thank you very much and thank you"
+        );
+    }
+
+    #[test]
+    fn abbreviation_regex_is_rebuilt_when_words_change() {
+        let first = abbreviations(&[("idk", "I don't know")]);
+        let second = abbreviations(&[("imo", "in my opinion")]);
+        assert_eq!(expand_abbreviations("idk imo", &first), "I don't know imo");
+        assert_eq!(expand_abbreviations("idk imo", &first), "I don't know imo");
+        assert_eq!(
+            expand_abbreviations("idk imo", &second),
+            "idk in my opinion"
+        );
+        assert_eq!(expand_abbreviations("idk", &HashMap::new()), "idk");
+    }
+
+    fn sentences(text: &str) -> String {
+        auto_capitalize(text, AutoCapitalizeMode::Sentences)
+    }
+
+    #[test]
+    fn sentences_start_with_a_capital() {
+        assert_eq!(
+            sentences("hi. how are you? fine! ok"),
+            "Hi. How are you? Fine! Ok"
+        );
+        assert_eq!(sentences("one\ntwo"), "One\nTwo");
+        assert_eq!(sentences("  \"quoted.\" next"), "  \"Quoted.\" next");
+        assert_eq!(
+            auto_capitalize("hi. there", AutoCapitalizeMode::FirstWord),
+            "Hi. there"
+        );
+        assert_eq!(
+            auto_capitalize("hi. there", AutoCapitalizeMode::None),
+            "hi. there"
+        );
+    }
+
+    #[test]
+    fn abbreviations_do_not_end_sentences() {
+        assert_eq!(sentences("fruit, e.g. apples"), "Fruit, e.g. apples");
+        assert_eq!(sentences("that is, i.e. no"), "That is, i.e. no");
+        assert_eq!(
+            sentences("made in the U.S. today"),
+            "Made in the U.S. today"
+        );
+        assert_eq!(sentences("cats vs. dogs"), "Cats vs. dogs");
+        assert_eq!(
+            sentences("ask Dr. smith (cf. page two)"),
+            "Ask Dr. smith (cf. page two)"
+        );
+        assert_eq!(sentences("it costs approx. ten"), "It costs approx. ten");
+        assert_eq!(sentences("he said so. then left"), "He said so. Then left");
+    }
+
+    #[test]
+    fn dots_inside_numbers_and_urls_do_not_end_sentences() {
+        assert_eq!(sentences("v1.2 is out"), "V1.2 is out");
+        assert_eq!(sentences("it took 3.5 hours"), "It took 3.5 hours");
+        assert_eq!(
+            sentences("see example.com/docs.html for more"),
+            "See example.com/docs.html for more"
+        );
+        assert_eq!(
+            sentences("go to example.com. then log in"),
+            "Go to example.com. Then log in"
+        );
+    }
+}
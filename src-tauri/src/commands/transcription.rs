@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::{mpsc, Mutex};
 use tokio::task::JoinHandle;
 use tokio::time::{sleep, timeout, Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 #[cfg(target_os = "macos")]
 use std::path::PathBuf;
@@ -17,6 +18,61 @@ use flate2::write::GzEncoder;
 use flate2::Compression;
 use std::io::{Read as IoRead, Write as IoWrite};
 
+use super::dictation_session::{self, ProgressStage, UploadDetail};
+
+const DEFAULT_TRANSCRIPTION_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_TRANSCRIPTION_MAX_RETRIES: u32 = 3;
+const TRANSCRIPTION_RETRY_BASE_DELAY_MS: u64 = 500;
+
+// Upload bodies are fed to reqwest in chunks of this size so progress can be reported.
+const UPLOAD_CHUNK_BYTES: usize = 64 * 1024;
+
+const OPENAI_DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const GROQ_DEFAULT_BASE_URL: &str = "https://api.groq.com/openai/v1";
+const ZAI_DEFAULT_BASE_URL: &str = "https://api.z.ai/api/paas/v4";
+const ASSEMBLYAI_BASE_URL: &str = "https://api.assemblyai.com";
+const VOLCENGINE_BASE_URL: &str = "https://openspeech.bytedance.com";
+
+const CONNECTIVITY_TIMEOUT: Duration = Duration::from_secs(2);
+// How long a probe result is trusted by the check before each transcription. Offline
+// results expire sooner so dictation works again soon after the network comes back.
+const CONNECTIVITY_ONLINE_TTL: Duration = Duration::from_secs(30);
+const CONNECTIVITY_OFFLINE_TTL: Duration = Duration::from_secs(5);
+pub const NO_CONNECTION_ERROR: &str =
+    "No internet connection detected. Check your network and try again.";
+pub const TRANSCRIPTION_CANCELLED: &str = "Transcription cancelled";
+
+/// Parent of the tokens of user-facing transcriptions (dictation and the renderer);
+/// cancelling it cancels all of them. Taken on cancel, so later transcriptions start with a
+/// fresh one. Background work such as the offline queue passes its own token instead.
+#[derive(Default)]
+pub struct CancellationState(std::sync::Mutex<Option<CancellationToken>>);
+
+fn ensure_cancellation_state(app: &AppHandle) {
+    if app.try_state::<CancellationState>().is_none() {
+        app.manage(CancellationState::default());
+    }
+}
+
+/// Last connectivity result per provider, with the time it was taken.
+#[derive(Default)]
+pub struct ConnectivityState(std::sync::Mutex<HashMap<String, (Instant, bool)>>);
+
+fn ensure_connectivity_state(app: &AppHandle) {
+    if app.try_state::<ConnectivityState>().is_none() {
+        app.manage(ConnectivityState::default());
+    }
+}
+
+fn cancellation_token(app: &AppHandle) -> CancellationToken {
+    ensure_cancellation_state(app);
+    let state = app.state::<CancellationState>();
+    let mut parent = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    parent
+        .get_or_insert_with(CancellationToken::new)
+        .child_token()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TranscriptionProvider {
     pub id: String,
@@ -352,6 +408,113 @@ pub async fn cancel_openai_realtime_transcription(session_id: String) -> Result<
     Ok(())
 }
 
+/// True when `url` answers a HEAD request within the connectivity timeout, whatever the
+/// status. Goes through the configured proxy like every other request.
+async fn is_reachable(app: &AppHandle, url: &str) -> bool {
+    let Ok(client) = super::network::http_client_builder(app).and_then(|builder| {
+        builder
+            .timeout(CONNECTIVITY_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())
+    }) else {
+        return false;
+    };
+    client.head(url).send().await.is_ok()
+}
+
+/// Whether `provider` can be reached from here. Probes the provider's own host, so networks
+/// that block other sites but allow the provider still count as online. Local endpoints and
+/// unknown providers always do.
+pub async fn check_network_connectivity(app: &AppHandle, provider: &str) -> bool {
+    let online = match provider_ping_url(app, provider) {
+        Some(url) if !is_local_url(&url) => is_reachable(app, &url).await,
+        _ => true,
+    };
+    remember_connectivity(app, provider, online);
+    online
+}
+
+fn remember_connectivity(app: &AppHandle, provider: &str, online: bool) {
+    ensure_connectivity_state(app);
+    let state = app.state::<ConnectivityState>();
+    let mut entries = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    entries.insert(provider.to_string(), (Instant::now(), online));
+}
+
+/// `check_network_connectivity`, reusing a recent result instead of probing again.
+async fn cached_network_connectivity(app: &AppHandle, provider: &str) -> bool {
+    ensure_connectivity_state(app);
+    let cached = {
+        let state = app.state::<ConnectivityState>();
+        let entries = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(provider).copied()
+    };
+    let ttl = |online| {
+        if online {
+            CONNECTIVITY_ONLINE_TTL
+        } else {
+            CONNECTIVITY_OFFLINE_TTL
+        }
+    };
+    match cached {
+        Some((checked_at, online)) if checked_at.elapsed() < ttl(online) => online,
+        _ => check_network_connectivity(app, provider).await,
+    }
+}
+
+/// Self-hosted endpoints on this machine or the LAN work without internet access.
+fn is_local_url(url: &str) -> bool {
+    let Some(host) = reqwest::Url::parse(url).ok().and_then(|url| {
+        url.host_str()
+            .map(|host| host.trim_matches(['[', ']']).to_string())
+    }) else {
+        return false;
+    };
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => ip.is_loopback() || ip.is_private(),
+        Ok(std::net::IpAddr::V6(ip)) => ip.is_loopback(),
+        Err(_) => host == "localhost" || host.ends_with(".local"),
+    }
+}
+
+/// Base URL pinged for `provider`.
+fn provider_ping_url(app: &AppHandle, provider: &str) -> Option<String> {
+    match provider {
+        "openai" | "groq" | "zai" => Some(provider_base_url(app, provider)),
+        "assemblyai" => Some(ASSEMBLYAI_BASE_URL.to_string()),
+        "volcengine" => Some(VOLCENGINE_BASE_URL.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether the provider's API host answers at all (no API key needed).
+#[tauri::command]
+pub async fn ping_transcription_provider(app: AppHandle, provider: String) -> Result<bool, String> {
+    let url = provider_ping_url(&app, &provider)
+        .ok_or_else(|| format!("Unsupported transcription provider: {provider}"))?;
+    Ok(is_reachable(&app, &url).await)
+}
+
+/// Abort in-flight transcription requests and the dictation waiting on them.
+#[tauri::command]
+pub fn cancel_transcription(app: AppHandle) -> Result<(), String> {
+    ensure_cancellation_state(&app);
+    let parent = app
+        .state::<CancellationState>()
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take();
+    // Queued before the token fires, so the coordinator sees the cancel before the
+    // processing task can report that it finished.
+    super::dictation::cancel_dictation_processing(&app);
+    if let Some(parent) = parent {
+        parent.cancel();
+    }
+    let _ = app.emit("backend-transcription-cancelled", ());
+    Ok(())
+}
+
 /// Transcribe audio using cloud provider
 #[tauri::command]
 pub async fn transcribe_audio(
@@ -361,6 +524,227 @@ pub async fn transcribe_audio(
     model: Option<String>,
     language: Option<String>,
 ) -> Result<String, String> {
+    // Taken before any network activity, so a cancel during the request or the
+    // connectivity probe after it is never missed.
+    let token = cancellation_token(&app);
+    transcribe(&app, audio_data, provider, model, language, token)
+        .await
+        .map_err(String::from)
+}
+
+/// `transcribe_audio`, keeping the HTTP status of a failed provider call. Gives up with
+/// `TRANSCRIPTION_CANCELLED` once `token` is cancelled.
+pub async fn transcribe(
+    app: &AppHandle,
+    audio_data: Vec<u8>,
+    provider: String,
+    model: Option<String>,
+    language: Option<String>,
+    token: CancellationToken,
+) -> Result<String, TranscriptionError> {
+    tokio::select! {
+        result = transcribe_online(app, audio_data, provider, model, language) => result,
+        _ = token.cancelled() => {
+            super::logging::component_log(app, "transcription", "info", "cancelled");
+            Err(TRANSCRIPTION_CANCELLED.to_string().into())
+        }
+    }
+}
+
+/// Transcribe, reporting `NO_CONNECTION_ERROR` up front when no provider in the chain was
+/// reachable recently (see `cached_network_connectivity`), or when the request never got an
+/// HTTP response and the provider's host doesn't answer a fresh probe either.
+async fn transcribe_online(
+    app: &AppHandle,
+    audio_data: Vec<u8>,
+    provider: String,
+    model: Option<String>,
+    language: Option<String>,
+) -> Result<String, TranscriptionError> {
+    let chain = std::iter::once(provider.clone())
+        .chain(transcription_fallback_providers(app))
+        .collect::<Vec<_>>();
+    let probes = chain
+        .iter()
+        .map(|candidate| cached_network_connectivity(app, candidate));
+    if !futures_util::future::join_all(probes)
+        .await
+        .into_iter()
+        .any(|online| online)
+    {
+        super::logging::component_log(
+            app,
+            "transcription",
+            "warn",
+            &format!("no connection to provider={}; not sending audio", provider),
+        );
+        return Err(NO_CONNECTION_ERROR.to_string().into());
+    }
+
+    let result =
+        transcribe_with_fallbacks(app, audio_data, provider.clone(), model, language).await;
+    // Some provider in the chain answered, which is all the check above asks; back-to-back
+    // dictations then skip the probe.
+    if result.is_ok() {
+        remember_connectivity(app, &provider, true);
+    }
+    match result {
+        Err(err) if err.status.is_none() && !check_network_connectivity(app, &provider).await => {
+            super::logging::component_log(
+                app,
+                "transcription",
+                "warn",
+                &format!("no connection to provider={} ({})", provider, err),
+            );
+            Err(NO_CONNECTION_ERROR.to_string().into())
+        }
+        result => result,
+    }
+}
+
+/// Transcribe with `provider`, then each `transcriptionFallbackProviders` entry in turn.
+async fn transcribe_with_fallbacks(
+    app: &AppHandle,
+    audio_data: Vec<u8>,
+    provider: String,
+    model: Option<String>,
+    language: Option<String>,
+) -> Result<String, TranscriptionError> {
+    let fallbacks: Vec<String> = transcription_fallback_providers(app)
+        .into_iter()
+        .filter(|candidate| *candidate != provider)
+        .collect();
+
+    if fallbacks.is_empty() {
+        return transcribe_with_provider(app, audio_data, &provider, model, language).await;
+    }
+
+    let mut current = provider;
+    let mut last_error =
+        match transcribe_with_provider(app, audio_data.clone(), &current, model, language.clone())
+            .await
+        {
+            Ok(text) => return Ok(text),
+            Err(err) => err,
+        };
+
+    for next in fallbacks {
+        if !provider_has_api_key(app, &next) {
+            super::logging::component_log(
+                app,
+                "transcription",
+                "warn",
+                &format!("skipping fallback provider={} (no API key)", next),
+            );
+            continue;
+        }
+
+        super::logging::component_log(
+            app,
+            "transcription",
+            "error",
+            &format!(
+                "provider={} failed ({}); falling back to {}",
+                current, last_error, next
+            ),
+        );
+        let _ = app.emit(
+            "backend-transcription-fallback",
+            serde_json::json!({ "from_provider": current, "to_provider": next }),
+        );
+
+        // The requested model belongs to the primary provider; fallbacks use their defaults.
+        match transcribe_with_provider(app, audio_data.clone(), &next, None, language.clone()).await
+        {
+            Ok(text) => return Ok(text),
+            Err(err) => last_error = err,
+        }
+        current = next;
+    }
+
+    Err(last_error)
+}
+
+fn transcription_fallback_providers(app: &AppHandle) -> Vec<String> {
+    super::settings::get_setting(app.clone(), "transcriptionFallbackProviders".to_string())
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_array().cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| v.as_str().map(|s| s.trim().to_string()))
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn provider_has_api_key(app: &AppHandle, provider: &str) -> bool {
+    let key_name = match provider {
+        "volcengine" => "VOLCENGINE_ACCESS_TOKEN",
+        other => match api_key_name_for_provider(other) {
+            Some(key_name) => key_name,
+            None => return false,
+        },
+    };
+    super::settings::get_env_var(app.clone(), key_name.to_string())
+        .ok()
+        .flatten()
+        .is_some_and(|key| !key.trim().is_empty())
+}
+
+/// Audio to upload to a provider. With `progress_to` set, the request body reports
+/// `uploading` progress as reqwest sends it and `waiting-for-provider` after the last chunk.
+#[derive(Clone)]
+struct UploadAudio {
+    data: Vec<u8>,
+    progress_to: Option<AppHandle>,
+}
+
+impl UploadAudio {
+    fn into_body(self) -> reqwest::Body {
+        use futures_util::StreamExt;
+
+        let Some(app) = self.progress_to else {
+            return self.data.into();
+        };
+        let total_bytes = self.data.len() as u64;
+        let chunks: Vec<Vec<u8>> = self
+            .data
+            .chunks(UPLOAD_CHUNK_BYTES)
+            .map(<[u8]>::to_vec)
+            .collect();
+        let mut sent_bytes = 0u64;
+        let stream = futures_util::stream::iter(chunks).map(move |chunk| {
+            sent_bytes += chunk.len() as u64;
+            dictation_session::progress(
+                &app,
+                ProgressStage::Uploading,
+                Some(UploadDetail {
+                    sent_bytes,
+                    total_bytes,
+                }),
+            );
+            if sent_bytes >= total_bytes {
+                dictation_session::progress(&app, ProgressStage::WaitingForProvider, None);
+            }
+            Ok::<_, std::io::Error>(chunk)
+        });
+        reqwest::Body::wrap_stream(stream)
+    }
+
+    fn into_part(self) -> reqwest::multipart::Part {
+        let length = self.data.len() as u64;
+        reqwest::multipart::Part::stream_with_length(self.into_body(), length)
+    }
+}
+
+async fn transcribe_with_provider(
+    app: &AppHandle,
+    audio_data: Vec<u8>,
+    provider: &str,
+    model: Option<String>,
+    language: Option<String>,
+) -> Result<String, TranscriptionError> {
+    let timeout_secs = transcription_timeout_secs(app);
     let transcription_prompt =
         super::settings::get_setting(app.clone(), "transcriptionPrompt".to_string())?
             .and_then(|v| v.as_str().map(|s| s.trim().to_string()))
@@ -380,9 +764,9 @@ pub async fn transcribe_audio(
                 .to_string()
         })?;
         let resource_id = "volc.seedasr.sauc.duration".to_string();
-        let hotwords = super::vocabulary::load_effective_hotwords(&app);
+        let hotwords = super::vocabulary::load_effective_hotwords(app);
 
-        return timeout(Duration::from_secs(60), async move {
+        return timeout(Duration::from_secs(timeout_secs), async move {
             transcribe_volcengine(
                 audio_data,
                 app_id,
@@ -395,35 +779,390 @@ pub async fn transcribe_audio(
             .await
         })
         .await
-        .map_err(|_| "Volcengine transcription timed out after 60 seconds".to_string())?;
+        .map_err(|_| transcription_timeout_error(timeout_secs))?
+        .map_err(TranscriptionError::from);
     }
 
     // Get API key from settings
-    let key_name = match provider.as_str() {
-        "assemblyai" => "ASSEMBLYAI_API_KEY",
-        "openai" => "OPENAI_API_KEY",
-        "groq" => "GROQ_API_KEY",
-        "zai" => "ZAI_API_KEY",
-        _ => return Err(format!("Unknown provider: {}", provider)),
-    };
+    let key_name = api_key_name_for_provider(provider)
+        .ok_or_else(|| format!("Unknown provider: {}", provider))?;
 
     let api_key = super::settings::get_env_var(app.clone(), key_name.to_string())?
         .ok_or_else(|| format!("{} not found. Please set your API key.", key_name))?;
 
-    timeout(Duration::from_secs(60), async move {
-        match provider.as_str() {
+    let client = super::network::http_client_builder(app)?
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+    let upload = UploadAudio {
+        data: audio_data,
+        progress_to: Some(app.clone()),
+    };
+    let max_retries = transcription_max_retries(app);
+    let base_url = provider_base_url(app, provider);
+    // Only Groq streams partial text back; other providers ignore the setting.
+    let partials_to =
+        (provider == "groq" && stream_transcription_enabled(app)).then(|| app.clone());
+
+    // Retries happen inside the overall timeout, so a flapping provider can't stretch a
+    // dictation past the configured limit.
+    timeout(Duration::from_secs(timeout_secs), async move {
+        let base_delay_ms = TRANSCRIPTION_RETRY_BASE_DELAY_MS;
+        match provider {
             "assemblyai" => {
-                transcribe_assemblyai(audio_data, api_key, model, language, transcription_prompt)
-                    .await
+                with_retry(
+                    || {
+                        transcribe_assemblyai(
+                            &client,
+                            upload.clone(),
+                            api_key.clone(),
+                            model.clone(),
+                            language.clone(),
+                            transcription_prompt.clone(),
+                            timeout_secs,
+                        )
+                    },
+                    max_retries,
+                    base_delay_ms,
+                )
+                .await
+            }
+            "openai" => {
+                with_retry(
+                    || {
+                        transcribe_openai(
+                            &client,
+                            &base_url,
+                            upload.clone(),
+                            api_key.clone(),
+                            model.clone(),
+                            language.clone(),
+                            timeout_secs,
+                        )
+                    },
+                    max_retries,
+                    base_delay_ms,
+                )
+                .await
+            }
+            "groq" => {
+                with_retry(
+                    || {
+                        transcribe_groq(
+                            &client,
+                            &base_url,
+                            upload.clone(),
+                            api_key.clone(),
+                            model.clone(),
+                            language.clone(),
+                            timeout_secs,
+                            partials_to.as_ref(),
+                        )
+                    },
+                    max_retries,
+                    base_delay_ms,
+                )
+                .await
             }
-            "openai" => transcribe_openai(audio_data, api_key, model, language).await,
-            "groq" => transcribe_groq(audio_data, api_key, model, language).await,
-            "zai" => transcribe_zai(audio_data, api_key, model, language).await,
-            _ => Err(format!("Unknown provider: {}", provider)),
+            "zai" => {
+                with_retry(
+                    || {
+                        transcribe_zai(
+                            &client,
+                            &base_url,
+                            upload.clone(),
+                            api_key.clone(),
+                            model.clone(),
+                            language.clone(),
+                            timeout_secs,
+                        )
+                    },
+                    max_retries,
+                    base_delay_ms,
+                )
+                .await
+            }
+            _ => Err(format!("Unknown provider: {}", provider).into()),
         }
     })
     .await
-    .map_err(|_| "Transcription timed out after 60 seconds".to_string())?
+    .map_err(|_| transcription_timeout_error(timeout_secs))?
+}
+
+/// Base URL for OpenAI-compatible providers, overridable for self-hosted endpoints
+/// (LiteLLM, LocalAI, ...). Paths like `/audio/transcriptions` are appended to it.
+fn provider_base_url(app: &AppHandle, provider: &str) -> String {
+    let (setting_key, default_url) = match provider {
+        "openai" => ("openaiApiBaseUrl", OPENAI_DEFAULT_BASE_URL),
+        "groq" => ("groqApiBaseUrl", GROQ_DEFAULT_BASE_URL),
+        "zai" => ("zaiApiBaseUrl", ZAI_DEFAULT_BASE_URL),
+        _ => return String::new(),
+    };
+
+    super::settings::get_setting(app.clone(), setting_key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| {
+            v.as_str()
+                .map(|s| s.trim().trim_end_matches('/').to_string())
+        })
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| default_url.to_string())
+}
+
+fn transcription_timeout_secs(app: &AppHandle) -> u64 {
+    super::settings::get_setting(app.clone(), "transcriptionTimeoutSeconds".to_string())
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_u64())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_TRANSCRIPTION_TIMEOUT_SECS)
+}
+
+/// Kept stable so the UI can tell timeouts apart from provider errors.
+fn transcription_timeout_error(timeout_secs: u64) -> String {
+    format!("Transcription timed out after {timeout_secs} seconds")
+}
+
+/// Longest a transcription can legitimately take: every attempt on the provider and on
+/// each fallback running into the request timeout, plus the connectivity probe.
+pub fn worst_case_transcription_time(app: &AppHandle) -> Duration {
+    let attempts = u64::from(transcription_max_retries(app)) + 1;
+    let providers = transcription_fallback_providers(app).len() as u64 + 1;
+    let secs = transcription_timeout_secs(app)
+        .saturating_mul(attempts)
+        .saturating_mul(providers);
+    Duration::from_secs(secs) + CONNECTIVITY_TIMEOUT
+}
+
+fn transcription_max_retries(app: &AppHandle) -> u32 {
+    super::settings::get_setting(app.clone(), "transcriptionMaxRetries".to_string())
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_u64())
+        .map(|retries| retries.min(u32::MAX as u64) as u32)
+        .unwrap_or(DEFAULT_TRANSCRIPTION_MAX_RETRIES)
+}
+
+/// `streamTranscription`: request `stream=true` from providers that support it and show
+/// partial text in the overlay while the transcript arrives.
+fn stream_transcription_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "streamTranscription".to_string())
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// A failed transcription, with the HTTP status when a provider answered with one.
+#[derive(Debug, Clone)]
+pub struct TranscriptionError {
+    pub status: Option<reqwest::StatusCode>,
+    pub message: String,
+}
+
+impl std::fmt::Display for TranscriptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for TranscriptionError {
+    fn from(message: String) -> Self {
+        Self {
+            status: None,
+            message,
+        }
+    }
+}
+
+impl From<TranscriptionError> for String {
+    fn from(error: TranscriptionError) -> Self {
+        error.message
+    }
+}
+
+/// A non-2xx provider response.
+fn api_status_error(label: &str, status: reqwest::StatusCode, body: &str) -> TranscriptionError {
+    TranscriptionError {
+        status: Some(status),
+        message: format!("{label} (HTTP {}): {body}", status.as_u16()),
+    }
+}
+
+/// 429 and 5xx are worth another attempt; everything else (400/401/403, parse errors,
+/// timeouts) fails immediately.
+pub fn is_retryable_error(error: &TranscriptionError) -> bool {
+    error.status.is_some_and(|status| {
+        status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    })
+}
+
+async fn with_retry<F, Fut>(
+    mut f: F,
+    max_retries: u32,
+    base_delay_ms: u64,
+) -> Result<String, TranscriptionError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<String, TranscriptionError>>,
+{
+    let mut attempt = 0u32;
+    loop {
+        match f().await {
+            Ok(text) => return Ok(text),
+            Err(err) if attempt < max_retries && is_retryable_error(&err) => {
+                attempt += 1;
+                let delay_ms = base_delay_ms.saturating_mul(1u64 << (attempt - 1).min(16));
+                super::logging::log(
+                    "transcription",
+                    "error",
+                    &format!(
+                        "retry attempt={}/{} delay_ms={} error={}",
+                        attempt, max_retries, delay_ms, err
+                    ),
+                );
+                sleep(Duration::from_millis(delay_ms)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn send_error(err: reqwest::Error, timeout_secs: u64) -> String {
+    if err.is_timeout() {
+        transcription_timeout_error(timeout_secs)
+    } else {
+        err.to_string()
+    }
+}
+
+fn api_key_name_for_provider(provider: &str) -> Option<&'static str> {
+    match provider {
+        "assemblyai" => Some("ASSEMBLYAI_API_KEY"),
+        "openai" => Some("OPENAI_API_KEY"),
+        "groq" => Some("GROQ_API_KEY"),
+        "zai" => Some("ZAI_API_KEY"),
+        _ => None,
+    }
+}
+
+/// Build a short 16kHz mono PCM16 WAV containing only silence.
+fn silence_wav(duration_ms: u32) -> Vec<u8> {
+    let sample_rate: u32 = 16_000;
+    let data_len = sample_rate / 1000 * duration_ms * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+    wav
+}
+
+/// Send a minimal request to the provider to confirm the stored API key is accepted.
+///
+/// Returns `Ok(true)` on a 2xx response and `Ok(false)` when the provider rejects the
+/// key (401). Any other failure, including network errors, is returned as an error string.
+#[tauri::command]
+pub async fn validate_api_key(app: AppHandle, provider: String) -> Result<bool, String> {
+    let key_name = api_key_name_for_provider(&provider)
+        .ok_or_else(|| format!("API key validation is not supported for provider: {provider}"))?;
+    let api_key = super::settings::get_env_var(app.clone(), key_name.to_string())?
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("{} not found. Please set your API key.", key_name))?;
+
+    let client = super::network::http_client_builder(&app)?
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let base_url = provider_base_url(&app, &provider);
+
+    let (label, request) = match provider.as_str() {
+        "openai" => (
+            "OpenAI",
+            client
+                .get(format!("{base_url}/models"))
+                .header("Authorization", format!("Bearer {}", api_key)),
+        ),
+        "groq" => (
+            "Groq",
+            client
+                .get(format!("{base_url}/models"))
+                .header("Authorization", format!("Bearer {}", api_key)),
+        ),
+        "assemblyai" => (
+            "AssemblyAI",
+            client
+                .get("https://api.assemblyai.com/v2/transcript?limit=1")
+                .header("authorization", api_key),
+        ),
+        "zai" => {
+            let part = reqwest::multipart::Part::bytes(silence_wav(500))
+                .file_name("audio.wav")
+                .mime_str("audio/wav")
+                .map_err(|e| e.to_string())?;
+            let form = reqwest::multipart::Form::new()
+                .part("file", part)
+                .text("model", "glm-asr-2512");
+            (
+                "Z.ai",
+                client
+                    .post(format!("{base_url}/audio/transcriptions"))
+                    .header("Authorization", format!("Bearer {}", api_key))
+                    .multipart(form),
+            )
+        }
+        _ => return Err(format!("Unknown provider: {}", provider)),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {label}: {e}"))?;
+    let status = response.status();
+
+    if status.is_success() {
+        super::logging::component_log(
+            &app,
+            "transcription",
+            "info",
+            &format!("[validate-key] {} key accepted", label),
+        );
+        return Ok(true);
+    }
+
+    let error_text = response.text().await.unwrap_or_default();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        super::logging::component_log(
+            &app,
+            "transcription",
+            "info",
+            &format!(
+                "[validate-key] {} rejected the API key (401): {}",
+                label,
+                error_text.chars().take(300).collect::<String>()
+            ),
+        );
+        return Ok(false);
+    }
+
+    Err(format!(
+        "{label} key validation failed: {} {}",
+        status.as_u16(),
+        error_text.chars().take(300).collect::<String>()
+    ))
 }
 
 #[derive(Deserialize)]
@@ -468,16 +1207,17 @@ fn build_assemblyai_speech_models(model: &str) -> Vec<String> {
 }
 
 async fn transcribe_assemblyai(
-    audio_data: Vec<u8>,
+    client: &reqwest::Client,
+    audio: UploadAudio,
     api_key: String,
     model: Option<String>,
     language: Option<String>,
     prompt: Option<String>,
-) -> Result<String, String> {
+    timeout_secs: u64,
+) -> Result<String, TranscriptionError> {
     const POLL_INTERVAL_MS: u64 = 1_000;
     const MAX_WAIT_SECONDS: u64 = 180;
 
-    let client = reqwest::Client::new();
     let model = normalize_assemblyai_model(model);
     let speech_models = build_assemblyai_speech_models(&model);
     let prompt = if model == "universal-3-pro" {
@@ -487,27 +1227,33 @@ async fn transcribe_assemblyai(
     };
     let preferred_language = language.unwrap_or_else(|| "auto".to_string());
 
-    eprintln!(
-        "[assemblyai] submitting transcript model={} speech_models={:?} preferred_language={} language_detection=true includes_prompt={}",
-        model,
+    super::logging::log("transcription", "info", &format!("[assemblyai] submitting transcript model={} speech_models={:?} preferred_language={} language_detection=true includes_prompt={}", model,
         speech_models,
         preferred_language,
-        prompt.is_some()
-    );
+        prompt.is_some()));
 
     let upload_response = client
         .post("https://api.assemblyai.com/v2/upload")
         .header("authorization", api_key.clone())
         .header("content-type", "application/octet-stream")
-        .body(audio_data)
+        .body(audio.into_body())
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| send_error(e, timeout_secs))?;
 
-    if !upload_response.status().is_success() {
+    let upload_status = upload_response.status();
+    if !upload_status.is_success() {
         let error_text = upload_response.text().await.unwrap_or_default();
-        eprintln!("[assemblyai] upload failed status_text={}", error_text);
-        return Err(format!("AssemblyAI upload failed: {}", error_text));
+        super::logging::log(
+            "transcription",
+            "error",
+            &format!("[assemblyai] upload failed status_text={}", error_text),
+        );
+        return Err(api_status_error(
+            "AssemblyAI upload failed",
+            upload_status,
+            &error_text,
+        ));
     }
 
     let upload_result: AssemblyAIUploadResponse =
@@ -526,19 +1272,18 @@ async fn transcribe_assemblyai(
         .json(&transcript_request)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| send_error(e, timeout_secs))?;
 
-    if !transcript_response.status().is_success() {
+    let transcript_status = transcript_response.status();
+    if !transcript_status.is_success() {
         let error_text = transcript_response.text().await.unwrap_or_default();
-        eprintln!(
-            "[assemblyai] transcript submission failed preferred_language={} speech_models={:?} error={}",
-            preferred_language,
+        super::logging::log("transcription", "error", &format!("[assemblyai] transcript submission failed preferred_language={} speech_models={:?} error={}", preferred_language,
             speech_models,
-            error_text
-        );
-        return Err(format!(
-            "AssemblyAI transcript submission failed: {}",
-            error_text
+            error_text));
+        return Err(api_status_error(
+            "AssemblyAI transcript submission failed",
+            transcript_status,
+            &error_text,
         ));
     }
 
@@ -557,11 +1302,16 @@ async fn transcribe_assemblyai(
             .header("authorization", api_key.clone())
             .send()
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| send_error(e, timeout_secs))?;
 
-        if !status_response.status().is_success() {
+        let poll_status = status_response.status();
+        if !poll_status.is_success() {
             let error_text = status_response.text().await.unwrap_or_default();
-            return Err(format!("AssemblyAI polling failed: {}", error_text));
+            return Err(api_status_error(
+                "AssemblyAI polling failed",
+                poll_status,
+                &error_text,
+            ));
         }
 
         let status: AssemblyAITranscriptStatus =
@@ -571,20 +1321,23 @@ async fn transcribe_assemblyai(
             "completed" => {
                 let text = status.text.unwrap_or_default();
                 if text.trim().is_empty() {
-                    return Err("AssemblyAI returned no transcription text".to_string());
+                    return Err("AssemblyAI returned no transcription text"
+                        .to_string()
+                        .into());
                 }
                 return Ok(text);
             }
             "error" => {
                 return Err(status
                     .error
-                    .unwrap_or_else(|| "AssemblyAI transcription failed".to_string()))
+                    .unwrap_or_else(|| "AssemblyAI transcription failed".to_string())
+                    .into())
             }
             _ => sleep(Duration::from_millis(POLL_INTERVAL_MS)).await,
         }
     }
 
-    Err("AssemblyAI transcription timed out".to_string())
+    Err("AssemblyAI transcription timed out".to_string().into())
 }
 
 #[cfg(target_os = "macos")]
@@ -655,19 +1408,22 @@ async fn convert_to_wav_macos(input: &[u8]) -> Result<Vec<u8>, String> {
 }
 
 async fn transcribe_openai(
-    audio_data: Vec<u8>,
+    client: &reqwest::Client,
+    base_url: &str,
+    audio: UploadAudio,
     api_key: String,
     model: Option<String>,
     language: Option<String>,
-) -> Result<String, String> {
-    let client = reqwest::Client::new();
+    timeout_secs: u64,
+) -> Result<String, TranscriptionError> {
     let mut model = model.unwrap_or_else(|| "whisper-1".to_string());
     if model == "gpt-realtime-whisper" {
         model = "gpt-4o-mini-transcribe".to_string();
     }
 
     // Create multipart form
-    let part = reqwest::multipart::Part::bytes(audio_data)
+    let part = audio
+        .into_part()
         .file_name("audio.webm")
         .mime_str("audio/webm")
         .map_err(|e| e.to_string())?;
@@ -683,16 +1439,17 @@ async fn transcribe_openai(
     }
 
     let response = client
-        .post("https://api.openai.com/v1/audio/transcriptions")
+        .post(format!("{base_url}/audio/transcriptions"))
         .header("Authorization", format!("Bearer {}", api_key))
         .multipart(form)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| send_error(e, timeout_secs))?;
 
-    if !response.status().is_success() {
+    let status = response.status();
+    if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("OpenAI API error: {}", error_text));
+        return Err(api_status_error("OpenAI API error", status, &error_text));
     }
 
     #[derive(Deserialize)]
@@ -704,16 +1461,21 @@ async fn transcribe_openai(
     Ok(result.text)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn transcribe_groq(
-    audio_data: Vec<u8>,
+    client: &reqwest::Client,
+    base_url: &str,
+    audio: UploadAudio,
     api_key: String,
     model: Option<String>,
     language: Option<String>,
-) -> Result<String, String> {
-    let client = reqwest::Client::new();
+    timeout_secs: u64,
+    partials_to: Option<&AppHandle>,
+) -> Result<String, TranscriptionError> {
     let model = model.unwrap_or_else(|| "whisper-large-v3-turbo".to_string());
 
-    let part = reqwest::multipart::Part::bytes(audio_data)
+    let part = audio
+        .into_part()
         .file_name("audio.webm")
         .mime_str("audio/webm")
         .map_err(|e| e.to_string())?;
@@ -728,17 +1490,22 @@ async fn transcribe_groq(
         }
     }
 
-    let response = client
-        .post("https://api.groq.com/openai/v1/audio/transcriptions")
+    if partials_to.is_some() {
+        form = form.text("stream", "true");
+    }
+
+    let mut response = client
+        .post(format!("{base_url}/audio/transcriptions"))
         .header("Authorization", format!("Bearer {}", api_key))
         .multipart(form)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| send_error(e, timeout_secs))?;
 
-    if !response.status().is_success() {
+    let status = response.status();
+    if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Groq API error: {}", error_text));
+        return Err(api_status_error("Groq API error", status, &error_text));
     }
 
     #[derive(Deserialize)]
@@ -746,32 +1513,78 @@ async fn transcribe_groq(
         text: String,
     }
 
-    let result: GroqResponse = response.json().await.map_err(|e| e.to_string())?;
-    Ok(result.text)
+    let is_event_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    let Some(app) = partials_to.filter(|_| is_event_stream) else {
+        let result: GroqResponse = response.json().await.map_err(|e| e.to_string())?;
+        return Ok(result.text);
+    };
+
+    // Server-sent events: `transcript.text.delta` chunks, then `transcript.text.done` with
+    // the full text.
+    let mut pending = String::new();
+    let mut text = String::new();
+    let mut final_text: Option<String> = None;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| send_error(e, timeout_secs))?
+    {
+        pending.push_str(&String::from_utf8_lossy(&chunk));
+        while let Some(newline) = pending.find('\n') {
+            let line: String = pending.drain(..=newline).collect();
+            let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if data == "[DONE]" {
+                continue;
+            }
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else {
+                continue;
+            };
+            if let Some(delta) = event.get("delta").and_then(|v| v.as_str()) {
+                text.push_str(delta);
+                crate::overlay::show_partial_transcript(app, &text);
+            } else if let Some(done) = event.get("text").and_then(|v| v.as_str()) {
+                final_text = Some(done.to_string());
+            }
+        }
+    }
+
+    Ok(final_text.unwrap_or(text))
 }
 
 async fn transcribe_zai(
-    audio_data: Vec<u8>,
+    client: &reqwest::Client,
+    base_url: &str,
+    audio: UploadAudio,
     api_key: String,
     model: Option<String>,
     language: Option<String>,
-) -> Result<String, String> {
-    let client = reqwest::Client::new();
+    timeout_secs: u64,
+) -> Result<String, TranscriptionError> {
     let model = model.unwrap_or_else(|| "glm-asr-2512".to_string());
 
     // Z.ai requires WAV/MP3; on macOS we convert using the built-in `afconvert`.
     #[cfg(target_os = "macos")]
-    let audio_data = {
-        // Our native macOS recorder already produces 16kHz mono WAV.
+    let audio = {
+        // Our native macOS recorder produces 16kHz mono WAV unless `recordingFormat` is m4a.
         // Avoid `afconvert` when the input is already WAV to reduce flakiness.
-        if guess_audio_extension(&audio_data) == "wav" {
-            audio_data
+        if guess_audio_extension(&audio.data) == "wav" {
+            audio
         } else {
-            convert_to_wav_macos(&audio_data).await?
+            UploadAudio {
+                data: convert_to_wav_macos(&audio.data).await?,
+                progress_to: audio.progress_to,
+            }
         }
     };
 
-    let part = reqwest::multipart::Part::bytes(audio_data)
+    let part = audio
+        .into_part()
         .file_name("audio.wav")
         .mime_str("audio/wav")
         .map_err(|e| e.to_string())?;
@@ -785,16 +1598,17 @@ async fn transcribe_zai(
     let _ = language;
 
     let response = client
-        .post("https://api.z.ai/api/paas/v4/audio/transcriptions")
+        .post(format!("{base_url}/audio/transcriptions"))
         .header("Authorization", format!("Bearer {}", api_key))
         .multipart(form)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| send_error(e, timeout_secs))?;
 
-    if !response.status().is_success() {
+    let status = response.status();
+    if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Z.ai API error: {}", error_text));
+        return Err(api_status_error("Z.ai API error", status, &error_text));
     }
 
     let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
@@ -813,7 +1627,7 @@ async fn transcribe_zai(
 
     match candidate {
         Some(text) if !text.trim().is_empty() => Ok(text.to_string()),
-        _ => Err("Z.ai returned no transcription text".to_string()),
+        _ => Err("Z.ai returned no transcription text".to_string().into()),
     }
 }
 
@@ -881,13 +1695,17 @@ async fn transcribe_volcengine(
     let ws_url = mode.endpoint();
     let connect_id = uuid::Uuid::new_v4().to_string();
 
-    eprintln!(
-        "[volcengine] connecting to {} mode={} resource={} audio_ms={} connect_id={}",
-        ws_url,
-        mode.label(),
-        resource_id,
-        expected_audio_duration_ms,
-        connect_id
+    super::logging::log(
+        "transcription",
+        "info",
+        &format!(
+            "[volcengine] connecting to {} mode={} resource={} audio_ms={} connect_id={}",
+            ws_url,
+            mode.label(),
+            resource_id,
+            expected_audio_duration_ms,
+            connect_id
+        ),
     );
 
     // Build HTTP request with custom headers (required by Volcengine)
@@ -924,10 +1742,14 @@ async fn transcribe_volcengine(
             }
             Err(err) => {
                 let message = format!("Failed to connect to Volcengine ASR: {err}");
-                eprintln!(
-                    "[volcengine] connect failed auth_mode={} error={}",
-                    auth_mode.label(),
-                    message
+                super::logging::log(
+                    "transcription",
+                    "error",
+                    &format!(
+                        "[volcengine] connect failed auth_mode={} error={}",
+                        auth_mode.label(),
+                        message
+                    ),
                 );
                 if should_retry_volcengine_auth(&message, auth_mode) {
                     last_connect_error = Some(message);
@@ -947,11 +1769,15 @@ async fn transcribe_volcengine(
         .get("X-Tt-Logid")
         .and_then(|value| value.to_str().ok())
         .unwrap_or("");
-    eprintln!(
-        "[volcengine] connected auth_mode={} connect_id={} log_id={}",
-        auth_mode.label(),
-        connect_id,
-        log_id
+    super::logging::log(
+        "transcription",
+        "info",
+        &format!(
+            "[volcengine] connected auth_mode={} connect_id={} log_id={}",
+            auth_mode.label(),
+            connect_id,
+            log_id
+        ),
     );
 
     let (mut write, mut read) = ws_stream.split();
@@ -1002,13 +1828,17 @@ async fn transcribe_volcengine(
         });
     }
 
-    eprintln!(
-        "[volcengine] config prepared auth_mode={} mode={} resource={} audio_ms={} hotwords={}",
-        auth_mode.label(),
-        mode.label(),
-        resource_id,
-        expected_audio_duration_ms,
-        hotwords.len()
+    super::logging::log(
+        "transcription",
+        "info",
+        &format!(
+            "[volcengine] config prepared auth_mode={} mode={} resource={} audio_ms={} hotwords={}",
+            auth_mode.label(),
+            mode.label(),
+            resource_id,
+            expected_audio_duration_ms,
+            hotwords.len()
+        ),
     );
 
     let json_bytes = serde_json::to_vec(&config_payload).map_err(|e| e.to_string())?;
@@ -1068,11 +1898,15 @@ async fn transcribe_volcengine(
         }
     }
 
-    eprintln!(
-        "[volcengine] sent {} audio chunks ({} bytes, {}ms interval)",
-        total_chunks,
-        audio_data.len(),
-        chunk_interval_ms
+    super::logging::log(
+        "transcription",
+        "info",
+        &format!(
+            "[volcengine] sent {} audio chunks ({} bytes, {}ms interval)",
+            total_chunks,
+            audio_data.len(),
+            chunk_interval_ms
+        ),
     );
 
     // 3. Read responses until connection closes
@@ -1106,10 +1940,14 @@ async fn transcribe_volcengine(
                 .take(128)
                 .map(|b| format!("{:02x}", b))
                 .collect();
-            eprintln!(
-                "[volcengine] error packet raw ({} bytes): {}",
-                data.len(),
-                hex.join(" ")
+            super::logging::log(
+                "transcription",
+                "error",
+                &format!(
+                    "[volcengine] error packet raw ({} bytes): {}",
+                    data.len(),
+                    hex.join(" ")
+                ),
             );
 
             // Error packet format per spec: Header(4) + ErrorCode(4) + MessageSize(4) + UTF-8 string
@@ -1120,7 +1958,11 @@ async fn transcribe_volcengine(
                 let code = u32::from_be_bytes(data[h..h + 4].try_into().unwrap_or([0; 4]));
                 let msg_size =
                     u32::from_be_bytes(data[h + 4..h + 8].try_into().unwrap_or([0; 4])) as usize;
-                eprintln!("[volcengine] error code={}, msg_size={}", code, msg_size);
+                super::logging::log(
+                    "transcription",
+                    "error",
+                    &format!("[volcengine] error code={}, msg_size={}", code, msg_size),
+                );
 
                 if msg_size > 0 && data.len() >= h + 8 + msg_size {
                     let raw = &data[h + 8..h + 8 + msg_size];
@@ -1136,7 +1978,11 @@ async fn transcribe_volcengine(
                 error_msg = String::from_utf8_lossy(&data[h..]).to_string();
             }
 
-            eprintln!("[volcengine] server error: {}", error_msg);
+            super::logging::log(
+                "transcription",
+                "error",
+                &format!("[volcengine] server error: {}", error_msg),
+            );
             return Err(error_msg);
         }
 
@@ -1154,9 +2000,13 @@ async fn transcribe_volcengine(
         };
 
         if let Ok(parsed) = serde_json::from_slice::<serde_json::Value>(&payload_bytes) {
-            eprintln!(
-                "[volcengine] response payload: {}",
-                serde_json::to_string(&parsed).unwrap_or_default()
+            super::logging::log(
+                "transcription",
+                "info",
+                &format!(
+                    "[volcengine] response payload: {}",
+                    serde_json::to_string(&parsed).unwrap_or_default()
+                ),
             );
 
             let text = volcengine_response_text(&parsed).unwrap_or("");
@@ -1176,12 +2026,9 @@ async fn transcribe_volcengine(
                 // without waiting for the close frame. Avoid returning on prefetch/non-definite
                 // packets because those can miss tail punctuation or the last unstable words.
                 if is_final_result && response_audio_ms + 250 >= expected_audio_duration_ms {
-                    eprintln!(
-                        "[volcengine] final result ready before close: chars={} response_audio_ms={} expected_audio_ms={}",
-                        accumulated_text.len(),
+                    super::logging::log("transcription", "info", &format!("[volcengine] final result ready before close: chars={} response_audio_ms={} expected_audio_ms={}", accumulated_text.len(),
                         response_audio_ms,
-                        expected_audio_duration_ms
-                    );
+                        expected_audio_duration_ms));
                     return Ok(accumulated_text);
                 }
             }
@@ -1191,9 +2038,13 @@ async fn transcribe_volcengine(
     if accumulated_text.is_empty() {
         Err("Volcengine ASR returned no transcription result".to_string())
     } else {
-        eprintln!(
-            "[volcengine] transcription complete: {} chars",
-            accumulated_text.len()
+        super::logging::log(
+            "transcription",
+            "info",
+            &format!(
+                "[volcengine] transcription complete: {} chars",
+                accumulated_text.len()
+            ),
         );
         Ok(accumulated_text)
     }
@@ -1289,11 +2140,19 @@ fn normalize_volcengine_resource_id(resource_id: &str) -> String {
     };
 
     if trimmed.is_empty() {
-        eprintln!("[volcengine] resource_id empty, using {}", fallback);
+        super::logging::log(
+            "transcription",
+            "warn",
+            &format!("[volcengine] resource_id empty, using {}", fallback),
+        );
     } else {
-        eprintln!(
-            "[volcengine] resource_id '{}' is not an API Resource ID, using {}",
-            trimmed, fallback
+        super::logging::log(
+            "transcription",
+            "info",
+            &format!(
+                "[volcengine] resource_id '{}' is not an API Resource ID, using {}",
+                trimmed, fallback
+            ),
         );
     }
 
@@ -1506,10 +2365,7 @@ async fn run_openai_realtime_session(
     let normalized_language = normalize_openai_realtime_language(language.as_deref());
     let ws_url = format!("wss://api.openai.com/v1/realtime?model={realtime_model}");
 
-    eprintln!(
-        "[openai-realtime] connecting session={} realtime_model={} transcription_model={} delay={}",
-        session_id, realtime_model, transcription_model, transcription_delay
-    );
+    super::logging::component_log(&app, "transcription", "info", &format!("[openai-realtime] connecting session={} realtime_model={} transcription_model={} delay={}", session_id, realtime_model, transcription_model, transcription_delay));
 
     let uri: http::Uri = ws_url
         .parse()
@@ -1534,10 +2390,15 @@ async fn run_openai_realtime_session(
         .await
         .map_err(|e| format!("Failed to connect to OpenAI realtime transcription: {e}"))?;
 
-    eprintln!(
-        "[openai-realtime] connected session={} status={}",
-        session_id,
-        response.status()
+    super::logging::component_log(
+        &app,
+        "transcription",
+        "info",
+        &format!(
+            "[openai-realtime] connected session={} status={}",
+            session_id,
+            response.status()
+        ),
     );
 
     let (mut write, mut read) = ws_stream.split();
@@ -1609,10 +2470,7 @@ async fn run_openai_realtime_session(
                                 .map_err(|e| format!("OpenAI realtime send finish: {e}"))?;
                             finish_requested = true;
                             finish_started_at = Some(Instant::now());
-                            eprintln!(
-                                "[openai-realtime] finish sent session={} chunks={} bytes={}",
-                                session_id, audio_chunk_count, total_audio_bytes
-                            );
+                            super::logging::component_log(&app, "transcription", "info", &format!("[openai-realtime] finish sent session={} chunks={} bytes={}", session_id, audio_chunk_count, total_audio_bytes));
                         }
                     }
                     Some(OpenAIRealtimeCommand::Cancel) => {
@@ -1706,11 +2564,8 @@ async fn run_openai_realtime_session(
                         if accumulated_text.trim().is_empty() {
                             return Err("OpenAI realtime ASR returned no transcription result".to_string());
                         }
-                        eprintln!(
-                            "[openai-realtime] final result session={} chars={}",
-                            session_id,
-                            accumulated_text.len()
-                        );
+                        super::logging::component_log(&app, "transcription", "info", &format!("[openai-realtime] final result session={} chars={}", session_id,
+                            accumulated_text.len()));
                         return Ok(accumulated_text);
                     }
                     "error" => {
@@ -1727,11 +2582,8 @@ async fn run_openai_realtime_session(
                     if accumulated_text.trim().is_empty() {
                         return Err("OpenAI realtime ASR returned no transcription result".to_string());
                     }
-                    eprintln!(
-                        "[openai-realtime] final wait timeout session={} using latest chars={}",
-                        session_id,
-                        accumulated_text.len()
-                    );
+                    super::logging::component_log(&app, "transcription", "warn", &format!("[openai-realtime] final wait timeout session={} using latest chars={}", session_id,
+                        accumulated_text.len()));
                     return Ok(accumulated_text);
                 }
             }
@@ -1763,14 +2615,11 @@ async fn run_volcengine_streaming_session(
     let ws_url = mode.endpoint();
     let connect_id = uuid::Uuid::new_v4().to_string();
 
-    eprintln!(
-        "[volcengine-stream] connecting session={} endpoint={} mode={} resource={} connect_id={}",
-        session_id,
+    super::logging::component_log(&app, "transcription", "info", &format!("[volcengine-stream] connecting session={} endpoint={} mode={} resource={} connect_id={}", session_id,
         ws_url,
         mode.label(),
         resource_id,
-        connect_id
-    );
+        connect_id));
 
     let uri: http::Uri = ws_url
         .parse()
@@ -1805,11 +2654,16 @@ async fn run_volcengine_streaming_session(
             }
             Err(err) => {
                 let message = format!("Failed to connect to Volcengine streaming ASR: {err}");
-                eprintln!(
-                    "[volcengine-stream] connect failed session={} auth_mode={} error={}",
-                    session_id,
-                    auth_mode.label(),
-                    message
+                super::logging::component_log(
+                    &app,
+                    "transcription",
+                    "error",
+                    &format!(
+                        "[volcengine-stream] connect failed session={} auth_mode={} error={}",
+                        session_id,
+                        auth_mode.label(),
+                        message
+                    ),
                 );
                 if should_retry_volcengine_auth(&message, auth_mode) {
                     last_connect_error = Some(message);
@@ -1829,12 +2683,17 @@ async fn run_volcengine_streaming_session(
         .get("X-Tt-Logid")
         .and_then(|value| value.to_str().ok())
         .unwrap_or("");
-    eprintln!(
-        "[volcengine-stream] connected session={} auth_mode={} connect_id={} log_id={}",
-        session_id,
-        auth_mode.label(),
-        connect_id,
-        log_id
+    super::logging::component_log(
+        &app,
+        "transcription",
+        "info",
+        &format!(
+            "[volcengine-stream] connected session={} auth_mode={} connect_id={} log_id={}",
+            session_id,
+            auth_mode.label(),
+            connect_id,
+            log_id
+        ),
     );
 
     let (mut write, mut read) = ws_stream.split();
@@ -1953,10 +2812,7 @@ async fn run_volcengine_streaming_session(
                                 .map_err(|e| format!("Volcengine streaming send finish: {e}"))?;
                             finish_requested = true;
                             finish_started_at = Some(Instant::now());
-                            eprintln!(
-                                "[volcengine-stream] finish sent session={} chunks={} bytes={}",
-                                session_id, audio_packet_count, total_audio_bytes
-                            );
+                            super::logging::component_log(&app, "transcription", "info", &format!("[volcengine-stream] finish sent session={} chunks={} bytes={}", session_id, audio_packet_count, total_audio_bytes));
                         }
                     }
                     Some(VolcengineStreamCommand::Cancel) => {
@@ -1999,11 +2855,8 @@ async fn run_volcengine_streaming_session(
 
                 if msg_type == VOLC_MSG_SERVER_ERROR {
                     if finish_requested && !accumulated_text.trim().is_empty() {
-                        eprintln!(
-                            "[volcengine-stream] server closed after finish session={} chars={}",
-                            session_id,
-                            accumulated_text.len()
-                        );
+                        super::logging::component_log(&app, "transcription", "info", &format!("[volcengine-stream] server closed after finish session={} chars={}", session_id,
+                            accumulated_text.len()));
                         return Ok(accumulated_text);
                     }
                     return Err(volcengine_error_packet_to_string(&data, header_byte_len));
@@ -2056,13 +2909,10 @@ async fn run_volcengine_streaming_session(
                         && (response_audio_ms == 0
                             || response_audio_ms + 250 >= expected_audio_duration_ms)
                     {
-                        eprintln!(
-                            "[volcengine-stream] final result session={} chars={} response_audio_ms={} expected_audio_ms={}",
-                            session_id,
+                        super::logging::component_log(&app, "transcription", "info", &format!("[volcengine-stream] final result session={} chars={} response_audio_ms={} expected_audio_ms={}", session_id,
                             accumulated_text.len(),
                             response_audio_ms,
-                            expected_audio_duration_ms
-                        );
+                            expected_audio_duration_ms));
                         return Ok(accumulated_text);
                     }
                 }
@@ -2075,11 +2925,8 @@ async fn run_volcengine_streaming_session(
                     if accumulated_text.trim().is_empty() {
                         return Err("Volcengine streaming ASR returned no transcription result".to_string());
                     }
-                    eprintln!(
-                        "[volcengine-stream] final wait timeout session={} using latest chars={}",
-                        session_id,
-                        accumulated_text.len()
-                    );
+                    super::logging::component_log(&app, "transcription", "warn", &format!("[volcengine-stream] final wait timeout session={} using latest chars={}", session_id,
+                        accumulated_text.len()));
                     return Ok(accumulated_text);
                 }
             }
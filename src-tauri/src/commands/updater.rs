@@ -0,0 +1,110 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/Charlo-O/typefree/releases/latest";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// How long a successful check is reused, so opening settings doesn't hit the API each time.
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub release_url: String,
+    pub changelog: String,
+    pub is_update_available: bool,
+}
+
+/// The fields we use from GitHub's release JSON.
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+    body: Option<String>,
+}
+
+/// Last successful check and when it was made.
+#[derive(Default)]
+pub struct UpdateCache(Mutex<Option<(Instant, UpdateInfo)>>);
+
+fn ensure_update_cache(app: &AppHandle) {
+    if app.try_state::<UpdateCache>().is_none() {
+        app.manage(UpdateCache::default());
+    }
+}
+
+fn cached_update_info(app: &AppHandle) -> Option<UpdateInfo> {
+    ensure_update_cache(app);
+    app.state::<UpdateCache>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .filter(|(checked_at, _)| checked_at.elapsed() < CACHE_TTL)
+        .map(|(_, info)| info.clone())
+}
+
+fn parse_version(version: &str) -> Result<semver::Version, String> {
+    let trimmed = version.trim();
+    let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+    semver::Version::parse(trimmed).map_err(|e| format!("Invalid version '{}': {}", version, e))
+}
+
+async fn fetch_latest_release(
+    app: &AppHandle,
+    current_version: &str,
+) -> Result<GithubRelease, String> {
+    let client = super::network::http_client_builder(app)?
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+    let response = client
+        .get(LATEST_RELEASE_URL)
+        .header("User-Agent", format!("typefree/{}", current_version))
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body_text = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "GitHub API error: {} {}",
+            status.as_u16(),
+            body_text
+        ));
+    }
+    response
+        .json::<GithubRelease>()
+        .await
+        .map_err(|e| format!("Failed to parse release info: {}", e))
+}
+
+/// Compare this build with the latest GitHub release. Results are cached for an hour.
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateInfo, String> {
+    if let Some(info) = cached_update_info(&app) {
+        return Ok(info);
+    }
+
+    let current_version = env!("CARGO_PKG_VERSION").to_string();
+    let release = fetch_latest_release(&app, &current_version).await?;
+    let is_update_available = parse_version(&release.tag_name)? > parse_version(&current_version)?;
+    let info = UpdateInfo {
+        current_version,
+        latest_version: release.tag_name,
+        release_url: release.html_url,
+        changelog: release.body.unwrap_or_default(),
+        is_update_available,
+    };
+
+    *app.state::<UpdateCache>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some((Instant::now(), info.clone()));
+    Ok(info)
+}
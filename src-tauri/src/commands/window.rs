@@ -1,7 +1,11 @@
+use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{
-    AppHandle, Emitter, LogicalSize, Manager, PhysicalPosition, Size, WebviewUrl, WebviewWindow,
-    WebviewWindowBuilder, Window,
+    AppHandle, Emitter, LogicalSize, Manager, Monitor, PhysicalPosition, PhysicalRect,
+    PhysicalSize, Size, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window,
 };
 
 const MAIN_WINDOW_WIDTH: f64 = 240.0;
@@ -11,6 +15,54 @@ const CONTROL_PANEL_WIDTH: f64 = 1040.0;
 const CONTROL_PANEL_HEIGHT: f64 = 760.0;
 const CLIPBOARD_PANEL_WIDTH: f64 = 920.0;
 const CLIPBOARD_PANEL_HEIGHT: f64 = 720.0;
+const SAVED_WINDOW_POSITION_KEY: &str = "savedWindowPosition";
+const SAVE_POSITION_DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Where the user last dragged the dictation panel, in physical pixels.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedWindowPosition {
+    x: i32,
+    y: i32,
+    monitor_name: String,
+}
+
+/// Position we last moved the dictation panel to ourselves, so the move event that follows
+/// isn't mistaken for a drag.
+static PLACED_POSITION: Mutex<Option<(i32, i32)>> = Mutex::new(None);
+static SAVE_POSITION_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Distance from a work-area edge within which a dragged dictation panel snaps flush to it.
+const SNAP_THRESHOLD_PX: i32 = 20;
+
+#[derive(Debug, Clone, Copy)]
+enum HorizontalEdge {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum VerticalEdge {
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Snap {
+    horizontal: Option<HorizontalEdge>,
+    vertical: Option<VerticalEdge>,
+}
+
+/// Edges the dictation panel was last snapped to, re-applied on reveal so it stays flush
+/// after the monitor layout changes.
+#[derive(Default)]
+pub struct SnappedEdges(Mutex<Snap>);
+
+fn snapped_edges(app: &AppHandle) -> tauri::State<'_, SnappedEdges> {
+    if app.try_state::<SnappedEdges>().is_none() {
+        app.manage(SnappedEdges::default());
+    }
+    app.state::<SnappedEdges>()
+}
 
 #[cfg(target_os = "macos")]
 fn log_webview_state(stage: &str, window: &WebviewWindow) {
@@ -211,6 +263,19 @@ fn move_main_webview_to_lower_center(window: &WebviewWindow) -> Result<(), Strin
         .or_else(|_| window.inner_size())
         .map_err(|e| e.to_string())?;
 
+    let snap = if window_snapping_enabled(window.app_handle()) {
+        *snapped_edges(window.app_handle())
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    } else {
+        Snap::default()
+    };
+
+    if let Some(saved) = saved_window_position(window, &monitor) {
+        return place_main_webview(window, apply_snap(work_area, window_size, saved, snap));
+    }
+
     let max_x = work_area.position.x + work_area.size.width as i32 - window_size.width as i32;
     let max_y = work_area.position.y + work_area.size.height as i32 - window_size.height as i32;
     let centered_x =
@@ -219,14 +284,185 @@ fn move_main_webview_to_lower_center(window: &WebviewWindow) -> Result<(), Strin
         work_area.position.y as f64 + (work_area.size.height as f64 * MAIN_WINDOW_CENTER_Y_RATIO);
     let centered_y = target_center_y.round() as i32 - (window_size.height as i32 / 2);
 
-    window
-        .set_position(PhysicalPosition::new(
-            centered_x.clamp(work_area.position.x, max_x.max(work_area.position.x)),
-            centered_y.clamp(work_area.position.y, max_y.max(work_area.position.y)),
-        ))
-        .map_err(|e| e.to_string())?;
+    let centered = PhysicalPosition::new(
+        centered_x.clamp(work_area.position.x, max_x.max(work_area.position.x)),
+        centered_y.clamp(work_area.position.y, max_y.max(work_area.position.y)),
+    );
+    place_main_webview(window, apply_snap(work_area, window_size, centered, snap))
+}
 
-    Ok(())
+/// `windowSnapping`: snap the dragged dictation panel to work-area edges (default on).
+fn window_snapping_enabled(app: &AppHandle) -> bool {
+    super::settings::get_setting(app.clone(), "windowSnapping".to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// `position` moved flush against the edges in `snap`.
+fn apply_snap(
+    work_area: &PhysicalRect<i32, u32>,
+    size: PhysicalSize<u32>,
+    position: PhysicalPosition<i32>,
+    snap: Snap,
+) -> PhysicalPosition<i32> {
+    let x = match snap.horizontal {
+        Some(HorizontalEdge::Left) => work_area.position.x,
+        Some(HorizontalEdge::Right) => {
+            work_area.position.x + work_area.size.width as i32 - size.width as i32
+        }
+        None => position.x,
+    };
+    let y = match snap.vertical {
+        Some(VerticalEdge::Top) => work_area.position.y,
+        Some(VerticalEdge::Bottom) => {
+            work_area.position.y + work_area.size.height as i32 - size.height as i32
+        }
+        None => position.y,
+    };
+    PhysicalPosition::new(x, y)
+}
+
+/// Snap the dictation panel flush to any work-area edge it was dragged within
+/// `SNAP_THRESHOLD_PX` of, and remember those edges for the next reveal.
+pub(crate) fn snap_window_to_edge(window: &WebviewWindow) {
+    let app = window.app_handle();
+    if !window_snapping_enabled(app) {
+        return;
+    }
+    let (Ok(position), Ok(size), Ok(Some(monitor))) = (
+        window.outer_position(),
+        window.outer_size(),
+        window.current_monitor(),
+    ) else {
+        return;
+    };
+
+    let work_area = monitor.work_area();
+    let left = work_area.position.x;
+    let right = left + work_area.size.width as i32 - size.width as i32;
+    let top = work_area.position.y;
+    let bottom = top + work_area.size.height as i32 - size.height as i32;
+    let near = |value: i32, edge: i32| (value - edge).abs() <= SNAP_THRESHOLD_PX;
+    let snap = Snap {
+        horizontal: if near(position.x, left) {
+            Some(HorizontalEdge::Left)
+        } else if near(position.x, right) {
+            Some(HorizontalEdge::Right)
+        } else {
+            None
+        },
+        vertical: if near(position.y, top) {
+            Some(VerticalEdge::Top)
+        } else if near(position.y, bottom) {
+            Some(VerticalEdge::Bottom)
+        } else {
+            None
+        },
+    };
+    *snapped_edges(app)
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = snap;
+
+    let snapped = apply_snap(work_area, size, position, snap);
+    if snapped != position {
+        if let Err(err) = window.set_position(snapped) {
+            eprintln!("[window] failed to snap dictation panel: {}", err);
+        }
+    }
+}
+
+fn place_main_webview(
+    window: &WebviewWindow,
+    position: PhysicalPosition<i32>,
+) -> Result<(), String> {
+    *PLACED_POSITION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some((position.x, position.y));
+    window.set_position(position).map_err(|e| e.to_string())
+}
+
+/// The user's saved panel position, if it was saved on `monitor` and the panel still fits
+/// entirely on that monitor there.
+fn saved_window_position(
+    window: &WebviewWindow,
+    monitor: &Monitor,
+) -> Option<PhysicalPosition<i32>> {
+    let saved: SavedWindowPosition = super::settings::get_setting(
+        window.app_handle().clone(),
+        SAVED_WINDOW_POSITION_KEY.to_string(),
+    )
+    .ok()
+    .flatten()
+    .and_then(|value| serde_json::from_value(value).ok())?;
+
+    if monitor.name() != Some(&saved.monitor_name) {
+        return None;
+    }
+
+    let size = window.outer_size().ok()?;
+    let origin = monitor.position();
+    let bounds = monitor.size();
+    let fits = saved.x >= origin.x
+        && saved.y >= origin.y
+        && saved.x + size.width as i32 <= origin.x + bounds.width as i32
+        && saved.y + size.height as i32 <= origin.y + bounds.height as i32;
+    fits.then(|| PhysicalPosition::new(saved.x, saved.y))
+}
+
+/// Remember where the user dragged the dictation panel. Called for every move event, so
+/// the write is debounced until the drag settles; moves we made ourselves are ignored.
+pub(crate) fn save_window_position(window: &WebviewWindow) {
+    let Ok(position) = window.outer_position() else {
+        return;
+    };
+    let placed = *PLACED_POSITION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if placed == Some((position.x, position.y)) {
+        return;
+    }
+
+    let seq = SAVE_POSITION_SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_POSITION_DEBOUNCE).await;
+        if SAVE_POSITION_SEQ.load(Ordering::SeqCst) != seq {
+            return;
+        }
+
+        let Ok(position) = window.outer_position() else {
+            return;
+        };
+        let Some(monitor_name) = window
+            .current_monitor()
+            .ok()
+            .flatten()
+            .and_then(|monitor| monitor.name().cloned())
+        else {
+            return;
+        };
+
+        let saved = SavedWindowPosition {
+            x: position.x,
+            y: position.y,
+            monitor_name,
+        };
+        let result = serde_json::to_value(&saved)
+            .map_err(|e| e.to_string())
+            .and_then(|value| {
+                super::settings::set_setting(
+                    window.app_handle().clone(),
+                    SAVED_WINDOW_POSITION_KEY.to_string(),
+                    value,
+                )
+            });
+        if let Err(err) = result {
+            eprintln!("[window] failed to save dictation panel position: {}", err);
+        }
+    });
 }
 
 pub(crate) fn reveal_window(window: &Window) -> Result<(), String> {
@@ -305,6 +541,45 @@ pub(crate) fn reveal_main_window(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Re-place the dictation panel after displays were connected, removed or rescaled. A
+/// hidden panel is left alone: it is placed, and its saved position checked, on reveal.
+#[cfg(target_os = "macos")]
+fn handle_display_change(app: &AppHandle) {
+    let Some(main_window) = app.get_webview_window("main") else {
+        return;
+    };
+    if !main_window.is_visible().unwrap_or(false) {
+        return;
+    }
+    eprintln!("[window] display configuration changed; re-placing dictation panel");
+    if let Err(err) = reveal_main_window(app) {
+        eprintln!("[window] failed to re-place dictation panel: {}", err);
+    }
+}
+
+/// Observe `NSApplicationDidChangeScreenParametersNotification` for the life of the app.
+#[cfg(target_os = "macos")]
+pub(crate) fn watch_display_changes(app: &AppHandle) {
+    use block2::RcBlock;
+    use objc2_app_kit::NSApplicationDidChangeScreenParametersNotification;
+    use objc2_foundation::{NSNotification, NSNotificationCenter, NSOperationQueue};
+    use std::ptr::NonNull;
+
+    let app = app.clone();
+    let block: RcBlock<dyn Fn(NonNull<NSNotification>)> =
+        RcBlock::new(move |_| handle_display_change(&app));
+    let observer = unsafe {
+        NSNotificationCenter::defaultCenter().addObserverForName_object_queue_usingBlock(
+            Some(NSApplicationDidChangeScreenParametersNotification),
+            None,
+            Some(&NSOperationQueue::mainQueue()),
+            &block,
+        )
+    };
+    // Never removed; the notification center only needs the token to unregister.
+    std::mem::forget(observer);
+}
+
 /// Show the dictation panel window
 #[tauri::command]
 pub fn show_dictation_panel(window: Window) -> Result<(), String> {
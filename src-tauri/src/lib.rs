@@ -1,14 +1,15 @@
 mod clipboard_listener;
 mod commands;
 mod overlay;
+mod tray;
 
 use commands::{
-    audio_ducking, clipboard, database, hotkey, logging, reasoning, recording, settings,
-    transcription, window,
+    audio_ducking, clipboard, database, dictation, hotkey, logging, pending_recordings, reasoning,
+    recording, settings, settings_schema, text_expander, transcription, updater, window,
 };
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
-use tauri::WindowEvent;
+use tauri::{Manager, WindowEvent};
 
 const TRAY_OPEN_CONTROL_PANEL_ID: &str = "tray_open_control_panel";
 const TRAY_QUIT_ID: &str = "tray_quit";
@@ -21,6 +22,9 @@ fn show_control_panel_from_tray(app: tauri::AppHandle) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Debug-level diagnostics (e.g. debounced hotkey presses) show up with RUST_LOG=debug.
+    let _ = env_logger::try_init();
+
     let builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
@@ -59,6 +63,15 @@ pub fn run() {
             }
         })
         .on_window_event(|window, event| {
+            if window.label() == "main" {
+                if let WindowEvent::Moved(_) = event {
+                    if let Some(main_window) = window.app_handle().get_webview_window("main") {
+                        window::snap_window_to_edge(&main_window);
+                        window::save_window_position(&main_window);
+                    }
+                }
+            }
+
             #[cfg(target_os = "windows")]
             if window.label() == "control" {
                 if let WindowEvent::CloseRequested { api, .. } = event {
@@ -81,26 +94,52 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // Clipboard commands
             clipboard::paste_text,
+            clipboard::paste_text_by_typing,
             clipboard::paste_image,
             clipboard::read_clipboard,
             clipboard::write_clipboard,
             clipboard::write_clipboard_image,
+            clipboard::read_clipboard_html,
+            clipboard::write_clipboard_html,
             clipboard::check_paste_tools,
             clipboard::check_accessibility_permission,
+            clipboard::request_accessibility_permission,
+            clipboard::get_clipboard_history,
+            clipboard::clear_clipboard_history,
+            clipboard::pause_clipboard_monitoring,
+            clipboard::resume_clipboard_monitoring,
             // Database commands
             database::db_save_transcription,
             database::db_get_transcriptions,
             database::db_delete_transcription,
             database::db_clear_transcriptions,
+            database::db_get_recording_path,
+            database::db_save_clipboard_item,
+            database::db_get_clipboard_history,
+            database::db_save_prompt_template,
+            database::db_get_prompt_templates,
+            database::db_update_prompt_template,
+            database::db_delete_prompt_template,
+            database::db_get_ai_usage_summary,
+            database::db_get_dictation_stats,
             // Settings commands
             settings::get_setting,
             settings::set_setting,
             settings::get_env_var,
             settings::set_env_var,
+            settings::get_api_key,
+            settings::set_api_key,
             settings::get_all_settings,
+            settings_schema::get_settings_schema,
+            settings::export_settings,
+            settings::import_settings,
             // Transcription commands
             transcription::transcribe_audio,
+            transcription::ping_transcription_provider,
+            transcription::cancel_transcription,
+            pending_recordings::get_pending_recordings_count,
             transcription::get_transcription_providers,
+            transcription::validate_api_key,
             transcription::start_volcengine_streaming_transcription,
             transcription::send_volcengine_streaming_audio,
             transcription::finish_volcengine_streaming_transcription,
@@ -109,10 +148,19 @@ pub fn run() {
             transcription::send_openai_realtime_audio,
             transcription::finish_openai_realtime_transcription,
             transcription::cancel_openai_realtime_transcription,
-            // Native recording commands (macOS only; returns error on other platforms)
+            // Native recording commands (macOS and Windows; returns error on other platforms)
+            recording::list_audio_input_devices,
+            recording::get_current_input_device,
             recording::start_native_recording,
             recording::stop_native_recording,
             recording::cancel_native_recording,
+            recording::pause_native_recording,
+            recording::resume_native_recording,
+            recording::get_recording_status,
+            recording::check_microphone_permission,
+            recording::request_microphone_permission,
+            recording::check_system_audio_permission,
+            recording::calibrate_noise_floor,
             // Audio ducking commands
             audio_ducking::start_audio_ducking,
             audio_ducking::stop_audio_ducking,
@@ -130,14 +178,35 @@ pub fn run() {
             // Hotkey commands
             hotkey::register_hotkey,
             hotkey::register_hotkeys,
+            hotkey::register_action_hotkey,
             hotkey::unregister_hotkeys,
+            // Dictation commands
+            dictation::reset_dictation_state,
+            dictation::repeat_last_dictation,
+            dictation::paste_last_transcription,
+            text_expander::get_default_text_commands,
+            text_expander::add_abbreviation,
+            text_expander::remove_abbreviation,
+            text_expander::test_text_substitution,
             // Reasoning commands
             reasoning::process_anthropic_reasoning,
+            reasoning::process_anthropic_reasoning_streaming,
+            reasoning::process_openai_reasoning,
+            reasoning::process_ollama_reasoning,
+            reasoning::list_ollama_models,
+            reasoning::process_reasoning,
             // Logging commands
             logging::write_renderer_log,
             logging::get_debug_state,
+            logging::get_log_level,
+            logging::read_renderer_logs,
+            logging::get_log_file_path,
+            logging::cleanup_old_logs,
             logging::set_debug_logging,
             logging::open_logs_folder,
+            logging::generate_diagnostic_report,
+            // Updater commands
+            updater::check_for_update,
         ])
         .setup(|app| {
             #[cfg(desktop)]
@@ -151,6 +220,12 @@ pub fn run() {
                 ))?;
             }
 
+            // Backend logging (per-component log files).
+            logging::init_logging(app.handle());
+
+            // API keys used to live in a plaintext .env file.
+            settings::migrate_env_keys_to_keychain(app.handle());
+
             // Initialize database on startup
             database::init_database(app.handle())?;
 
@@ -160,12 +235,19 @@ pub fn run() {
             // Start clipboard monitoring (text + images) and broadcast updates to renderer.
             clipboard_listener::start(app.handle().clone());
 
-            // Backend dictation coordinator (macOS hotkey path).
-            commands::dictation::init_dictation_coordinator(app.handle());
+            // Backend dictation coordinator (hotkey path on platforms with native recording).
+            dictation::init_dictation_coordinator(app.handle());
+
+            // Transcribe recordings made while offline once the connection is back.
+            pending_recordings::start_pending_queue_worker(app.handle().clone());
 
             // Handy-style recording overlay (non-activating panel on macOS).
             overlay::init_recording_overlay(app.handle());
 
+            // Keep the dictation panel on-screen as displays come and go.
+            #[cfg(target_os = "macos")]
+            window::watch_display_changes(app.handle());
+
             if let Some(tray) = app.tray_by_id("main") {
                 let open = MenuItem::with_id(
                     app,
@@ -181,6 +263,9 @@ pub fn run() {
                 tray.set_menu(Some(menu))?;
                 tray.set_tooltip(Some("TypeFree"))?;
                 let _ = tray.set_show_menu_on_left_click(false);
+
+                // Recording/processing indicator.
+                tray::init_tray_state(app.handle());
             } else {
                 eprintln!("[tray] main tray icon not found; tray menu was not attached");
             }
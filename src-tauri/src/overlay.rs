@@ -1,9 +1,10 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
 
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Emitter, LogicalPosition, Manager, Position, Size, WebviewUrl};
 
-#[cfg(target_os = "macos")]
-use tauri::{LogicalPosition, Position, Size, WebviewUrl};
+#[cfg(not(target_os = "macos"))]
+use tauri::WebviewWindowBuilder;
 
 #[cfg(target_os = "macos")]
 use objc2::exception;
@@ -28,19 +29,80 @@ tauri_panel! {
     })
 }
 
-#[derive(Clone, Copy, Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OverlayState {
     Recording,
+    Paused,
     Transcribing,
-    Processing,
+    /// A processing step after recording (`finalizing-audio`, `uploading`, ...), sent
+    /// to the renderer as `{ "progress": stage }`.
+    Progress(String),
+    /// Shown only for a short notice (see `flash_overlay_notice`).
+    Notice,
+}
+
+/// `show-overlay` payload. `locked` is set while a push-to-talk session has been locked by
+/// a double tap, so the UI can tell it apart from a held key.
+#[derive(Clone, Debug, serde::Serialize)]
+struct OverlayPayload {
+    state: OverlayState,
+    locked: bool,
+}
+
+static RECORDING_LOCKED: AtomicBool = AtomicBool::new(false);
+
+// Bumped whenever the overlay is shown, updated or hidden, so a notice only hides the
+// overlay if nothing else has used it since.
+static OVERLAY_SEQ: AtomicU64 = AtomicU64::new(0);
+
+const NOTICE_DURATION: Duration = Duration::from_millis(1200);
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OverlayNoticePayload<'a> {
+    text: &'a str,
+    duration_ms: u64,
+}
+
+fn overlay_payload(state: &OverlayState) -> OverlayPayload {
+    OverlayPayload {
+        state: state.clone(),
+        locked: RECORDING_LOCKED.load(Ordering::Relaxed),
+    }
+}
+
+/// Where the overlay sits within the work area (`overlayPosition` setting).
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverlayAnchor {
+    BottomCenter,
+    BottomLeft,
+    BottomRight,
+    TopCenter,
+    TopLeft,
+    TopRight,
+    /// `overlayOffsetX`/`overlayOffsetY` from the top-left of the work area.
+    Custom,
+}
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayPositionConfig {
+    pub position: OverlayAnchor,
+    pub offset_x: f64,
+    pub offset_y: f64,
 }
 
 const OVERLAY_WINDOW_LABEL: &str = "recording_overlay";
 
 const OVERLAY_WIDTH: f64 = 420.0;
 const OVERLAY_HEIGHT: f64 = 56.0;
-const OVERLAY_BOTTOM_OFFSET: f64 = 6.0;
+const OVERLAY_EDGE_OFFSET: f64 = 6.0;
+// The overlay grows with partial transcript text up to this height, then scrolls.
+const MAX_OVERLAY_HEIGHT: f64 = 200.0;
+const OVERLAY_LINE_HEIGHT: f64 = 18.0;
+const OVERLAY_TEXT_WIDTH: f64 = OVERLAY_WIDTH - 96.0;
 
 #[cfg(target_os = "macos")]
 fn create_overlay_panel_window(app: &AppHandle) {
@@ -119,7 +181,55 @@ fn create_overlay_panel_window(app: &AppHandle) {
     }
 }
 
-#[cfg(target_os = "macos")]
+// Windows/Linux: a regular borderless, always-on-top webview window rendering the same UI.
+#[cfg(not(target_os = "macos"))]
+fn create_overlay_panel_window(app: &AppHandle) {
+    if app.get_webview_window(OVERLAY_WINDOW_LABEL).is_some() {
+        return;
+    }
+
+    let (x, y) = calculate_overlay_position(app).unwrap_or_else(|| {
+        eprintln!("[overlay] could not determine initial position; using fallback");
+        (100.0, 100.0)
+    });
+
+    let built = WebviewWindowBuilder::new(
+        app,
+        OVERLAY_WINDOW_LABEL,
+        WebviewUrl::App("?overlay=true".into()),
+    )
+    .title("Recording")
+    .inner_size(OVERLAY_WIDTH, OVERLAY_HEIGHT)
+    .position(x, y)
+    .decorations(false)
+    .transparent(true)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .shadow(false)
+    .focused(false)
+    .visible(false)
+    .build();
+
+    match built {
+        Ok(window) => {
+            // The overlay is display-only; let clicks reach whatever is underneath.
+            let _ = window.set_ignore_cursor_events(true);
+            #[cfg(target_os = "windows")]
+            {
+                let _ = window.set_always_on_top(true);
+            }
+            eprintln!(
+                "[overlay] overlay window created ({})",
+                OVERLAY_WINDOW_LABEL
+            );
+        }
+        Err(err) => {
+            eprintln!("[overlay] failed to create overlay window: {}", err);
+        }
+    }
+}
+
 fn get_monitor_with_cursor(app: &AppHandle) -> Option<tauri::Monitor> {
     let cursor = app.cursor_position().ok();
     if let Some(cursor) = cursor {
@@ -131,11 +241,40 @@ fn get_monitor_with_cursor(app: &AppHandle) -> Option<tauri::Monitor> {
     app.primary_monitor().ok().flatten()
 }
 
-// Returns logical (point) coordinates.
-#[cfg(target_os = "macos")]
-fn calculate_overlay_position(app: &AppHandle) -> Option<(f64, f64)> {
-    let monitor = get_monitor_with_cursor(app)?;
+fn get_setting_f64(app: &AppHandle, key: &str) -> Option<f64> {
+    crate::commands::settings::get_setting(app.clone(), key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_f64())
+}
 
+fn read_overlay_position_config(app: &AppHandle) -> OverlayPositionConfig {
+    let position = crate::commands::settings::get_setting(app.clone(), "overlayPosition".into())
+        .ok()
+        .flatten()
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+    let position = match position.as_deref() {
+        Some("bottom-left") => OverlayAnchor::BottomLeft,
+        Some("bottom-right") => OverlayAnchor::BottomRight,
+        Some("top-center") => OverlayAnchor::TopCenter,
+        Some("top-left") => OverlayAnchor::TopLeft,
+        Some("top-right") => OverlayAnchor::TopRight,
+        Some("custom") => OverlayAnchor::Custom,
+        _ => OverlayAnchor::BottomCenter,
+    };
+
+    OverlayPositionConfig {
+        position,
+        offset_x: get_setting_f64(app, "overlayOffsetX").unwrap_or(0.0),
+        offset_y: get_setting_f64(app, "overlayOffsetY").unwrap_or(0.0),
+    }
+}
+
+// Returns logical (point) coordinates.
+fn calculate_overlay_position_for_config(
+    monitor: &tauri::Monitor,
+    config: &OverlayPositionConfig,
+) -> (f64, f64) {
     let work_area = monitor.work_area();
     let scale = monitor.scale_factor();
 
@@ -145,28 +284,133 @@ fn calculate_overlay_position(app: &AppHandle) -> Option<(f64, f64)> {
     let work_area_x = work_area.position.x as f64 / scale;
     let work_area_y = work_area.position.y as f64 / scale;
 
-    let x = work_area_x + (work_area_width - OVERLAY_WIDTH) / 2.0;
-    let y = work_area_y + work_area_height - OVERLAY_HEIGHT - OVERLAY_BOTTOM_OFFSET;
+    let left = work_area_x + OVERLAY_EDGE_OFFSET;
+    let center = work_area_x + (work_area_width - OVERLAY_WIDTH) / 2.0;
+    let right = work_area_x + work_area_width - OVERLAY_WIDTH - OVERLAY_EDGE_OFFSET;
+    let top = work_area_y + OVERLAY_EDGE_OFFSET;
+    let bottom = work_area_y + work_area_height - OVERLAY_HEIGHT - OVERLAY_EDGE_OFFSET;
+
+    match config.position {
+        OverlayAnchor::BottomCenter => (center, bottom),
+        OverlayAnchor::BottomLeft => (left, bottom),
+        OverlayAnchor::BottomRight => (right, bottom),
+        OverlayAnchor::TopCenter => (center, top),
+        OverlayAnchor::TopLeft => (left, top),
+        OverlayAnchor::TopRight => (right, top),
+        OverlayAnchor::Custom => (work_area_x + config.offset_x, work_area_y + config.offset_y),
+    }
+}
+
+fn calculate_overlay_position(app: &AppHandle) -> Option<(f64, f64)> {
+    let monitor = get_monitor_with_cursor(app)?;
+    Some(calculate_overlay_position_for_config(
+        &monitor,
+        &read_overlay_position_config(app),
+    ))
+}
 
-    Some((x, y))
+// Rough text metrics: CJK glyphs are about twice as wide as Latin ones.
+fn overlay_height_for_text(text: &str) -> f64 {
+    let text_width: f64 = text
+        .chars()
+        .map(|c| if c.is_ascii() { 7.0 } else { 13.0 })
+        .sum();
+    let lines = (text_width / OVERLAY_TEXT_WIDTH).ceil().max(1.0);
+    (OVERLAY_HEIGHT + (lines - 1.0) * OVERLAY_LINE_HEIGHT).min(MAX_OVERLAY_HEIGHT)
 }
 
-pub fn init_recording_overlay(app: &AppHandle) {
-    // Best-effort: keep dictation working even if overlay fails.
+fn resize_recording_overlay(app: &AppHandle, height: f64) {
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return;
+    };
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+
+    // Bottom-anchored overlays grow upwards so they stay clear of the screen edge.
+    let grows_up = matches!(
+        read_overlay_position_config(app).position,
+        OverlayAnchor::BottomCenter | OverlayAnchor::BottomLeft | OverlayAnchor::BottomRight
+    );
+    let pos = calculate_overlay_position(app).map(|(x, y)| {
+        if grows_up {
+            (x, y - (height - OVERLAY_HEIGHT))
+        } else {
+            (x, y)
+        }
+    });
+
+    let _ = window.set_size(Size::Logical(tauri::LogicalSize {
+        width: OVERLAY_WIDTH,
+        height,
+    }));
+    if let Some((x, y)) = pos {
+        let _ = window.set_position(Position::Logical(LogicalPosition { x, y }));
+    }
+
+    // Resizing can drop the panel back to a normal window level; re-assert it.
     #[cfg(target_os = "macos")]
     {
-        create_overlay_panel_window(app);
+        let window_for_mt = window.clone();
+        let result = window.run_on_main_thread(move || {
+            let protected = exception::catch(AssertUnwindSafe(|| {
+                if let Ok(panel) = window_for_mt
+                    .app_handle()
+                    .get_webview_panel(OVERLAY_WINDOW_LABEL)
+                {
+                    panel.set_level(PanelLevel::Status.value());
+                    panel.set_collection_behavior(
+                        CollectionBehavior::new()
+                            .can_join_all_spaces()
+                            .full_screen_auxiliary()
+                            .value(),
+                    );
+                }
+            }));
+            if let Err(exc) = protected {
+                eprintln!("[overlay] objc exception during resize: {:?}", exc);
+            }
+        });
+        if let Err(err) = result {
+            eprintln!("[overlay] run_on_main_thread(resize) failed: {}", err);
+        }
     }
+}
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        // On Windows/Linux the main window renders the App UI (floating mic button).
-        // Ensure it is positioned and visible so it can receive toggle-dictation events.
-        let _ = app; // no-op init; window is statically declared in tauri.conf.json.
+/// Show streaming transcript text in the overlay, growing it to fit.
+pub fn show_partial_transcript(app: &AppHandle, text: &str) {
+    crate::commands::dictation_session::emit(
+        app,
+        "backend-dictation-partial",
+        crate::commands::dictation_session::Text { text },
+    );
+    resize_recording_overlay(app, overlay_height_for_text(text));
+}
+
+/// Called after `overlayPosition`/`overlayOffsetX`/`overlayOffsetY` change: notify the
+/// renderer and move a visible overlay right away.
+pub fn overlay_position_changed(app: &AppHandle) {
+    let config = read_overlay_position_config(app);
+    let _ = app.emit("backend-overlay-position-changed", config);
+
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return;
+    };
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+    if let Some((x, y)) = calculate_overlay_position(app) {
+        let _ = window.set_position(Position::Logical(LogicalPosition { x, y }));
     }
 }
 
+pub fn init_recording_overlay(app: &AppHandle) {
+    // Best-effort: keep dictation working even if overlay fails.
+    create_overlay_panel_window(app);
+}
+
 pub fn show_recording_overlay(app: &AppHandle, state: OverlayState) {
+    OVERLAY_SEQ.fetch_add(1, Ordering::Relaxed);
     #[cfg(target_os = "macos")]
     {
         if app.get_webview_window(OVERLAY_WINDOW_LABEL).is_none() {
@@ -189,7 +433,9 @@ pub fn show_recording_overlay(app: &AppHandle, state: OverlayState) {
         let pos = calculate_overlay_position(app);
 
         let window_for_mt = window.clone();
+        let state_for_mt = state.clone();
         let result = window.run_on_main_thread(move || {
+            let state = state_for_mt;
             // ObjC exceptions MUST be caught before they reach tao/tauri catch_unwind wrappers,
             // otherwise the process aborts ("Rust cannot catch foreign exceptions").
             let protected = exception::catch(AssertUnwindSafe(|| {
@@ -222,7 +468,7 @@ pub fn show_recording_overlay(app: &AppHandle, state: OverlayState) {
                 // catches ObjC exceptions.
                 crate::commands::window::promote_webview_window_for_fullscreen(&window_for_mt);
 
-                let _ = window_for_mt.emit("show-overlay", state);
+                let _ = window_for_mt.emit("show-overlay", overlay_payload(&state));
             }));
 
             if let Err(exc) = protected {
@@ -244,20 +490,113 @@ pub fn show_recording_overlay(app: &AppHandle, state: OverlayState) {
         let window_for_retry = window.clone();
         tauri::async_runtime::spawn(async move {
             tokio::time::sleep(Duration::from_millis(150)).await;
-            let _ = window_for_retry.emit("show-overlay", state);
+            let _ = window_for_retry.emit("show-overlay", overlay_payload(&state));
         });
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        // On Windows/Linux, the main window renders the App component with recording UI.
-        // Show the main window and let the frontend handle the recording state.
-        eprintln!("[overlay] show {:?} (windows/linux)", state);
-        let _ = crate::commands::window::reveal_main_window(app);
+        if app.get_webview_window(OVERLAY_WINDOW_LABEL).is_none() {
+            create_overlay_panel_window(app);
+        }
+
+        let window = match app.get_webview_window(OVERLAY_WINDOW_LABEL) {
+            Some(window) => window,
+            None => {
+                eprintln!(
+                    "[overlay] overlay window '{}' not found; skipping show",
+                    OVERLAY_WINDOW_LABEL
+                );
+                return;
+            }
+        };
+
+        // Reposition each time in case user is on a different monitor.
+        if let Some((x, y)) = calculate_overlay_position(app) {
+            eprintln!("[overlay] show {:?} at ({:.1}, {:.1})", state, x, y);
+            let _ = window.set_position(Position::Logical(LogicalPosition { x, y }));
+        } else {
+            eprintln!("[overlay] show {:?} (position unknown)", state);
+        }
+        let _ = window.set_size(Size::Logical(tauri::LogicalSize {
+            width: OVERLAY_WIDTH,
+            height: OVERLAY_HEIGHT,
+        }));
+
+        if let Err(err) = window.show() {
+            eprintln!("[overlay] failed to show overlay window: {}", err);
+        }
+        // Windows drops the topmost flag when the window is hidden; re-assert it on show.
+        #[cfg(target_os = "windows")]
+        {
+            let _ = window.set_always_on_top(true);
+        }
+
+        let _ = window.emit("show-overlay", overlay_payload(&state));
+
+        let window_for_retry = window.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            let _ = window_for_retry.emit("show-overlay", overlay_payload(&state));
+        });
+    }
+}
+
+/// Switch the overlay's displayed state without showing it if it is currently hidden.
+pub fn update_recording_overlay(app: &AppHandle, state: OverlayState) {
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return;
+    };
+    if window.is_visible().unwrap_or(false) {
+        OVERLAY_SEQ.fetch_add(1, Ordering::Relaxed);
+        eprintln!("[overlay] update {:?}", state);
+        let _ = window.emit("show-overlay", overlay_payload(&state));
     }
 }
 
+/// Mark the current recording as locked (double tap in push-to-talk) or held.
+pub fn set_recording_locked(app: &AppHandle, locked: bool) {
+    let was_locked = RECORDING_LOCKED.swap(locked, Ordering::Relaxed);
+    // Unlocking happens as the session ends; the next state shown carries the flag.
+    if locked && !was_locked {
+        update_recording_overlay(app, OverlayState::Recording);
+    }
+}
+
+/// Briefly show a short notice, e.g. the dictation language after cycling it. A visible
+/// overlay shows it in place; a hidden one pops up for `NOTICE_DURATION`.
+pub fn flash_overlay_notice(app: &AppHandle, text: &str) {
+    if app.get_webview_window(OVERLAY_WINDOW_LABEL).is_none() {
+        create_overlay_panel_window(app);
+    }
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return;
+    };
+    let was_visible = window.is_visible().unwrap_or(false);
+    let _ = window.emit(
+        "overlay-notice",
+        OverlayNoticePayload {
+            text,
+            duration_ms: NOTICE_DURATION.as_millis() as u64,
+        },
+    );
+    if was_visible {
+        return;
+    }
+
+    show_recording_overlay(app, OverlayState::Notice);
+    let seq = OVERLAY_SEQ.load(Ordering::Relaxed);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(NOTICE_DURATION).await;
+        if OVERLAY_SEQ.load(Ordering::Relaxed) == seq {
+            hide_recording_overlay(&app);
+        }
+    });
+}
+
 pub fn hide_recording_overlay(app: &AppHandle) {
+    OVERLAY_SEQ.fetch_add(1, Ordering::Relaxed);
     #[cfg(target_os = "macos")]
     {
         let window = match app.get_webview_window(OVERLAY_WINDOW_LABEL) {
@@ -303,8 +642,18 @@ pub fn hide_recording_overlay(app: &AppHandle) {
 
     #[cfg(not(target_os = "macos"))]
     {
-        // On Windows/Linux, the main window handles hiding itself after transcription completes
-        // (via the frontend onTranscriptionComplete callback in useAudioRecording).
-        eprintln!("[overlay] hide (windows/linux) — delegated to frontend");
+        let window = match app.get_webview_window(OVERLAY_WINDOW_LABEL) {
+            Some(window) => window,
+            None => return,
+        };
+
+        eprintln!("[overlay] hide");
+
+        // Let the renderer run a fade-out animation before hiding the window.
+        let _ = window.emit("hide-overlay", ());
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let _ = window.hide();
+        });
     }
 }
@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::image::Image;
+use tauri::{AppHandle, Listener};
+
+const TRAY_ID: &str = "main";
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static PROCESSING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+enum TrayState {
+    Idle,
+    Recording,
+    Processing,
+}
+
+impl TrayState {
+    fn current() -> Self {
+        if RECORDING.load(Ordering::SeqCst) {
+            Self::Recording
+        } else if PROCESSING.load(Ordering::SeqCst) {
+            Self::Processing
+        } else {
+            Self::Idle
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn icon(self) -> Image<'static> {
+        match self {
+            Self::Idle => tauri::include_image!("icons/tray-idle.png"),
+            Self::Recording => tauri::include_image!("icons/tray-recording.png"),
+            Self::Processing => tauri::include_image!("icons/tray-processing.png"),
+        }
+    }
+
+    /// Nothing recolours template images outside macOS, so the white idle and processing
+    /// icons would vanish on light taskbars: use the app icon and a coloured variant.
+    #[cfg(not(target_os = "macos"))]
+    fn icon(self) -> Image<'static> {
+        match self {
+            Self::Idle => tauri::include_image!("icons/32x32.png"),
+            Self::Recording => tauri::include_image!("icons/tray-recording.png"),
+            Self::Processing => tauri::include_image!("icons/tray-processing-color.png"),
+        }
+    }
+
+    /// The red recording icon keeps its color; the others are macOS template images so
+    /// they follow the menu bar's light/dark appearance.
+    fn is_template(self) -> bool {
+        cfg!(target_os = "macos") && !matches!(self, Self::Recording)
+    }
+}
+
+fn apply_tray_state(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let state = TrayState::current();
+    if let Err(err) = tray.set_icon(Some(state.icon())) {
+        eprintln!("[tray] failed to set {:?} icon: {}", state, err);
+        return;
+    }
+    let _ = tray.set_icon_as_template(state.is_template());
+}
+
+/// Track `backend-dictation-{recording,processing}` and swap the tray icon to match.
+pub fn init_tray_state(app: &AppHandle) {
+    apply_tray_state(app);
+
+    for (event, flag) in [
+        ("backend-dictation-recording", &RECORDING),
+        ("backend-dictation-processing", &PROCESSING),
+    ] {
+        let app_handle = app.clone();
+        app.listen_any(event, move |event| {
+            let active = serde_json::from_str::<serde_json::Value>(event.payload())
+                .ok()
+                .and_then(|payload| payload.get("active").and_then(|v| v.as_bool()))
+                .unwrap_or(false);
+            if flag.swap(active, Ordering::SeqCst) != active {
+                apply_tray_state(&app_handle);
+            }
+        });
+    }
+}
@@ -35,6 +35,7 @@ import {
   clearTranscriptions as clearStoreTranscriptions,
 } from "../stores/transcriptionStore";
 import type { TranscriptionItem as TranscriptionItemType } from "../types/electron";
+import { getDictationStats, type DictationStats } from "../utils/tauriAPI";
 
 type NavigationSection = SettingsSectionType | "history";
 
@@ -157,6 +158,8 @@ function parseClipboardOnlyMode(): boolean {
 
 export default function ControlPanel() {
   const history = useTranscriptions();
+  // Aggregates over the whole database; the loaded history may be truncated.
+  const [dictationStats, setDictationStats] = useState<DictationStats | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [activeSection, setActiveSection] = useState<NavigationSection>(() =>
     parseInitialSection()
@@ -224,11 +227,15 @@ export default function ControlPanel() {
 
     history.forEach((item) => {
       totalCharacters += item.text?.length || 0;
+      if (dictationStats) return;
       const date = parseHistoryDate(item);
       if (!date) return;
       const key = toDateKey(date);
       dailyCounts[key] = (dailyCounts[key] || 0) + 1;
     });
+    dictationStats?.by_day.forEach((bucket) => {
+      dailyCounts[bucket.day] = bucket.sessions;
+    });
 
     const daysUsed = Object.keys(dailyCounts).length;
     const saved = formatDurationParts(totalCharacters * 0.67, t);
@@ -236,11 +243,11 @@ export default function ControlPanel() {
     return {
       dailyCounts,
       daysUsed,
-      totalSessions: history.length,
+      totalSessions: dictationStats?.sessions ?? history.length,
       totalCharacters,
       saved,
     };
-  }, [history, t]);
+  }, [history, dictationStats, t]);
 
   const heatmapWeeks = useMemo(() => {
     const today = startOfLocalDay(new Date());
@@ -279,6 +286,16 @@ export default function ControlPanel() {
     loadTranscriptions();
   }, []);
 
+  useEffect(() => {
+    let cancelled = false;
+    getDictationStats().then((stats) => {
+      if (!cancelled && stats) setDictationStats(stats);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [history.length]);
+
   useEffect(() => {
     let unlistenClipboardPanel: undefined | (() => void);
     let unlistenControlPanel: undefined | (() => void);
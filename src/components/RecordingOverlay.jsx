@@ -39,36 +39,80 @@ function PushingText({ text }) {
   );
 }
 
-function labelForState(state) {
+const PROGRESS_LABELS = {
+  "finalizing-audio": "收尾中",
+  uploading: "上传中",
+  "waiting-for-provider": "转写中",
+  "post-processing": "优化中",
+  pasting: "粘贴中",
+};
+
+function labelForState(state, locked, progress) {
+  if (state === "progress") {
+    const label = PROGRESS_LABELS[progress.stage] || "处理中";
+    return progress.stage === "uploading" && progress.percent != null
+      ? `${label} ${progress.percent}%`
+      : label;
+  }
   if (state === "processing") return "优化中";
   if (state === "transcribing") return "转写中";
-  if (state === "recording") return "录音中";
+  if (state === "recording") return locked ? "录音中（已锁定）" : "录音中";
   return "Ready";
 }
 
 export default function RecordingOverlay() {
   const [state, setState] = useState("idle");
+  // Push-to-talk session locked on by a double tap (vs. held key).
+  const [locked, setLocked] = useState(false);
   const [visible, setVisible] = useState(false);
   const [liveText, setLiveText] = useState("");
+  // Streaming transcript shown while transcribing; the backend grows the window to fit.
+  const [partialText, setPartialText] = useState("");
+  // Short notice from the backend, e.g. the language after cycling it.
+  const [notice, setNotice] = useState("");
+  // Processing step while `state === "progress"`, with upload percentage when known.
+  const [progress, setProgress] = useState({ stage: "", percent: null });
+  const noticeTimerRef = useRef(null);
+  const partialScrollRef = useRef(null);
   const lastRecordingRef = useRef(false);
 
   useEffect(() => {
     let unlistenShow = null;
     let unlistenHide = null;
+    let unlistenNotice = null;
 
     (async () => {
       try {
         const { listen } = await import("@tauri-apps/api/event");
         unlistenShow = await listen("show-overlay", (event) => {
-          const next = String(event?.payload || "idle").toLowerCase();
+          // Payload is `{ state, locked }`; older builds sent the bare state string.
+          // Processing steps arrive as `state: { progress: stage }`.
+          const payload = event?.payload;
+          const rawState = payload && typeof payload === "object" ? payload.state : payload;
+          const stage = rawState && typeof rawState === "object" ? String(rawState.progress) : "";
+          const next = stage ? "progress" : String(rawState || "idle").toLowerCase();
           setState(next);
+          setProgress((prev) => (prev.stage === stage ? prev : { stage, percent: null }));
+          setLocked(Boolean(payload?.locked));
           if (next === "recording") {
             setLiveText("");
+            setPartialText("");
           }
           setVisible(true);
         });
         unlistenHide = await listen("hide-overlay", () => {
           setVisible(false);
+          setPartialText("");
+        });
+        unlistenNotice = await listen("overlay-notice", (event) => {
+          const text = String(event?.payload?.text || "").trim();
+          if (!text) return;
+          setNotice(text);
+          clearTimeout(noticeTimerRef.current);
+          noticeTimerRef.current = setTimeout(
+            () => setNotice(""),
+            Number(event?.payload?.durationMs) || 1200
+          );
         });
       } catch {
         console.warn("[overlay-ui] failed to register Tauri event listeners");
@@ -79,6 +123,8 @@ export default function RecordingOverlay() {
       try {
         unlistenShow?.();
         unlistenHide?.();
+        unlistenNotice?.();
+        clearTimeout(noticeTimerRef.current);
       } catch {
         // ignore
       }
@@ -90,6 +136,8 @@ export default function RecordingOverlay() {
     let unlistenRecording = null;
     let unlistenError = null;
     let unlistenStreaming = null;
+    let unlistenPartial = null;
+    let unlistenProgress = null;
 
     (async () => {
       try {
@@ -100,7 +148,7 @@ export default function RecordingOverlay() {
         });
 
         unlistenRecording = await listen("backend-dictation-recording", (event) => {
-          const next = Boolean(event?.payload);
+          const next = Boolean(event?.payload?.active);
           const prev = lastRecordingRef.current;
           lastRecordingRef.current = next;
           if (!next && prev) playStopSound();
@@ -114,6 +162,18 @@ export default function RecordingOverlay() {
           const text = String(event?.payload?.text || "").trim();
           if (text) setLiveText(text);
         });
+
+        unlistenPartial = await listen("backend-dictation-partial", (event) => {
+          const text = String(event?.payload?.text || "").trim();
+          if (text) setPartialText(text);
+        });
+
+        unlistenProgress = await listen("backend-dictation-progress", (event) => {
+          const { stage, detail } = event?.payload || {};
+          if (stage !== "uploading" || !detail?.totalBytes) return;
+          const percent = Math.round((detail.sentBytes / detail.totalBytes) * 100);
+          setProgress({ stage, percent });
+        });
       } catch {
         // ignore
       }
@@ -125,21 +185,39 @@ export default function RecordingOverlay() {
         unlistenRecording?.();
         unlistenError?.();
         unlistenStreaming?.();
+        unlistenPartial?.();
+        unlistenProgress?.();
       } catch {
         // ignore
       }
     };
   }, []);
 
-  const label = useMemo(() => labelForState(state), [state]);
+  useEffect(() => {
+    const el = partialScrollRef.current;
+    if (el) el.scrollTop = el.scrollHeight;
+  }, [partialText]);
+
+  const busy = state === "processing" || state === "transcribing" || state === "progress";
+  const transcribing =
+    state === "transcribing" ||
+    (state === "progress" &&
+      (progress.stage === "uploading" || progress.stage === "waiting-for-provider"));
+  const showPartial = transcribing && Boolean(partialText);
+  const label = useMemo(() => labelForState(state, locked, progress), [state, locked, progress]);
   const displayText = state === "recording" ? liveText : "";
   const textLength = Array.from(displayText || "").length;
-  const capsuleWidth =
-    state === "recording"
-      ? Math.min(360, Math.max(124, 78 + textLength * 8))
-      : state === "processing" || state === "transcribing"
-        ? 92
-        : 86;
+  const capsuleWidth = notice
+    ? Math.min(240, Math.max(92, 48 + Array.from(notice).length * 8))
+    : showPartial
+      ? 360
+      : state === "recording"
+        ? Math.min(360, Math.max(124, 78 + textLength * 8))
+        : state === "progress"
+          ? Math.max(92, 48 + Array.from(label).length * 12)
+          : busy
+            ? 92
+            : 86;
 
   return (
     <div
@@ -152,10 +230,10 @@ export default function RecordingOverlay() {
     >
       <div
         className={[
-          "relative h-8 overflow-hidden rounded-full border border-white/10 backdrop-blur-md",
-          state === "processing" || state === "transcribing"
-            ? "bg-neutral-700/90 px-5 text-white/70"
-            : "bg-neutral-950/95 px-1.5 text-white",
+          "relative overflow-hidden border backdrop-blur-md",
+          showPartial ? "max-h-full rounded-2xl py-2" : "h-8 rounded-full",
+          state === "recording" && locked ? "border-emerald-400/60" : "border-white/10",
+          busy ? "bg-neutral-700/90 px-5 text-white/70" : "bg-neutral-950/95 px-1.5 text-white",
           "flex items-center justify-center gap-1.5",
         ].join(" ")}
         style={{
@@ -163,11 +241,15 @@ export default function RecordingOverlay() {
           WebkitAppRegion: "no-drag",
           transition: "width 180ms ease, opacity 250ms ease",
         }}
-        role={state === "processing" || state === "transcribing" ? "status" : "group"}
-        aria-live={state === "processing" || state === "transcribing" ? "polite" : undefined}
+        role={busy ? "status" : "group"}
+        aria-live={busy ? "polite" : undefined}
         aria-label={label}
       >
-        {state === "recording" ? (
+        {notice ? (
+          <span className="relative z-10 text-xs font-semibold leading-none text-white/90">
+            {notice}
+          </span>
+        ) : state === "recording" ? (
           <>
             <GlyphCircle variant="cancel" />
             <span className="relative z-10 flex min-w-0 flex-1 items-center justify-center px-1">
@@ -175,9 +257,16 @@ export default function RecordingOverlay() {
             </span>
             <GlyphCircle variant="confirm" />
           </>
-        ) : state === "processing" || state === "transcribing" ? (
+        ) : showPartial ? (
+          <div
+            ref={partialScrollRef}
+            className="relative z-10 max-h-full w-full overflow-y-auto whitespace-pre-wrap break-words text-left text-xs font-medium leading-[18px] text-white/80"
+          >
+            {partialText}
+          </div>
+        ) : busy ? (
           <span className="relative z-10 text-xs font-semibold leading-none text-white/60">
-            {state === "processing" ? "优化中" : "转写中"}
+            {label}
           </span>
         ) : (
           <SoundWaveIcon size={12} />
@@ -19,6 +19,7 @@ import PromptStudio from "./ui/PromptStudio";
 import ReasoningModelSelector from "./ReasoningModelSelector";
 import ClipboardSettings from "./ClipboardSettings";
 import VocabularySettings from "./VocabularySettings";
+import TextSubstitutionSettings from "./TextSubstitutionSettings";
 import type { UpdateInfoResult } from "../types/electron";
 import { HotkeyInput } from "./ui/HotkeyInput";
 import { useHotkeyRegistration } from "../hooks/useHotkeyRegistration";
@@ -874,7 +875,12 @@ export default function SettingsPage({ activeSection = "general" }: SettingsPage
         return <ClipboardSettings />;
 
       case "vocabulary":
-        return <VocabularySettings />;
+        return (
+          <div className="space-y-7">
+            <VocabularySettings />
+            <TextSubstitutionSettings />
+          </div>
+        );
 
       case "aiModels":
         return (
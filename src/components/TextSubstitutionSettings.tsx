@@ -0,0 +1,212 @@
+import { useCallback, useEffect, useState } from "react";
+import { ArrowDown, ArrowUp, Plus, Trash2 } from "lucide-react";
+import { Button } from "./ui/button";
+import { Input } from "./ui/input";
+import {
+  getSetting,
+  setSetting,
+  testTextSubstitution,
+  type TextSubstitutionRule,
+} from "../utils/tauriAPI";
+import { useI18n } from "../i18n";
+
+const TEXT_SUBSTITUTION_RULES_KEY = "textSubstitutionRules";
+
+function normalizeRules(value: unknown): TextSubstitutionRule[] {
+  if (!Array.isArray(value)) return [];
+  return value
+    .filter((rule) => rule && typeof rule === "object")
+    .map((rule) => ({
+      pattern: String(rule.pattern ?? ""),
+      replacement: String(rule.replacement ?? ""),
+      flags: String(rule.flags ?? ""),
+    }))
+    .filter((rule) => rule.pattern);
+}
+
+export default function TextSubstitutionSettings() {
+  const { t } = useI18n();
+  const [rules, setRules] = useState<TextSubstitutionRule[]>([]);
+  const [draft, setDraft] = useState<TextSubstitutionRule>({
+    pattern: "",
+    replacement: "",
+    flags: "",
+  });
+  const [sample, setSample] = useState("");
+  const [preview, setPreview] = useState("");
+  const [previewError, setPreviewError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    void getSetting<unknown>(TEXT_SUBSTITUTION_RULES_KEY).then((stored) => {
+      if (!cancelled) setRules(normalizeRules(stored));
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  // Preview the saved rules plus the one being typed, without saving anything.
+  useEffect(() => {
+    if (!sample) {
+      setPreview("");
+      setPreviewError(null);
+      return;
+    }
+    let cancelled = false;
+    const candidate = draft.pattern ? [...rules, draft] : rules;
+    testTextSubstitution(sample, candidate)
+      .then((result) => {
+        if (cancelled) return;
+        setPreview(result);
+        setPreviewError(null);
+      })
+      .catch((error) => {
+        if (cancelled) return;
+        setPreview("");
+        setPreviewError(String(error));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [sample, rules, draft]);
+
+  const persist = useCallback(async (next: TextSubstitutionRule[]) => {
+    setRules(next);
+    await setSetting(TEXT_SUBSTITUTION_RULES_KEY, next);
+  }, []);
+
+  const addRule = async () => {
+    if (!draft.pattern) return;
+    try {
+      await testTextSubstitution("", [draft]);
+    } catch (error) {
+      setPreviewError(String(error));
+      return;
+    }
+    await persist([...rules, { ...draft, flags: draft.flags.trim() }]);
+    setDraft({ pattern: "", replacement: "", flags: "" });
+  };
+
+  const moveRule = (index: number, offset: number) => {
+    const target = index + offset;
+    if (target < 0 || target >= rules.length) return;
+    const next = [...rules];
+    [next[index], next[target]] = [next[target], next[index]];
+    void persist(next);
+  };
+
+  const removeRule = (index: number) => {
+    void persist(rules.filter((_, i) => i !== index));
+  };
+
+  return (
+    <section className="space-y-4 rounded-xl border border-neutral-200 bg-white p-5 shadow-sm">
+      <div>
+        <h4 className="text-sm font-semibold text-neutral-900">{t("substitutions.title")}</h4>
+        <p className="mt-1 text-xs text-neutral-500">{t("substitutions.desc")}</p>
+      </div>
+
+      <div className="rounded-xl bg-neutral-50 p-3">
+        <div className="mb-3 flex flex-wrap items-center gap-2">
+          <Input
+            value={draft.pattern}
+            onChange={(event) => setDraft({ ...draft, pattern: event.target.value })}
+            placeholder={t("substitutions.pattern")}
+            className="h-9 min-w-0 flex-1 border-dashed font-mono text-sm"
+          />
+          <Input
+            value={draft.replacement}
+            onChange={(event) => setDraft({ ...draft, replacement: event.target.value })}
+            onKeyDown={(event) => {
+              if (event.key === "Enter") void addRule();
+            }}
+            placeholder={t("substitutions.replacement")}
+            className="h-9 min-w-0 flex-1 border-dashed font-mono text-sm"
+          />
+          <Input
+            value={draft.flags}
+            onChange={(event) => setDraft({ ...draft, flags: event.target.value })}
+            placeholder={t("substitutions.flags")}
+            title={t("substitutions.flags.help")}
+            className="h-9 w-20 border-dashed font-mono text-sm"
+          />
+          <Button
+            size="icon"
+            variant="ghost"
+            onClick={() => void addRule()}
+            disabled={!draft.pattern}
+            title={t("substitutions.add")}
+          >
+            <Plus className="h-5 w-5 text-green-700" />
+          </Button>
+        </div>
+
+        <div className="rounded-lg bg-white">
+          {rules.length ? (
+            rules.map((rule, index) => (
+              <div
+                key={`${index}:${rule.pattern}`}
+                className="flex items-center gap-2 border-b border-neutral-100 px-3 py-2 last:border-b-0"
+              >
+                <span className="w-6 shrink-0 text-xs text-neutral-400">{index + 1}</span>
+                <code className="min-w-0 flex-1 truncate text-xs text-neutral-900">
+                  /{rule.pattern}/{rule.flags}
+                </code>
+                <code className="min-w-0 flex-1 truncate text-xs text-neutral-600">
+                  {rule.replacement || t("substitutions.empty")}
+                </code>
+                <Button
+                  size="icon"
+                  variant="ghost"
+                  onClick={() => moveRule(index, -1)}
+                  disabled={index === 0}
+                  title={t("substitutions.moveUp")}
+                >
+                  <ArrowUp className="h-4 w-4" />
+                </Button>
+                <Button
+                  size="icon"
+                  variant="ghost"
+                  onClick={() => moveRule(index, 1)}
+                  disabled={index === rules.length - 1}
+                  title={t("substitutions.moveDown")}
+                >
+                  <ArrowDown className="h-4 w-4" />
+                </Button>
+                <Button
+                  size="icon"
+                  variant="ghost"
+                  onClick={() => removeRule(index)}
+                  title={t("substitutions.remove")}
+                >
+                  <Trash2 className="h-4 w-4 text-red-600" />
+                </Button>
+              </div>
+            ))
+          ) : (
+            <p className="px-3 py-3 text-xs text-neutral-500">{t("substitutions.none")}</p>
+          )}
+        </div>
+      </div>
+
+      <div className="space-y-2">
+        <Input
+          value={sample}
+          onChange={(event) => setSample(event.target.value)}
+          placeholder={t("substitutions.test.placeholder")}
+          className="h-9 text-sm"
+        />
+        {previewError ? (
+          <p className="text-xs text-red-600">{previewError}</p>
+        ) : (
+          sample && (
+            <p className="whitespace-pre-wrap rounded-md bg-neutral-50 px-3 py-2 text-sm text-neutral-800">
+              {preview}
+            </p>
+          )
+        )}
+      </div>
+    </section>
+  );
+}
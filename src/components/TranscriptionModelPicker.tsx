@@ -12,6 +12,7 @@ import { getProviderIcon } from "../utils/providerIcons";
 import { normalizeBaseUrl } from "../config/constants";
 import { createExternalLinkHandler } from "../utils/externalLinks";
 import { useI18n } from "../i18n";
+import { validateApiKey } from "../utils/tauriAPI";
 
 interface TranscriptionModelPickerProps {
   selectedCloudProvider: string;
@@ -137,6 +138,10 @@ export default function TranscriptionModelPicker({
   const [connectionStatus, setConnectionStatus] = useState<"idle" | "success" | "error">("idle");
   const [connectionMessage, setConnectionMessage] = useState("");
   const [customFetchedModels, setCustomFetchedModels] = useState<ModelCardOption[]>([]);
+  const [keyTest, setKeyTest] = useState<{
+    status: "idle" | "testing" | "valid" | "invalid" | "error";
+    message: string;
+  }>({ status: "idle", message: "" });
   const [transcriptionPrompt, setTranscriptionPrompt] = useState(() => {
     try {
       return localStorage.getItem("transcriptionPrompt") || "";
@@ -416,6 +421,27 @@ export default function TranscriptionModelPicker({
     setZaiApiKey,
   ]);
 
+  useEffect(() => {
+    setKeyTest({ status: "idle", message: "" });
+  }, [draftProvider, selectedApiKey]);
+
+  const handleTestApiKey = useCallback(async () => {
+    setKeyTest({ status: "testing", message: "" });
+    try {
+      const valid = await validateApiKey(draftProvider);
+      setKeyTest(
+        valid
+          ? { status: "valid", message: t("transcription.testKey.valid") }
+          : { status: "invalid", message: t("transcription.testKey.invalid") }
+      );
+    } catch (error) {
+      setKeyTest({
+        status: "error",
+        message: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }, [draftProvider, t]);
+
   const saveTranscriptionPrompt = useCallback(() => {
     try {
       const trimmedPrompt = transcriptionPrompt.trim();
@@ -735,6 +761,30 @@ export default function TranscriptionModelPicker({
                 label=""
                 helpText=""
               />
+              <div className="flex items-center gap-2">
+                <Button
+                  type="button"
+                  variant="outline"
+                  size="sm"
+                  onClick={handleTestApiKey}
+                  disabled={keyTest.status === "testing" || !selectedApiKey.trim()}
+                  className="h-7 shrink-0 rounded-md border-neutral-200 px-2 text-[11px] shadow-none hover:border-neutral-300 hover:bg-neutral-50 [&_svg]:size-3"
+                >
+                  {keyTest.status === "testing" && (
+                    <Loader2 className="mr-1 h-3 w-3 animate-spin" aria-hidden="true" />
+                  )}
+                  {keyTest.status === "testing"
+                    ? t("transcription.testKey.testing")
+                    : t("transcription.testKey.button")}
+                </Button>
+                {keyTest.message && (
+                  <p
+                    className={`text-xs ${keyTest.status === "valid" ? "text-green-600" : "text-red-600"}`}
+                  >
+                    {keyTest.message}
+                  </p>
+                )}
+              </div>
             </div>
 
             <div className="pt-4 space-y-3">
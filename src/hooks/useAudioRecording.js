@@ -46,6 +46,8 @@ export const useAudioRecording = (toast, options = {}) => {
   const [transcript, setTranscript] = useState("");
   const [liveTranscript, setLiveTranscript] = useState("");
   const [audioLevel, setAudioLevel] = useState(0);
+  // Backend continuous mode: recording restarts after each transcription until stopped.
+  const [isContinuous, setIsContinuous] = useState(false);
   const audioManagerRef = useRef(null);
   const recordingFeedbackRef = useRef(false);
   const completionGuardRef = useRef(createCompletionGuard());
@@ -339,6 +341,20 @@ export const useAudioRecording = (toast, options = {}) => {
       })
     );
 
+    const disposeBackendCancelled = toCleanup(
+      window.electronAPI?.onBackendDictationCancelled?.(() => {
+        if (!isActiveToken(token)) return;
+        setLiveTranscript("");
+      })
+    );
+
+    const disposeBackendContinuous = toCleanup(
+      window.electronAPI?.onBackendDictationContinuousActive?.((value) => {
+        if (!isActiveToken(token)) return;
+        setIsContinuous(!!value);
+      })
+    );
+
     const disposeBackendProcessing = toCleanup(
       window.electronAPI?.onBackendDictationProcessing?.((value) => {
         if (!isActiveToken(token)) return;
@@ -355,6 +371,28 @@ export const useAudioRecording = (toast, options = {}) => {
       })
     );
 
+    const disposeBackendCopied = toCleanup(
+      window.electronAPI?.onBackendDictationCopied?.(() => {
+        if (!isActiveToken(token)) return;
+        toastRef.current?.({
+          title: "Copied to Clipboard",
+          description: "Paste mode is set to copy only.",
+          variant: "default",
+        });
+      })
+    );
+
+    const disposeBackendReadyToPaste = toCleanup(
+      window.electronAPI?.onBackendDictationReadyToPaste?.(() => {
+        if (!isActiveToken(token)) return;
+        toastRef.current?.({
+          title: "Ready to Paste",
+          description: "Auto-paste is off. The text is on your clipboard.",
+          variant: "default",
+        });
+      })
+    );
+
     const handleNoAudioDetected = () => {
       if (!isActiveToken(token)) return;
       toastRef.current?.({
@@ -391,8 +429,12 @@ export const useAudioRecording = (toast, options = {}) => {
       runCleanup(disposeBackendStartFeedback);
       runCleanup(disposeBackendError);
       runCleanup(disposeBackendRecording);
+      runCleanup(disposeBackendCancelled);
+      runCleanup(disposeBackendContinuous);
       runCleanup(disposeBackendProcessing);
       runCleanup(disposeBackendResult);
+      runCleanup(disposeBackendCopied);
+      runCleanup(disposeBackendReadyToPaste);
       disposeNoAudio?.();
       if (audioManagerRef.current) {
         audioManagerRef.current.cleanup();
@@ -452,6 +494,7 @@ export const useAudioRecording = (toast, options = {}) => {
   return {
     isRecording,
     isProcessing,
+    isContinuous,
     transcript,
     liveTranscript,
     audioLevel,
@@ -168,6 +168,25 @@ export const usePermissions = (
 
     setMicPermissionError(null);
 
+    // Native recording needs the app-level permission, which the webview prompt doesn't cover.
+    if (window.electronAPI?.requestMicrophonePermission) {
+      const granted = await window.electronAPI.requestMicrophonePermission();
+      if (!granted) {
+        const message = `Permission was denied. Open ${getPlatformPrivacyPath()} and allow Typefree.`;
+        setMicPermissionGranted(false);
+        setMicPermissionError(message);
+        if (showAlertDialog) {
+          showAlertDialog({
+            title: "Microphone Permission Required",
+            description: message,
+          });
+        } else {
+          alert(message);
+        }
+        return;
+      }
+    }
+
     try {
       const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
       stopTracks(stream);
@@ -217,16 +236,41 @@ export const usePermissions = (
     checkPasteToolsAvailability();
   }, [checkPasteToolsAvailability]);
 
+  // Reflect already-granted permissions without prompting
+  useEffect(() => {
+    let cancelled = false;
+    (async () => {
+      if ((await getPlatform()) !== "darwin") return;
+      const micStatus = await window.electronAPI?.checkMicrophonePermission?.();
+      if (!cancelled && micStatus === "granted") setMicPermissionGranted(true);
+      const granted = await window.electronAPI?.checkAccessibilityPermission?.(false);
+      if (!cancelled && granted) setAccessibilityPermissionGranted(true);
+    })();
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
   const testAccessibilityPermission = useCallback(async () => {
     const platform = await getPlatform();
 
     // On macOS, actually test the accessibility permission
     if (platform === "darwin") {
       try {
-        const granted = window.electronAPI.checkAccessibilityPermission
-          ? await window.electronAPI.checkAccessibilityPermission(true)
+        let granted = window.electronAPI.checkAccessibilityPermission
+          ? await window.electronAPI.checkAccessibilityPermission(false)
           : false;
 
+        // Let macOS show its own prompt first; fall back to opening System Settings.
+        if (!granted && window.electronAPI.requestAccessibilityPermission) {
+          await window.electronAPI.requestAccessibilityPermission();
+          granted = window.electronAPI.checkAccessibilityPermission
+            ? await window.electronAPI.checkAccessibilityPermission(false)
+            : false;
+        } else if (!granted && window.electronAPI.checkAccessibilityPermission) {
+          granted = await window.electronAPI.checkAccessibilityPermission(true);
+        }
+
         if (granted) {
           setAccessibilityPermissionGranted(true);
           if (showAlertDialog) {
@@ -4,7 +4,14 @@ import { useDebouncedCallback } from "./useDebouncedCallback";
 import { getModelProvider } from "../models/ModelRegistry";
 import { API_ENDPOINTS } from "../config/constants";
 import ReasoningService from "../services/ReasoningService";
-import { getSetting, setSetting } from "../utils/tauriAPI";
+import {
+  getApiKey,
+  getSetting,
+  onDictationLanguageChanged,
+  onEnvVarChanged,
+  onSettingChanged,
+  setSetting,
+} from "../utils/tauriAPI";
 import {
   DEFAULT_PROCESSING_MODE_ID,
   PROCESSING_MODE_STORAGE_KEY,
@@ -14,6 +21,25 @@ import { syncVocabularySettingsToBackend } from "../utils/vocabulary";
 
 const VOLCENGINE_ASR2_MODEL = "volcengine-bigmodel-async";
 
+/** Reasoning providers whose cached key belongs to each stored API key. */
+const REASONING_KEY_PROVIDERS: Record<
+  string,
+  Parameters<typeof ReasoningService.clearApiKeyCache>[0]
+> = {
+  OPENAI_API_KEY: "openai",
+  ANTHROPIC_API_KEY: "anthropic",
+  GEMINI_API_KEY: "gemini",
+  GROQ_API_KEY: "groq",
+  DEEPSEEK_API_KEY: "deepseek",
+  CUSTOM_REASONING_API_KEY: "openai",
+};
+
+const isProcessingModeId = (value: unknown): value is ProcessingModeId =>
+  value === "direct" ||
+  value === "voice-polish" ||
+  value === "translate-en" ||
+  value === "prompt-optimize";
+
 export interface TranscriptionSettings {
   preferredLanguage: string;
   cloudTranscriptionProvider: string;
@@ -106,6 +132,22 @@ export function useSettings() {
     void setSetting("preferredLanguage", preferredLanguage);
   }, [preferredLanguage]);
 
+  // The cycle-language hotkey changes the language from the backend; keep it in sync here.
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+    void onDictationLanguageChanged((language) => {
+      if (language) setPreferredLanguage(language);
+    }).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [setPreferredLanguage]);
+
   useEffect(() => {
     void setSetting("cloudTranscriptionProvider", cloudTranscriptionProvider);
   }, [cloudTranscriptionProvider]);
@@ -133,13 +175,7 @@ export function useSettings() {
     DEFAULT_PROCESSING_MODE_ID,
     {
       serialize: String,
-      deserialize: (value) =>
-        value === "direct" ||
-        value === "voice-polish" ||
-        value === "translate-en" ||
-        value === "prompt-optimize"
-          ? value
-          : DEFAULT_PROCESSING_MODE_ID,
+      deserialize: (value) => (isProcessingModeId(value) ? value : DEFAULT_PROCESSING_MODE_ID),
     }
   );
 
@@ -263,6 +299,55 @@ export function useSettings() {
     }
   );
 
+  // An API key changed (possibly in another window): re-read it, and drop the cached copy
+  // so the next reasoning request uses and validates the new one.
+  useEffect(() => {
+    const localSetters: Record<string, (value: string) => void> = {
+      OPENAI_API_KEY: setOpenaiApiKeyLocal,
+      ASSEMBLYAI_API_KEY: setAssemblyaiApiKeyLocal,
+      ANTHROPIC_API_KEY: setAnthropicApiKeyLocal,
+      GEMINI_API_KEY: setGeminiApiKeyLocal,
+      GROQ_API_KEY: setGroqApiKeyLocal,
+      DEEPSEEK_API_KEY: setDeepseekApiKeyLocal,
+      ZAI_API_KEY: setZaiApiKeyLocal,
+      VOLCENGINE_APP_ID: setVolcengineAppIdLocal,
+      VOLCENGINE_ACCESS_TOKEN: setVolcengineAccessTokenLocal,
+      VOLCENGINE_RESOURCE_ID: setVolcengineResourceIdLocal,
+      CUSTOM_REASONING_API_KEY: setCustomReasoningApiKeyLocal,
+    };
+
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+    void onEnvVarChanged((key) => {
+      const provider = REASONING_KEY_PROVIDERS[key];
+      if (provider) ReasoningService.clearApiKeyCache(provider);
+      const setLocal = localSetters[key];
+      if (!setLocal) return;
+      void getApiKey(key).then((value) => {
+        if (!disposed) setLocal(value ?? "");
+      });
+    }).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [
+    setOpenaiApiKeyLocal,
+    setAssemblyaiApiKeyLocal,
+    setAnthropicApiKeyLocal,
+    setGeminiApiKeyLocal,
+    setGroqApiKeyLocal,
+    setDeepseekApiKeyLocal,
+    setZaiApiKeyLocal,
+    setVolcengineAppIdLocal,
+    setVolcengineAccessTokenLocal,
+    setVolcengineResourceIdLocal,
+    setCustomReasoningApiKeyLocal,
+  ]);
+
   // Sync settings and API keys from main process on first mount (if localStorage was cleared)
   const hasRunApiKeySync = useRef(false);
   useEffect(() => {
@@ -299,11 +384,11 @@ export function useSettings() {
         if (envKey) setGroqApiKeyLocal(envKey);
       }
       if (!deepseekApiKey) {
-        const envKey = await window.electronAPI.getEnvVar?.("DEEPSEEK_API_KEY");
+        const envKey = await window.electronAPI.getApiKey?.("DEEPSEEK_API_KEY");
         if (envKey) setDeepseekApiKeyLocal(envKey);
       }
       if (!customReasoningApiKey) {
-        const envKey = await window.electronAPI.getEnvVar?.("CUSTOM_REASONING_API_KEY");
+        const envKey = await window.electronAPI.getApiKey?.("CUSTOM_REASONING_API_KEY");
         if (envKey) setCustomReasoningApiKeyLocal(envKey);
       }
       if (!zaiApiKey) {
@@ -391,7 +476,7 @@ export function useSettings() {
   const setDeepseekApiKey = useCallback(
     (key: string) => {
       setDeepseekApiKeyLocal(key);
-      window.electronAPI?.setEnvVar?.("DEEPSEEK_API_KEY", key);
+      window.electronAPI?.setApiKey?.("DEEPSEEK_API_KEY", key);
       ReasoningService.clearApiKeyCache("deepseek");
       debouncedPersistToEnv();
     },
@@ -437,7 +522,7 @@ export function useSettings() {
   const setCustomReasoningApiKey = useCallback(
     (key: string) => {
       setCustomReasoningApiKeyLocal(key);
-      window.electronAPI?.setEnvVar?.("CUSTOM_REASONING_API_KEY", key);
+      window.electronAPI?.setApiKey?.("CUSTOM_REASONING_API_KEY", key);
       ReasoningService.clearApiKeyCache("openai");
       debouncedPersistToEnv();
     },
@@ -537,16 +622,87 @@ export function useSettings() {
 
   useEffect(() => {
     if (deepseekApiKey.trim()) {
-      void window.electronAPI?.setEnvVar?.("DEEPSEEK_API_KEY", deepseekApiKey);
+      void window.electronAPI?.setApiKey?.("DEEPSEEK_API_KEY", deepseekApiKey);
     }
   }, [deepseekApiKey]);
 
   useEffect(() => {
     if (customReasoningApiKey.trim()) {
-      void window.electronAPI?.setEnvVar?.("CUSTOM_REASONING_API_KEY", customReasoningApiKey);
+      void window.electronAPI?.setApiKey?.("CUSTOM_REASONING_API_KEY", customReasoningApiKey);
     }
   }, [customReasoningApiKey]);
 
+  // A setting was written (possibly by another window): pick up the new value here.
+  // Setting the same value again is a no-op, so this doesn't echo back and forth.
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+    void onSettingChanged((key, value) => {
+      const text = typeof value === "string" ? value : null;
+      const flag = typeof value === "boolean" ? value : null;
+      switch (key) {
+        case "preferredLanguage":
+          if (text) setPreferredLanguage(text);
+          break;
+        case "cloudTranscriptionProvider":
+          if (text) setCloudTranscriptionProvider(text);
+          break;
+        case "cloudTranscriptionModel":
+          if (text) setCloudTranscriptionModel(text);
+          break;
+        case "cloudTranscriptionBaseUrl":
+          if (text !== null) setCloudTranscriptionBaseUrl(text);
+          break;
+        case "cloudReasoningBaseUrl":
+          if (text !== null) setCloudReasoningBaseUrl(text);
+          break;
+        case "reasoningModel":
+          if (text !== null) setReasoningModel(text);
+          break;
+        case "useReasoningModel":
+          if (flag !== null) setUseReasoningModel(flag);
+          break;
+        case "muteSystemAudioWhileRecording":
+          if (flag !== null) setMuteSystemAudioWhileRecording(flag);
+          break;
+        case PROCESSING_MODE_STORAGE_KEY:
+          if (isProcessingModeId(value)) setProcessingModeId(value);
+          break;
+        case "recordingOverlayVisualStyle":
+          if (value === "classic" || value === "dual" || value === "timeline") {
+            setRecordingOverlayVisualStyle(value);
+          }
+          break;
+        case "activationMode":
+          if (value === "tap" || value === "push") setActivationMode(value);
+          break;
+        case "dictationTriggerMode":
+          if (value === "single" || value === "double") setDictationTriggerMode(value);
+          break;
+      }
+    }).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [
+    setPreferredLanguage,
+    setCloudTranscriptionProvider,
+    setCloudTranscriptionModel,
+    setCloudTranscriptionBaseUrl,
+    setCloudReasoningBaseUrl,
+    setReasoningModel,
+    setUseReasoningModel,
+    setMuteSystemAudioWhileRecording,
+    setProcessingModeId,
+    setRecordingOverlayVisualStyle,
+    setActivationMode,
+    setDictationTriggerMode,
+  ]);
+
   // Batch operations
   const updateTranscriptionSettings = useCallback(
     (settings: Partial<TranscriptionSettings>) => {
@@ -277,6 +277,20 @@ const EN_US: Record<string, string> = {
   "vocabulary.noHotwordSuggestions": "No hotword suggestions",
   "vocabulary.backToEdit": "Back to Edit",
   "vocabulary.addSelected": "Add Selected ({count})",
+  "substitutions.title": "Substitution rules",
+  "substitutions.desc":
+    "Regular-expression find and replace, applied in order to every transcript. Use $1 to insert a capture group.",
+  "substitutions.pattern": "Pattern, e.g. \\bteh\\b",
+  "substitutions.replacement": "Replacement",
+  "substitutions.flags": "Flags",
+  "substitutions.flags.help": "i ignore case, m multi-line, s dot matches newline, x verbose, U lazy",
+  "substitutions.add": "Add rule",
+  "substitutions.moveUp": "Move up",
+  "substitutions.moveDown": "Move down",
+  "substitutions.remove": "Remove rule",
+  "substitutions.empty": "(remove match)",
+  "substitutions.none": "No substitution rules yet.",
+  "substitutions.test.placeholder": "Type sample text to preview the rules...",
   "settings.overlayVisualStyle.title": "Recording overlay animation",
   "settings.overlayVisualStyle.desc":
     "Choose the motion style used by the floating recording overlay.",
@@ -492,6 +506,10 @@ const EN_US: Record<string, string> = {
   "transcription.testConnection.failed": "Connection failed",
   "transcription.testConnection.error": "Connection error",
   "transcription.testConnection.missingFields": "Please fill in Endpoint URL and Model Name",
+  "transcription.testKey.button": "Test key",
+  "transcription.testKey.testing": "Testing...",
+  "transcription.testKey.valid": "Key accepted",
+  "transcription.testKey.invalid": "The provider rejected this key",
 
   "transcription.setDefaultModel": "Set as Default",
   "transcription.defaultModel": "Default",
@@ -1006,6 +1024,19 @@ const ZH_CN: Record<string, string> = {
   "vocabulary.noHotwordSuggestions": "没有热词建议",
   "vocabulary.backToEdit": "返回修改",
   "vocabulary.addSelected": "添加选中项（{count}）",
+  "substitutions.title": "替换规则",
+  "substitutions.desc": "按顺序对每次转写结果执行正则查找替换。可用 $1 插入捕获组。",
+  "substitutions.pattern": "正则，例如 \\bteh\\b",
+  "substitutions.replacement": "替换为",
+  "substitutions.flags": "标志",
+  "substitutions.flags.help": "i 忽略大小写，m 多行，s 点号匹配换行，x 宽松模式，U 非贪婪",
+  "substitutions.add": "添加规则",
+  "substitutions.moveUp": "上移",
+  "substitutions.moveDown": "下移",
+  "substitutions.remove": "删除规则",
+  "substitutions.empty": "（删除匹配内容）",
+  "substitutions.none": "还没有替换规则。",
+  "substitutions.test.placeholder": "输入示例文本以预览规则效果...",
   "settings.overlayVisualStyle.title": "录音浮窗动效",
   "settings.overlayVisualStyle.desc": "选择录音浮窗中的声音可视化动效。",
   "settings.overlayVisualStyle.timeline": "时间线粒子",
@@ -1201,6 +1232,10 @@ const ZH_CN: Record<string, string> = {
   "transcription.testConnection.failed": "连接失败",
   "transcription.testConnection.error": "连接错误",
   "transcription.testConnection.missingFields": "请填写端点 URL 和模型名称",
+  "transcription.testKey.button": "测试密钥",
+  "transcription.testKey.testing": "测试中...",
+  "transcription.testKey.valid": "密钥有效",
+  "transcription.testKey.invalid": "服务商拒绝了此密钥",
 
   "transcription.setDefaultModel": "设为默认",
   "transcription.defaultModel": "默认",
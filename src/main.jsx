@@ -24,8 +24,9 @@ function AppRouter() {
       typeof window.__TAURI__ !== "undefined" ||
       /\bTauri\b/i.test(navigator.userAgent || ""));
 
-  // In Tauri, the recording overlay lives in a dedicated NSPanel window
-  // (created from Rust via `tauri-nspanel`) and navigates with `?overlay=true`.
+  // In Tauri, the recording overlay lives in a dedicated window created from Rust (an NSPanel
+  // via `tauri-nspanel` on macOS, a borderless always-on-top window elsewhere) and navigates
+  // with `?overlay=true`.
   if (isTauri && isRecordingOverlay) return <RecordingOverlay />;
 
   // Control panel
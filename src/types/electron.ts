@@ -62,6 +62,7 @@ export interface UpdateCheckResult {
   releaseDate?: string;
   files?: any[];
   releaseNotes?: string;
+  releaseUrl?: string;
   message?: string;
 }
 
@@ -151,6 +152,8 @@ declare global {
       saveAllKeysToEnv: () => Promise<{ success: boolean; error?: string; message?: string }>;
       getEnvVar?: (key: string) => Promise<string | null>;
       setEnvVar?: (key: string, value: string) => Promise<void>;
+      getApiKey?: (key: string) => Promise<string | null>;
+      setApiKey?: (key: string, value: string) => Promise<void>;
 
       // Clipboard operations
       readClipboard: () => Promise<string>;
@@ -158,6 +161,11 @@ declare global {
       writeClipboardImage?: (dataUrl: string) => Promise<void>;
       checkPasteTools: () => Promise<PasteToolsResult>;
       checkAccessibilityPermission?: (prompt?: boolean) => Promise<boolean>;
+      requestAccessibilityPermission?: () => Promise<void>;
+      checkMicrophonePermission?: () => Promise<
+        "granted" | "denied" | "restricted" | "not-determined"
+      >;
+      requestMicrophonePermission?: () => Promise<boolean>;
 
       // Settings storage (app_data_dir/settings.json)
       getSetting?: (key: string) => Promise<any | null>;
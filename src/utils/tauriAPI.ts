@@ -20,6 +20,24 @@ export interface Transcription {
   processing_method: string;
   agent_name: string | null;
   error: string | null;
+  latency_ms?: number | null;
+  word_count?: number | null;
+  audio_seconds?: number | null;
+}
+
+export interface DailyDictationStats {
+  day: string;
+  sessions: number;
+  words: number;
+}
+
+export interface DictationStats {
+  sessions: number;
+  total_words: number;
+  total_audio_seconds: number;
+  average_latency_ms: number | null;
+  current_streak_days: number;
+  by_day: DailyDictationStats[];
 }
 
 export interface TranscriptionProvider {
@@ -102,6 +120,17 @@ export async function pasteText(text: string): Promise<void> {
   }
 }
 
+/** Type `text` with simulated keystrokes, for apps that ignore the paste shortcut. */
+export async function pasteTextByTyping(text: string): Promise<void> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("paste_text_by_typing", { text });
+  } catch (error) {
+    console.warn("pasteTextByTyping failed:", error);
+    throw error;
+  }
+}
+
 export async function pasteImage(dataUrl: string): Promise<void> {
   try {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -160,6 +189,37 @@ export async function checkAccessibilityPermission(prompt = false): Promise<bool
   }
 }
 
+export type MicrophonePermissionStatus = "granted" | "denied" | "restricted" | "not-determined";
+
+export async function checkMicrophonePermission(): Promise<MicrophonePermissionStatus> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("check_microphone_permission");
+  } catch (error) {
+    console.warn("checkMicrophonePermission failed:", error);
+    return "not-determined";
+  }
+}
+
+export async function requestMicrophonePermission(): Promise<boolean> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("request_microphone_permission");
+  } catch (error) {
+    console.warn("requestMicrophonePermission failed:", error);
+    return false;
+  }
+}
+
+export async function requestAccessibilityPermission(): Promise<void> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("request_accessibility_permission");
+  } catch (error) {
+    console.warn("requestAccessibilityPermission failed:", error);
+  }
+}
+
 // ============================================================================
 // Database Functions
 // ============================================================================
@@ -194,6 +254,17 @@ export async function getTranscriptions(limit?: number): Promise<Transcription[]
   }
 }
 
+/** Dictation totals over the last `rangeDays` days, or all time when omitted. */
+export async function getDictationStats(rangeDays?: number): Promise<DictationStats | null> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("db_get_dictation_stats", { rangeDays });
+  } catch (error) {
+    console.warn("getDictationStats failed:", error);
+    return null;
+  }
+}
+
 export async function deleteTranscription(id: number): Promise<CommandResult> {
   try {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -326,6 +397,113 @@ export async function getTranscriptionProviders(): Promise<TranscriptionProvider
   }
 }
 
+/**
+ * Check the stored API key for a transcription provider: true when accepted, false when the
+ * provider rejects it. Network and other failures throw the backend's error message.
+ */
+export async function validateApiKey(provider: string): Promise<boolean> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<boolean>("validate_api_key", { provider });
+  } catch (error) {
+    console.warn("validateApiKey failed:", error);
+    throw error;
+  }
+}
+
+/** Whether the provider's API host is reachable; false when offline or on error. */
+export async function pingTranscriptionProvider(provider: string): Promise<boolean> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<boolean>("ping_transcription_provider", { provider });
+  } catch (error) {
+    console.warn("pingTranscriptionProvider failed:", error);
+    return false;
+  }
+}
+
+export async function getPendingRecordingsCount(): Promise<number> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<number>("get_pending_recordings_count");
+  } catch (error) {
+    console.warn("getPendingRecordingsCount failed:", error);
+    return 0;
+  }
+}
+
+export async function cancelTranscription(): Promise<void> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("cancel_transcription");
+  } catch (error) {
+    console.warn("cancelTranscription failed:", error);
+    throw error;
+  }
+}
+
+export async function onTranscriptionCancelled(callback: () => void): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen("backend-transcription-cancelled", () => callback());
+  } catch (error) {
+    console.warn("onTranscriptionCancelled failed:", error);
+    return () => {};
+  }
+}
+
+export type PendingRecordingTranscribed = {
+  id: number | null;
+  text: string;
+  recordedAt: number;
+  remaining: number;
+};
+
+export async function onPendingRecordingTranscribed(
+  callback: (payload: PendingRecordingTranscribed) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<PendingRecordingTranscribed>("backend-pending-recording-transcribed", (event) => {
+      callback(event.payload);
+    });
+  } catch (error) {
+    console.warn("onPendingRecordingTranscribed failed:", error);
+    return () => {};
+  }
+}
+
+export type PendingRecordingFailed = {
+  recordedAt: number;
+  error: string;
+  audioPath: string | null;
+  remaining: number;
+};
+
+/** A queued recording was given up on; its audio is kept at `audioPath`. */
+export async function onPendingRecordingFailed(
+  callback: (payload: PendingRecordingFailed) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<PendingRecordingFailed>("backend-pending-recording-failed", (event) => {
+      callback(event.payload);
+    });
+  } catch (error) {
+    console.warn("onPendingRecordingFailed failed:", error);
+    return () => {};
+  }
+}
+
 // ============================================================================
 // System Audio Ducking
 // ============================================================================
@@ -463,6 +641,86 @@ export async function setEnvVar(key: string, value: string): Promise<void> {
   }
 }
 
+/** API keys live in the OS credential store; keys not found there fall back to .env. */
+export async function getApiKey(key: string): Promise<string | null> {
+  if (!hasTauriRuntime()) {
+    return null;
+  }
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("get_api_key", { key });
+  } catch (error) {
+    console.warn("getApiKey failed:", error);
+    return null;
+  }
+}
+
+export async function setApiKey(key: string, value: string): Promise<void> {
+  if (!hasTauriRuntime()) {
+    return;
+  }
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("set_api_key", { key, value });
+  } catch (error) {
+    console.warn("setApiKey failed:", error);
+  }
+}
+
+/** Fires in every window when a backend setting is written. */
+export async function onSettingChanged(
+  callback: (key: string, value: unknown) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<{ key: string; value: unknown }>("setting-changed", (event) => {
+      callback(String(event.payload?.key ?? ""), event.payload?.value ?? null);
+    });
+  } catch (error) {
+    console.warn("onSettingChanged failed:", error);
+    return () => {};
+  }
+}
+
+/** Fires when an API key is set or cleared; only the key name is sent. */
+export async function onEnvVarChanged(callback: (key: string) => void): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<{ key: string }>("env-var-changed", (event) => {
+      callback(String(event.payload?.key ?? ""));
+    });
+  } catch (error) {
+    console.warn("onEnvVarChanged failed:", error);
+    return () => {};
+  }
+}
+
+export type TextSubstitutionRule = {
+  pattern: string;
+  replacement: string;
+  flags: string;
+};
+
+export async function testTextSubstitution(
+  text: string,
+  rules: TextSubstitutionRule[]
+): Promise<string> {
+  if (!hasTauriRuntime()) {
+    return text;
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke("test_text_substitution", { text, rules });
+}
+
 // =========================================================================
 // Logging
 // =========================================================================
@@ -534,6 +792,25 @@ export async function getAllSettings(): Promise<Record<string, unknown>> {
   }
 }
 
+export interface SettingDescription {
+  key: string;
+  defaultValue: unknown;
+  /** What a valid value looks like, e.g. "an integer between 0 and 1000". */
+  expected: string;
+  description: string;
+}
+
+/** Known backend settings with their defaults; `setSetting` rejects values they don't allow. */
+export async function getSettingsSchema(): Promise<SettingDescription[]> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("get_settings_schema");
+  } catch (error) {
+    console.warn("getSettingsSchema failed:", error);
+    return [];
+  }
+}
+
 export async function getDebugState(): Promise<DebugState> {
   try {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -588,73 +865,140 @@ export async function openLogsFolder(): Promise<CommandResult> {
   }
 }
 
+/** Zip logs, redacted settings and system info; `path` is the archive on success. */
+export async function generateDiagnosticReport(): Promise<CommandResult & { path?: string }> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const path = await invoke<string>("generate_diagnostic_report");
+    return { success: true, path };
+  } catch (error) {
+    console.warn("generateDiagnosticReport failed:", error);
+    return { success: false, error: getErrorMessage(error) };
+  }
+}
+
+export async function onDiagnosticReportGenerated(
+  callback: (path: string) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<{ path: string }>("diagnostic-report-generated", (event) => {
+      callback(String(event.payload?.path ?? ""));
+    });
+  } catch (error) {
+    console.warn("onDiagnosticReportGenerated failed:", error);
+    return () => {};
+  }
+}
+
+/** Export settings to `destPath`. API key values are never included, only which are set. */
+export async function exportSettings(destPath: string): Promise<CommandResult> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("export_settings", { destPath });
+    return { success: true };
+  } catch (error) {
+    console.warn("exportSettings failed:", error);
+    return { success: false, error: getErrorMessage(error) };
+  }
+}
+
+export async function importSettings(path: string): Promise<CommandResult & { keys?: string[] }> {
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const keys = await invoke<string[]>("import_settings", { path });
+    return { success: true, keys };
+  } catch (error) {
+    console.warn("importSettings failed:", error);
+    return { success: false, error: getErrorMessage(error) };
+  }
+}
+
+export async function onSettingsImported(callback: (keys: string[]) => void): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<string[]>("settings-imported", (event) => {
+      callback(Array.isArray(event.payload) ? event.payload : []);
+    });
+  } catch (error) {
+    console.warn("onSettingsImported failed:", error);
+    return () => {};
+  }
+}
+
 // API Key helpers
 export async function getOpenAIKey(): Promise<string | null> {
-  return getEnvVar("OPENAI_API_KEY");
+  return getApiKey("OPENAI_API_KEY");
 }
 
 export async function saveOpenAIKey(key: string): Promise<void> {
-  return setEnvVar("OPENAI_API_KEY", key);
+  return setApiKey("OPENAI_API_KEY", key);
 }
 
 export async function getAssemblyAIKey(): Promise<string | null> {
-  return getEnvVar("ASSEMBLYAI_API_KEY");
+  return getApiKey("ASSEMBLYAI_API_KEY");
 }
 
 export async function saveAssemblyAIKey(key: string): Promise<void> {
-  return setEnvVar("ASSEMBLYAI_API_KEY", key);
+  return setApiKey("ASSEMBLYAI_API_KEY", key);
 }
 
 export async function getAnthropicKey(): Promise<string | null> {
-  return getEnvVar("ANTHROPIC_API_KEY");
+  return getApiKey("ANTHROPIC_API_KEY");
 }
 
 export async function saveAnthropicKey(key: string): Promise<void> {
-  return setEnvVar("ANTHROPIC_API_KEY", key);
+  return setApiKey("ANTHROPIC_API_KEY", key);
 }
 
 export async function getGeminiKey(): Promise<string | null> {
-  return getEnvVar("GEMINI_API_KEY");
+  return getApiKey("GEMINI_API_KEY");
 }
 
 export async function saveGeminiKey(key: string): Promise<void> {
-  return setEnvVar("GEMINI_API_KEY", key);
+  return setApiKey("GEMINI_API_KEY", key);
 }
 
 export async function getGroqKey(): Promise<string | null> {
-  return getEnvVar("GROQ_API_KEY");
+  return getApiKey("GROQ_API_KEY");
 }
 
 export async function saveGroqKey(key: string): Promise<void> {
-  return setEnvVar("GROQ_API_KEY", key);
+  return setApiKey("GROQ_API_KEY", key);
 }
 
 export async function getZaiKey(): Promise<string | null> {
-  return getEnvVar("ZAI_API_KEY");
+  return getApiKey("ZAI_API_KEY");
 }
 
 export async function saveZaiKey(key: string): Promise<void> {
-  return setEnvVar("ZAI_API_KEY", key);
+  return setApiKey("ZAI_API_KEY", key);
 }
 
 // Volcengine (豆包) credentials
 export async function getVolcengineAppId(): Promise<string | null> {
-  return getEnvVar("VOLCENGINE_APP_ID");
+  return getApiKey("VOLCENGINE_APP_ID");
 }
 export async function saveVolcengineAppId(value: string): Promise<void> {
-  return setEnvVar("VOLCENGINE_APP_ID", value);
+  return setApiKey("VOLCENGINE_APP_ID", value);
 }
 export async function getVolcengineAccessToken(): Promise<string | null> {
-  return getEnvVar("VOLCENGINE_ACCESS_TOKEN");
+  return getApiKey("VOLCENGINE_ACCESS_TOKEN");
 }
 export async function saveVolcengineAccessToken(value: string): Promise<void> {
-  return setEnvVar("VOLCENGINE_ACCESS_TOKEN", value);
+  return setApiKey("VOLCENGINE_ACCESS_TOKEN", value);
 }
 export async function getVolcengineResourceId(): Promise<string | null> {
-  return getEnvVar("VOLCENGINE_RESOURCE_ID");
+  return getApiKey("VOLCENGINE_RESOURCE_ID");
 }
 export async function saveVolcengineResourceId(value: string): Promise<void> {
-  return setEnvVar("VOLCENGINE_RESOURCE_ID", value);
+  return setApiKey("VOLCENGINE_RESOURCE_ID", value);
 }
 
 // =========================================================================
@@ -839,6 +1183,35 @@ export async function windowIsMaximized(): Promise<boolean> {
 
 type UnlistenFn = () => void;
 
+/** Stage timestamps (ms since the Unix epoch) of the dictation session an event belongs to. */
+export type DictationSessionTimings = {
+  recordStart: number | null;
+  recordStop: number | null;
+  transcribeStart: number | null;
+  transcribeEnd: number | null;
+  pasteEnd: number | null;
+};
+
+/** Every `backend-dictation-*` event carries its session id and timings next to its own fields. */
+export type BackendDictationEvent<T> = T & {
+  sessionId: string | null;
+  timings: DictationSessionTimings;
+};
+
+/** Processing step reported by `backend-dictation-progress`. */
+export type DictationProgressStage =
+  | "finalizing-audio"
+  | "uploading"
+  | "waiting-for-provider"
+  | "post-processing"
+  | "pasting";
+
+export type DictationProgress = {
+  stage: DictationProgressStage;
+  /** Only on `uploading`. */
+  detail?: { sentBytes: number; totalBytes: number };
+};
+
 export type VolcengineStreamingTranscriptPayload = {
   sessionId: string;
   text: string;
@@ -1003,9 +1376,12 @@ export async function onBackendDictationError(
   }
   try {
     const { listen } = await import("@tauri-apps/api/event");
-    return listen("backend-dictation-error", (event) => {
-      callback(String((event as any).payload ?? ""));
-    });
+    return listen<BackendDictationEvent<{ message: string }>>(
+      "backend-dictation-error",
+      (event) => {
+        callback(String(event.payload?.message ?? ""));
+      }
+    );
   } catch (error) {
     console.warn("onBackendDictationError failed:", error);
     return () => {};
@@ -1046,15 +1422,80 @@ export async function onBackendDictationRecording(
   }
   try {
     const { listen } = await import("@tauri-apps/api/event");
-    return listen("backend-dictation-recording", (event) => {
-      callback(Boolean((event as any).payload));
-    });
+    return listen<BackendDictationEvent<{ active: boolean }>>(
+      "backend-dictation-recording",
+      (event) => {
+        callback(Boolean(event.payload?.active));
+      }
+    );
   } catch (error) {
     console.warn("onBackendDictationRecording failed:", error);
     return () => {};
   }
 }
 
+/** `stage` is what the cancel interrupted: "recording", "paused" or "processing". */
+export async function onBackendDictationCancelled(
+  callback: (stage?: string) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<{ stage?: string }>("backend-dictation-cancelled", (event) =>
+      callback(event.payload?.stage)
+    );
+  } catch (error) {
+    console.warn("onBackendDictationCancelled failed:", error);
+    return () => {};
+  }
+}
+
+/** Fired instead of a result when the recording was shorter than `minRecordingSeconds`. */
+export async function onBackendDictationTooShort(
+  callback: (durationSeconds: number, minSeconds: number) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<{ durationSeconds: number; minSeconds: number }>(
+      "backend-dictation-too-short",
+      (event) => {
+        callback(
+          Number(event.payload?.durationSeconds ?? 0),
+          Number(event.payload?.minSeconds ?? 0)
+        );
+      }
+    );
+  } catch (error) {
+    console.warn("onBackendDictationTooShort failed:", error);
+    return () => {};
+  }
+}
+
+export async function onBackendDictationContinuousActive(
+  callback: (isActive: boolean) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<BackendDictationEvent<{ active: boolean }>>(
+      "backend-dictation-continuous-active",
+      (event) => {
+        callback(Boolean(event.payload?.active));
+      }
+    );
+  } catch (error) {
+    console.warn("onBackendDictationContinuousActive failed:", error);
+    return () => {};
+  }
+}
+
 export async function onBackendDictationProcessing(
   callback: (isProcessing: boolean) => void
 ): Promise<UnlistenFn> {
@@ -1063,25 +1504,37 @@ export async function onBackendDictationProcessing(
   }
   try {
     const { listen } = await import("@tauri-apps/api/event");
-    return listen("backend-dictation-processing", (event) => {
-      callback(Boolean((event as any).payload));
-    });
+    return listen<BackendDictationEvent<{ active: boolean }>>(
+      "backend-dictation-processing",
+      (event) => {
+        callback(Boolean(event.payload?.active));
+      }
+    );
   } catch (error) {
     console.warn("onBackendDictationProcessing failed:", error);
     return () => {};
   }
 }
 
+/** How a result reached the focused app. */
+export type DictationInsertMethod = "accessibility" | "paste" | "typing" | "clipboard";
+
 export async function onBackendDictationResult(
-  callback: (text: string) => void
+  callback: (text: string, repeated: boolean, method?: DictationInsertMethod) => void
 ): Promise<UnlistenFn> {
   if (!hasTauriRuntime()) {
     return () => {};
   }
   try {
     const { listen } = await import("@tauri-apps/api/event");
-    return listen("backend-dictation-result", (event) => {
-      callback(String((event as any).payload ?? ""));
+    return listen<
+      BackendDictationEvent<{ text: string; repeated: boolean; method?: DictationInsertMethod }>
+    >("backend-dictation-result", (event) => {
+      callback(
+        String(event.payload?.text ?? ""),
+        Boolean(event.payload?.repeated),
+        event.payload?.method
+      );
     });
   } catch (error) {
     console.warn("onBackendDictationResult failed:", error);
@@ -1089,6 +1542,80 @@ export async function onBackendDictationResult(
   }
 }
 
+export async function onBackendDictationCopied(
+  callback: (text: string) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<BackendDictationEvent<{ text: string }>>("backend-dictation-copied", (event) => {
+      callback(String(event.payload?.text ?? ""));
+    });
+  } catch (error) {
+    console.warn("onBackendDictationCopied failed:", error);
+    return () => {};
+  }
+}
+
+export async function onBackendDictationReadyToPaste(
+  callback: (text: string) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<BackendDictationEvent<{ text: string }>>(
+      "backend-dictation-ready-to-paste",
+      (event) => {
+        callback(String(event.payload?.text ?? ""));
+      }
+    );
+  } catch (error) {
+    console.warn("onBackendDictationReadyToPaste failed:", error);
+    return () => {};
+  }
+}
+
+export async function onBackendDictationProgress(
+  callback: (progress: DictationProgress) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<BackendDictationEvent<DictationProgress>>(
+      "backend-dictation-progress",
+      (event) => {
+        callback({ stage: event.payload.stage, detail: event.payload.detail });
+      }
+    );
+  } catch (error) {
+    console.warn("onBackendDictationProgress failed:", error);
+    return () => {};
+  }
+}
+
+export async function onDictationLanguageChanged(
+  callback: (language: string) => void
+): Promise<UnlistenFn> {
+  if (!hasTauriRuntime()) {
+    return () => {};
+  }
+  try {
+    const { listen } = await import("@tauri-apps/api/event");
+    return listen<{ language: string }>("dictation-language-changed", (event) => {
+      callback(String(event.payload?.language ?? ""));
+    });
+  } catch (error) {
+    console.warn("onDictationLanguageChanged failed:", error);
+    return () => {};
+  }
+}
+
 // ============================================================================
 // App Control
 // ============================================================================
@@ -1155,11 +1682,35 @@ export async function openAccessibilitySettings(): Promise<OpenSettingsResult> {
 // Updater compatibility
 // =========================================================================
 
+type UpdateInfo = {
+  current_version: string;
+  latest_version: string;
+  release_url: string;
+  changelog: string;
+  is_update_available: boolean;
+};
+
+/** Compares against the latest GitHub release; downloading is still manual. */
 export async function checkForUpdates(): Promise<UpdateCheckResult> {
-  return {
-    updateAvailable: false,
-    message: unavailableInTauriBuild("Automatic updates"),
-  };
+  if (!hasTauriRuntime()) {
+    return {
+      updateAvailable: false,
+      message: unavailableInTauriBuild("Automatic updates"),
+    };
+  }
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const info = await invoke<UpdateInfo>("check_for_update");
+    return {
+      updateAvailable: info.is_update_available,
+      version: info.latest_version,
+      releaseNotes: info.changelog,
+      releaseUrl: info.release_url,
+    };
+  } catch (error) {
+    console.warn("checkForUpdates failed:", error);
+    return { updateAvailable: false, message: getErrorMessage(error) };
+  }
 }
 
 export async function downloadUpdate(): Promise<{ success: boolean; message: string }> {
@@ -1380,7 +1931,7 @@ export async function setAutoStartEnabled(enabled: boolean): Promise<{ success:
 }
 
 export async function saveAllKeysToEnv(): Promise<CommandResult> {
-  // Keys are already saved individually via setEnvVar.
+  // Keys are already saved individually via setApiKey.
   return { success: true };
 }
 
@@ -1451,16 +2002,21 @@ export const electronAPICompat = {
 
   // Clipboard
   pasteText,
+  pasteTextByTyping,
   pasteImage,
   readClipboard,
   writeClipboard,
   writeClipboardImage,
   checkPasteTools,
   checkAccessibilityPermission,
+  requestAccessibilityPermission,
+  checkMicrophonePermission,
+  requestMicrophonePermission,
 
   // Database
   saveTranscription,
   getTranscriptions,
+  getDictationStats,
   deleteTranscription,
   deleteTranscriptions,
   clearTranscriptions,
@@ -1476,6 +2032,13 @@ export const electronAPICompat = {
   finishOpenAIRealtimeTranscription,
   cancelOpenAIRealtimeTranscription,
   getTranscriptionProviders,
+  pingTranscriptionProvider,
+  validateApiKey,
+  getPendingRecordingsCount,
+  cancelTranscription,
+  onTranscriptionCancelled,
+  onPendingRecordingTranscribed,
+  onPendingRecordingFailed,
 
   // Native Recording (macOS)
   startNativeRecording,
@@ -1491,10 +2054,21 @@ export const electronAPICompat = {
   setSetting,
   getEnvVar,
   setEnvVar,
+  getApiKey,
+  setApiKey,
+  onSettingChanged,
+  onEnvVarChanged,
+  testTextSubstitution,
   getAllSettings,
+  getSettingsSchema,
   getDebugState,
   setDebugLogging,
   openLogsFolder,
+  generateDiagnosticReport,
+  onDiagnosticReportGenerated,
+  exportSettings,
+  importSettings,
+  onSettingsImported,
   getAssemblyAIKey,
   getOpenAIKey,
   saveAssemblyAIKey,
@@ -1540,8 +2114,15 @@ export const electronAPICompat = {
   onBackendDictationShowWindow,
   onBackendDictationStartFeedback,
   onBackendDictationRecording,
+  onBackendDictationCancelled,
+  onBackendDictationTooShort,
+  onBackendDictationContinuousActive,
   onBackendDictationProcessing,
   onBackendDictationResult,
+  onBackendDictationCopied,
+  onBackendDictationReadyToPaste,
+  onBackendDictationProgress,
+  onDictationLanguageChanged,
 
   // App
   appQuit,
@@ -1616,7 +2197,7 @@ if (typeof window !== "undefined") {
     // ignore
   }
 
-  // Keep a minimal copy of dictation settings in the backend for the macOS "backend dictation"
+  // Keep a minimal copy of dictation settings in the backend for the "backend dictation"
   // pipeline (global hotkey -> record -> transcribe -> paste), which can run while the renderer
   // is throttled by fullscreen apps.
   //
@@ -1649,9 +2230,6 @@ if (typeof window !== "undefined") {
           await setSetting("recordingOverlayVisualStyle", recordingOverlayVisualStyle);
           await setSetting("muteSystemAudioWhileRecording", muteSystemAudioWhileRecording);
 
-          const isMac = /\bMac\b|\bDarwin\b/i.test(navigator.platform || navigator.userAgent || "");
-          if (!isMac) return;
-
           const provider = localStorage.getItem("cloudTranscriptionProvider") || "openai";
           const model = localStorage.getItem("cloudTranscriptionModel") || "";
           const preferredLanguage = localStorage.getItem("preferredLanguage") || "auto";